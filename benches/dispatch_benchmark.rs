@@ -9,11 +9,7 @@ struct BenchEvent {
     _data: String,
 }
 
-impl Event for BenchEvent {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-}
+impl Event for BenchEvent {}
 
 fn bench_single_listener(c: &mut Criterion) {
     let dispatcher = EventDispatcher::new();
@@ -12,11 +12,7 @@ struct EmailEvent {
 }
 
 #[cfg(feature = "async")]
-impl Event for EmailEvent {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-}
+impl Event for EmailEvent {}
 
 #[cfg(feature = "async")]
 #[tokio::main]
@@ -10,11 +10,7 @@ struct UserRegistered {
     _timestamp: u64,
 }
 
-impl Event for UserRegistered {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-}
+impl Event for UserRegistered {}
 
 #[derive(Debug, Clone)]
 struct OrderPlaced {
@@ -24,11 +20,7 @@ struct OrderPlaced {
     items: Vec<String>,
 }
 
-impl Event for OrderPlaced {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-}
+impl Event for OrderPlaced {}
 
 fn main() {
     println!("Mod Events - Basic Usage Example");
@@ -58,6 +50,7 @@ fn main() {
     });
 
     // Add middleware for logging
+    #[cfg(feature = "middleware")]
     dispatcher.add_middleware(|event: &dyn Event| {
         println!("🔍 Event: {}", event.event_name());
         true // Allow all events
@@ -0,0 +1,168 @@
+//! Actix integration (requires "actix" feature)
+//!
+//! Bridges the dispatcher and actix actors in both directions:
+//! [`forward_to`] delivers dispatched events to an actor's
+//! [`Recipient`], and [`DispatcherActor`] dispatches events sent to it as
+//! [`Publish`] messages. [`app_data`] shares the dispatcher with
+//! actix-web handlers.
+
+use crate::{Event, EventDispatcher, TypedListenerId};
+use actix::{Actor, Context, Handler, Message, Recipient};
+use std::sync::Arc;
+
+/// Wrap a shared dispatcher for `App::app_data`
+///
+/// Handlers then take `web::Data<EventDispatcher>`, which shares the same
+/// dispatcher as the `Arc`.
+///
+/// # Example
+///
+/// ```rust
+/// use actix_web::{web, App};
+/// use mod_events::actor::app_data;
+/// use mod_events::{Event, EventDispatcher};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone)]
+/// struct Visited;
+///
+/// impl Event for Visited {}
+///
+/// async fn index(dispatcher: web::Data<EventDispatcher>) -> &'static str {
+///     dispatcher.emit(Visited);
+///     "hello"
+/// }
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// let app = App::new()
+///     .app_data(app_data(dispatcher.clone()))
+///     .route("/", web::get().to(index));
+/// ```
+pub fn app_data(dispatcher: Arc<EventDispatcher>) -> actix_web::web::Data<EventDispatcher> {
+    actix_web::web::Data::from(dispatcher)
+}
+
+/// Forward every dispatched `T` to an actor as an `M` message
+///
+/// Messages are sent with `do_send`, so the dispatching thread never waits
+/// on the actor. Forwarding to a stopped actor is a no-op; unsubscribe
+/// the returned id to stop forwarding.
+///
+/// # Example
+///
+/// ```rust
+/// use actix::prelude::*;
+/// use mod_events::actor::forward_to;
+/// use mod_events::{Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct OrderPlaced {
+///     id: u64,
+/// }
+///
+/// impl Event for OrderPlaced {}
+///
+/// #[derive(Message)]
+/// #[rtype(result = "()")]
+/// struct ShipOrder(u64);
+///
+/// impl From<OrderPlaced> for ShipOrder {
+///     fn from(order: OrderPlaced) -> Self {
+///         ShipOrder(order.id)
+///     }
+/// }
+///
+/// struct Shipping;
+///
+/// impl Actor for Shipping {
+///     type Context = Context<Self>;
+/// }
+///
+/// impl Handler<ShipOrder> for Shipping {
+///     type Result = ();
+///
+///     fn handle(&mut self, order: ShipOrder, _: &mut Context<Self>) {
+///         println!("shipping order {}", order.0);
+///         System::current().stop();
+///     }
+/// }
+///
+/// let system = System::new();
+/// system.block_on(async {
+///     let shipping = Shipping.start();
+///     let dispatcher = EventDispatcher::new();
+///     forward_to::<OrderPlaced, ShipOrder>(&dispatcher, shipping.recipient());
+///     dispatcher.emit(OrderPlaced { id: 7 });
+/// });
+/// system.run().unwrap();
+/// ```
+pub fn forward_to<T, M>(dispatcher: &EventDispatcher, recipient: Recipient<M>) -> TypedListenerId<T>
+where
+    T: Event + Clone + 'static,
+    M: Message + From<T> + Send + 'static,
+    M::Result: Send,
+{
+    dispatcher.subscribe(move |event: &T| {
+        recipient.do_send(M::from(event.clone()));
+        Ok(())
+    })
+}
+
+/// Message asking a [`DispatcherActor`] to dispatch an event
+#[derive(Debug, Clone)]
+pub struct Publish<T>(pub T);
+
+impl<T: Event> Message for Publish<T> {
+    type Result = ();
+}
+
+/// Actor that dispatches the events sent to it
+///
+/// Lets actors that only know the actix message bus publish onto the
+/// dispatcher by sending [`Publish`] messages.
+///
+/// # Example
+///
+/// ```rust
+/// use actix::prelude::*;
+/// use mod_events::actor::{DispatcherActor, Publish};
+/// use mod_events::{Event, EventDispatcher};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone)]
+/// struct UserSignedUp;
+///
+/// impl Event for UserSignedUp {}
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// dispatcher.on(|_: &UserSignedUp| println!("welcome"));
+///
+/// System::new().block_on(async {
+///     let bus = DispatcherActor::new(dispatcher.clone()).start();
+///     bus.send(Publish(UserSignedUp)).await.unwrap();
+/// });
+/// assert_eq!(dispatcher.metrics().values().next().unwrap().dispatch_count, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DispatcherActor {
+    dispatcher: Arc<EventDispatcher>,
+}
+
+impl DispatcherActor {
+    /// Create an actor dispatching onto `dispatcher`
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
+
+impl Actor for DispatcherActor {
+    type Context = Context<Self>;
+}
+
+impl<T: Event> Handler<Publish<T>> for DispatcherActor {
+    type Result = ();
+
+    fn handle(&mut self, message: Publish<T>, _: &mut Context<Self>) {
+        self.dispatcher.emit(message.0);
+    }
+}
@@ -1,6 +1,6 @@
 //! Async event support (requires "async" feature)
 
-use crate::{Event, Priority};
+use crate::{Event, ListenerId, Priority};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -24,11 +24,7 @@ use std::sync::Arc;
 ///     email: String,
 /// }
 ///
-/// impl Event for UserRegistered {
-///     fn as_any(&self) -> &dyn std::any::Any {
-///         self
-///     }
-/// }
+/// impl Event for UserRegistered {}
 ///
 /// struct AsyncEmailNotifier;
 ///
@@ -54,11 +50,128 @@ pub trait AsyncEventListener<T: Event>: Send + Sync {
     fn priority(&self) -> Priority {
         Priority::Normal
     }
+
+    /// Called once the listener is registered, with its id
+    fn on_subscribe(&self, id: ListenerId) {
+        let _ = id;
+    }
+
+    /// Called before the first event is handled
+    ///
+    /// Concurrent first calls wait for a single run of the hook. Returning
+    /// an error fails that call; the hook runs again for the next event
+    /// until it succeeds. The hook must not await an async dispatch of
+    /// `T`, which would wait on the hook itself.
+    fn on_first_event<'a>(&'a self, event: &'a T) -> AsyncEventResult<'a> {
+        let _ = event;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called once the listener has been removed
+    ///
+    /// Runs when the listener is unsubscribed, when the dispatcher is
+    /// cleared or when it is dropped, after any calls still in flight.
+    fn on_unsubscribe(&self) {}
+}
+
+/// How synchronous dispatch treats async listeners (requires "async" feature)
+///
+/// [`dispatch`](crate::EventDispatcher::dispatch) and the other sync
+/// entry points cannot await futures. Set a bridge with
+/// [`EventDispatcher::set_async_bridge`](crate::EventDispatcher::set_async_bridge)
+/// to have them reach async listeners too.
+#[derive(Debug, Clone, Default)]
+pub enum AsyncBridge {
+    /// Leave async listeners out and count them in
+    /// [`DispatchResult::async_skipped`](crate::DispatchResult::async_skipped) (default)
+    #[default]
+    Skip,
+    /// Leave async listeners out as with `Skip`, and emit an
+    /// [`AsyncListenersSkipped`](crate::AsyncListenersSkipped) meta-event
+    ///
+    /// The warning reaches listeners of the meta-event once
+    /// [meta-events](crate::EventDispatcher::set_meta_events) are enabled,
+    /// and observers such as a logging observer.
+    Warn,
+    /// Record every async listener as failed with
+    /// [`ListenerError::AsyncUnsupported`](crate::ListenerError::AsyncUnsupported)
+    Error,
+    /// Run async listeners after the sync ones by blocking on this runtime
+    ///
+    /// The dispatching thread waits for them and their outcomes are part
+    /// of the result. Dispatches made from inside a Tokio runtime cannot
+    /// block, so they skip async listeners as with `Skip`.
+    Block(tokio::runtime::Handle),
+}
+
+/// [`AsyncEventListener`] driven through its lifecycle callbacks
+pub(crate) struct ManagedAsyncListener<T, L: AsyncEventListener<T>>
+where
+    T: Event,
+{
+    listener: L,
+    started: tokio::sync::OnceCell<()>,
+    _event: std::marker::PhantomData<fn(&T)>,
+}
+
+impl<T: Event, L: AsyncEventListener<T>> ManagedAsyncListener<T, L> {
+    pub(crate) fn new(listener: L) -> Self {
+        Self {
+            listener,
+            started: tokio::sync::OnceCell::new(),
+            _event: std::marker::PhantomData,
+        }
+    }
+
+    pub(crate) fn listener(&self) -> &L {
+        &self.listener
+    }
+
+    pub(crate) async fn handle(
+        &self,
+        event: &T,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.started
+            .get_or_try_init(|| self.listener.on_first_event(event))
+            .await?;
+        self.listener.handle(event).await
+    }
+}
+
+impl<T: Event, L: AsyncEventListener<T>> Drop for ManagedAsyncListener<T, L> {
+    fn drop(&mut self) {
+        self.listener.on_unsubscribe();
+    }
+}
+
+/// Shared [`AsyncEventListener`] trait object, forwarded to unchanged
+pub(crate) struct SharedAsyncListener<T: Event>(pub(crate) Arc<dyn AsyncEventListener<T>>);
+
+impl<T: Event> AsyncEventListener<T> for SharedAsyncListener<T> {
+    fn handle<'a>(&'a self, event: &'a T) -> AsyncEventResult<'a> {
+        self.0.handle(event)
+    }
+
+    fn priority(&self) -> Priority {
+        self.0.priority()
+    }
+
+    fn on_subscribe(&self, id: ListenerId) {
+        self.0.on_subscribe(id);
+    }
+
+    fn on_first_event<'a>(&'a self, event: &'a T) -> AsyncEventResult<'a> {
+        self.0.on_first_event(event)
+    }
+
+    fn on_unsubscribe(&self) {
+        self.0.on_unsubscribe();
+    }
 }
 
 /// Internal async listener wrapper
 /// Type alias for the async event handler function
-type AsyncEventHandler = dyn for<'a> Fn(
+pub(crate) type AsyncEventHandler = dyn for<'a> Fn(
         &'a dyn Event,
     ) -> Pin<
         Box<dyn Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>> + Send + 'a>,
@@ -69,6 +182,8 @@ pub(crate) struct AsyncListenerWrapper {
     pub(crate) handler: Arc<AsyncEventHandler>,
     pub(crate) priority: Priority,
     pub(crate) id: usize,
+    pub(crate) name: &'static str,
+    pub(crate) permits: Arc<tokio::sync::Semaphore>,
 }
 
 impl std::fmt::Debug for AsyncListenerWrapper {
@@ -76,28 +191,58 @@ impl std::fmt::Debug for AsyncListenerWrapper {
         f.debug_struct("AsyncListenerWrapper")
             .field("priority", &self.priority)
             .field("id", &self.id)
-            .field("handler", &"<async_function>")
+            .field("name", &self.name)
+            .field("available_permits", &self.permits.available_permits())
             .finish()
     }
 }
 
 impl AsyncListenerWrapper {
-    pub(crate) fn new<T, F, Fut>(listener: F, priority: Priority, id: usize) -> Self
+    pub(crate) fn new(
+        handler: Arc<AsyncEventHandler>,
+        priority: Priority,
+        id: usize,
+        name: &'static str,
+        max_concurrency: usize,
+    ) -> Self {
+        Self {
+            handler,
+            priority,
+            id,
+            name,
+            permits: Arc::new(tokio::sync::Semaphore::new(max_concurrency.max(1))),
+        }
+    }
+
+    /// Erase a closure returning a future
+    pub(crate) fn closure<T, F, Fut>(listener: F) -> Arc<AsyncEventHandler>
     where
         T: Event + 'static,
         F: Fn(&T) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>> + Send + 'static,
     {
-        Self {
-            handler: Arc::new(move |event: &dyn Event| {
-                if let Some(concrete_event) = event.as_any().downcast_ref::<T>() {
-                    Box::pin(listener(concrete_event))
-                } else {
-                    Box::pin(async { Ok(()) })
-                }
-            }),
-            priority,
-            id,
-        }
+        Arc::new(move |event: &dyn Event| {
+            if let Some(concrete_event) = event.downcast_ref::<T>() {
+                Box::pin(listener(concrete_event))
+            } else {
+                Box::pin(async { Ok(()) })
+            }
+        })
+    }
+
+    /// Erase an [`AsyncEventListener`] managed through its lifecycle
+    pub(crate) fn managed<T, L>(listener: Arc<ManagedAsyncListener<T, L>>) -> Arc<AsyncEventHandler>
+    where
+        T: Event + 'static,
+        L: AsyncEventListener<T> + 'static,
+    {
+        Arc::new(move |event: &dyn Event| {
+            if let Some(concrete_event) = event.downcast_ref::<T>() {
+                let listener = listener.clone();
+                Box::pin(async move { listener.handle(concrete_event).await })
+            } else {
+                Box::pin(async { Ok(()) })
+            }
+        })
     }
 }
@@ -0,0 +1,108 @@
+//! Authorization middleware
+
+use crate::{DispatchContext, Event, Middleware};
+use std::marker::PhantomData;
+
+/// Decides whether a principal may dispatch an event
+///
+/// The principal is read from the [`DispatchContext`] by type.
+pub trait Policy: Send + Sync {
+    /// Type identifying who is dispatching
+    type Principal: Send + Sync + 'static;
+
+    /// Check whether `principal` may dispatch `event`
+    fn allow(&self, principal: &Self::Principal, event: &dyn Event) -> bool;
+}
+
+/// Middleware that blocks events a [`Policy`] does not allow
+///
+/// Dispatches without a principal in their context are blocked too.
+/// Use [`EventDispatcher::dispatch_with_context`](crate::EventDispatcher::dispatch_with_context)
+/// to supply the principal; the block reason is available from
+/// [`DispatchResult::block_reason`](crate::DispatchResult::block_reason).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{AuthzMiddleware, DispatchContext, Event, EventDispatcher, Policy};
+///
+/// #[derive(Debug, Clone)]
+/// struct DeleteTenant {
+///     tenant: u32,
+/// }
+///
+/// impl Event for DeleteTenant {}
+///
+/// struct User {
+///     tenant: u32,
+///     admin: bool,
+/// }
+///
+/// struct TenantPolicy;
+///
+/// impl Policy for TenantPolicy {
+///     type Principal = User;
+///
+///     fn allow(&self, user: &User, event: &dyn Event) -> bool {
+///         match event.downcast_ref::<DeleteTenant>() {
+///             Some(delete) => user.admin && user.tenant == delete.tenant,
+///             None => true,
+///         }
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.add_middleware_layer(AuthzMiddleware::new(TenantPolicy));
+/// dispatcher.on(|_: &DeleteTenant| println!("tenant deleted"));
+///
+/// let intruder = DispatchContext::new().with(User { tenant: 2, admin: true });
+/// let result = dispatcher.dispatch_with_context(DeleteTenant { tenant: 1 }, &intruder);
+/// assert!(result.is_blocked());
+/// println!("{}", result.block_reason().unwrap());
+///
+/// let owner = DispatchContext::new().with(User { tenant: 1, admin: true });
+/// let result = dispatcher.dispatch_with_context(DeleteTenant { tenant: 1 }, &owner);
+/// assert!(result.all_succeeded());
+/// ```
+pub struct AuthzMiddleware<P> {
+    policy: P,
+    _principal: PhantomData<fn() -> P>,
+}
+
+impl<P: Policy> AuthzMiddleware<P> {
+    /// Create authorization middleware enforcing `policy`
+    pub fn new(policy: P) -> Self {
+        Self {
+            policy,
+            _principal: PhantomData,
+        }
+    }
+}
+
+impl<P> std::fmt::Debug for AuthzMiddleware<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthzMiddleware")
+            .field("policy", &std::any::type_name::<P>())
+            .finish()
+    }
+}
+
+impl<P: Policy> Middleware for AuthzMiddleware<P> {
+    fn process(&self, event: &dyn Event, context: &DispatchContext) -> Result<(), String> {
+        let Some(principal) = context.get::<P::Principal>() else {
+            return Err(format!(
+                "no {} in dispatch context",
+                std::any::type_name::<P::Principal>()
+            ));
+        };
+
+        if self.policy.allow(principal, event) {
+            Ok(())
+        } else {
+            Err(format!(
+                "principal is not allowed to dispatch {}",
+                event.event_name()
+            ))
+        }
+    }
+}
@@ -0,0 +1,465 @@
+//! Background dispatch on a worker thread pool
+
+use crate::sync::{Condvar, Mutex};
+use crate::{BackpressurePolicy, Clock, DispatchResult, Priority};
+use std::any::TypeId;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type Job = Box<dyn FnOnce() + Send>;
+type QueueBoost = Arc<dyn Fn(Duration) -> u32 + Send + Sync>;
+
+/// How the worker pool orders queued background dispatches
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, QueueFairness};
+/// use std::sync::{Arc, Barrier, Mutex};
+///
+/// #[derive(Debug, Clone)]
+/// struct Hold;
+///
+/// impl Event for Hold {}
+///
+/// #[derive(Debug, Clone)]
+/// struct Telemetry;
+///
+/// impl Event for Telemetry {}
+///
+/// #[derive(Debug, Clone)]
+/// struct Payment;
+///
+/// impl Event for Payment {}
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// dispatcher.set_background_threads(1);
+/// dispatcher.set_queue_fairness(QueueFairness::RoundRobin);
+/// dispatcher.set_queue_weight::<Payment>(2);
+///
+/// let order = Arc::new(Mutex::new(Vec::new()));
+/// let (telemetry, payments) = (order.clone(), order.clone());
+/// dispatcher.on(move |_: &Telemetry| telemetry.lock().unwrap().push("telemetry"));
+/// dispatcher.on(move |_: &Payment| payments.lock().unwrap().push("payment"));
+///
+/// // Keep the only worker busy while the queue fills up
+/// let release = Arc::new(Barrier::new(2));
+/// let held = release.clone();
+/// dispatcher.on(move |_: &Hold| {
+///     held.wait();
+/// });
+/// dispatcher.dispatch_background(Hold);
+///
+/// let mut handles: Vec<_> = (0..4).map(|_| dispatcher.dispatch_background(Telemetry)).collect();
+/// handles.extend((0..2).map(|_| dispatcher.dispatch_background(Payment)));
+/// release.wait();
+/// for handle in handles {
+///     handle.wait().unwrap();
+/// }
+///
+/// assert_eq!(
+///     *order.lock().unwrap(),
+///     ["telemetry", "payment", "payment", "telemetry", "telemetry", "telemetry"]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QueueFairness {
+    /// Run dispatches in the order they were queued (default)
+    #[default]
+    Fifo,
+    /// Take turns across event types, giving each type as many
+    /// consecutive dispatches per turn as its weight
+    RoundRobin,
+    /// Run the highest-priority dispatch first, in queue order among
+    /// equals
+    ///
+    /// Priorities come from
+    /// [`dispatch_background_with_priority`](crate::EventDispatcher::dispatch_background_with_priority).
+    /// Without [aging](crate::EventDispatcher::set_queue_aging), a steady
+    /// stream of high-priority dispatches can starve lower ones.
+    Priority,
+}
+
+/// Pending jobs, queued in order or per event type
+#[derive(Default)]
+struct JobQueue {
+    fairness: QueueFairness,
+    weights: HashMap<TypeId, u32>,
+    fifo: VecDeque<Job>,
+    per_type: HashMap<TypeId, VecDeque<Job>>,
+    ready: VecDeque<TypeId>,
+    served: u32,
+    prioritized: Vec<PrioritizedJob>,
+    aging: Option<Duration>,
+    boosts: HashMap<TypeId, QueueBoost>,
+    closed: bool,
+}
+
+impl JobQueue {
+    /// Queue a job; `now` is when it was queued, on the dispatcher's clock
+    fn push(&mut self, type_id: TypeId, priority: Priority, job: Job, now: Instant) {
+        match self.fairness {
+            QueueFairness::Fifo => self.fifo.push_back(job),
+            QueueFairness::Priority => self.prioritized.push(PrioritizedJob {
+                type_id,
+                priority,
+                queued_at: now,
+                job,
+            }),
+            QueueFairness::RoundRobin => self
+                .per_type
+                .entry(type_id)
+                .or_insert_with(|| {
+                    self.ready.push_back(type_id);
+                    VecDeque::new()
+                })
+                .push_back(job),
+        }
+    }
+
+    fn pop(&mut self, now: Instant) -> Option<Job> {
+        if let Some(job) = self.fifo.pop_front() {
+            return Some(job);
+        }
+        if !self.prioritized.is_empty() {
+            return Some(self.pop_prioritized(now));
+        }
+
+        let type_id = *self.ready.front()?;
+        let queue = self.per_type.get_mut(&type_id)?;
+        let job = queue.pop_front();
+        self.served += 1;
+        if queue.is_empty() {
+            self.per_type.remove(&type_id);
+            self.ready.pop_front();
+            self.served = 0;
+        } else if self.served >= self.weights.get(&type_id).copied().unwrap_or(1) {
+            self.ready.rotate_left(1);
+            self.served = 0;
+        }
+        job
+    }
+
+    /// Estimate the bytes held by queued jobs and the queues themselves
+    fn footprint(&self) -> usize {
+        use std::mem::{size_of, size_of_val};
+
+        let jobs = |jobs: &VecDeque<Job>| {
+            jobs.capacity() * size_of::<Job>()
+                + jobs.iter().map(|job| size_of_val(&**job)).sum::<usize>()
+        };
+        let fifo = jobs(&self.fifo);
+        let per_type = self.per_type.capacity() * size_of::<(TypeId, VecDeque<Job>)>()
+            + self.per_type.values().map(jobs).sum::<usize>()
+            + self.ready.capacity() * size_of::<TypeId>();
+        let prioritized = self.prioritized.capacity() * size_of::<PrioritizedJob>()
+            + self
+                .prioritized
+                .iter()
+                .map(|job| size_of_val(&*job.job))
+                .sum::<usize>();
+        fifo + per_type + prioritized
+    }
+
+    /// Remove the queued job with the highest effective priority
+    ///
+    /// A boost registered for the job's event type decides how far it
+    /// rises while waiting; otherwise, with aging, each full aging period
+    /// spent waiting raises it by one priority level.
+    fn pop_prioritized(&mut self, now: Instant) -> Job {
+        let score = |job: &PrioritizedJob| {
+            let waited = now.saturating_duration_since(job.queued_at);
+            let boost = match (self.boosts.get(&job.type_id), self.aging) {
+                (Some(boost), _) => boost(waited) as u128,
+                (None, Some(rate)) if !rate.is_zero() => {
+                    waited.as_nanos() / rate.as_nanos() * PRIORITY_LEVEL
+                }
+                (None, _) => 0,
+            };
+            job.priority as u128 + boost
+        };
+
+        let mut best = 0;
+        let mut best_score = score(&self.prioritized[0]);
+        for (index, job) in self.prioritized.iter().enumerate().skip(1) {
+            let score = score(job);
+            if score > best_score {
+                best = index;
+                best_score = score;
+            }
+        }
+        self.prioritized.remove(best).job
+    }
+}
+
+/// Distance between adjacent [`Priority`] levels
+const PRIORITY_LEVEL: u128 = 25;
+
+struct PrioritizedJob {
+    type_id: TypeId,
+    priority: Priority,
+    queued_at: Instant,
+    job: Job,
+}
+
+struct Shared {
+    queue: Mutex<JobQueue>,
+    available: Condvar,
+}
+
+/// Lazily started pool of worker threads
+///
+/// Threads are spawned on the first job. Dropping the pool, or resizing
+/// it, lets the current threads finish queued jobs and exit.
+pub(crate) struct WorkerPool {
+    threads: usize,
+    fairness: QueueFairness,
+    weights: HashMap<TypeId, u32>,
+    aging: Option<Duration>,
+    boosts: HashMap<TypeId, QueueBoost>,
+    /// Time source for queue aging, shared with the dispatcher
+    clock: Arc<dyn Clock>,
+    shared: Option<Arc<Shared>>,
+}
+
+impl WorkerPool {
+    pub(crate) fn new(threads: usize, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            threads: threads.max(1),
+            fairness: QueueFairness::default(),
+            weights: HashMap::new(),
+            aging: None,
+            boosts: HashMap::new(),
+            clock,
+            shared: None,
+        }
+    }
+
+    pub(crate) fn threads(&self) -> usize {
+        self.threads
+    }
+
+    pub(crate) fn resize(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+        self.close();
+    }
+
+    pub(crate) fn fairness(&self) -> QueueFairness {
+        self.fairness
+    }
+
+    pub(crate) fn set_fairness(&mut self, fairness: QueueFairness) {
+        self.fairness = fairness;
+        if let Some(shared) = &self.shared {
+            shared.queue.lock().unwrap().fairness = fairness;
+        }
+    }
+
+    pub(crate) fn set_weight(&mut self, type_id: TypeId, weight: u32) {
+        let weight = weight.max(1);
+        self.weights.insert(type_id, weight);
+        if let Some(shared) = &self.shared {
+            shared.queue.lock().unwrap().weights.insert(type_id, weight);
+        }
+    }
+
+    pub(crate) fn aging(&self) -> Option<Duration> {
+        self.aging
+    }
+
+    pub(crate) fn set_aging(&mut self, aging: Option<Duration>) {
+        self.aging = aging;
+        if let Some(shared) = &self.shared {
+            shared.queue.lock().unwrap().aging = aging;
+        }
+    }
+
+    pub(crate) fn set_boost(&mut self, type_id: TypeId, boost: QueueBoost) {
+        self.boosts.insert(type_id, boost.clone());
+        if let Some(shared) = &self.shared {
+            shared.queue.lock().unwrap().boosts.insert(type_id, boost);
+        }
+    }
+
+    pub(crate) fn execute(&mut self, type_id: TypeId, priority: Priority, job: Job) {
+        let shared = match &self.shared {
+            Some(shared) => shared,
+            None => self.shared.insert(self.start()),
+        };
+        let now = self.clock.now();
+        shared
+            .queue
+            .lock()
+            .unwrap()
+            .push(type_id, priority, job, now);
+        shared.available.notify_one();
+    }
+
+    /// Estimate the bytes held by jobs waiting for a worker
+    pub(crate) fn footprint(&self) -> usize {
+        self.shared
+            .as_ref()
+            .map_or(0, |shared| shared.queue.lock().unwrap().footprint())
+    }
+
+    fn start(&self) -> Arc<Shared> {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(JobQueue {
+                fairness: self.fairness,
+                weights: self.weights.clone(),
+                aging: self.aging,
+                boosts: self.boosts.clone(),
+                ..JobQueue::default()
+            }),
+            available: Condvar::new(),
+        });
+        for index in 0..self.threads {
+            let shared = shared.clone();
+            let clock = self.clock.clone();
+            std::thread::Builder::new()
+                .name(format!("mod-events-worker-{}", index))
+                .spawn(move || loop {
+                    let job = {
+                        let mut queue = shared.queue.lock().unwrap();
+                        loop {
+                            if let Some(job) = queue.pop(clock.now()) {
+                                break Some(job);
+                            }
+                            if queue.closed {
+                                break None;
+                            }
+                            queue = shared.available.wait(queue).unwrap();
+                        }
+                    };
+                    match job {
+                        Some(job) => job(),
+                        None => break,
+                    }
+                })
+                .expect("failed to spawn event worker thread");
+        }
+        shared
+    }
+
+    fn close(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            shared.queue.lock().unwrap().closed = true;
+            shared.available.notify_all();
+        }
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl std::fmt::Debug for WorkerPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("threads", &self.threads)
+            .field("fairness", &self.fairness)
+            .field("started", &self.shared.is_some())
+            .finish()
+    }
+}
+
+/// Pending background dispatches of one event type and their cap
+#[derive(Debug, Default)]
+pub(crate) struct TypeQueue {
+    state: Mutex<TypeQueueState>,
+    space: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct TypeQueueState {
+    depth: usize,
+    dropped: usize,
+    limit: Option<(usize, BackpressurePolicy)>,
+}
+
+impl TypeQueue {
+    /// Take a slot for a new dispatch
+    ///
+    /// Waits for room under [`BackpressurePolicy::Block`]; otherwise a
+    /// full queue counts a drop and returns the policy to apply. On
+    /// success, returns the capacity if this dispatch filled the queue.
+    pub(crate) fn reserve(&self) -> Result<Option<usize>, BackpressurePolicy> {
+        let mut state = self.state.lock().unwrap();
+        while let Some((capacity, policy)) = state.limit {
+            if state.depth < capacity {
+                break;
+            }
+            if policy != BackpressurePolicy::Block {
+                state.dropped += 1;
+                return Err(policy);
+            }
+            state = self.space.wait(state).unwrap();
+        }
+        state.depth += 1;
+        Ok(state
+            .limit
+            .map(|(capacity, _)| capacity)
+            .filter(|&capacity| state.depth == capacity))
+    }
+
+    /// Free the slot of a dispatch that left the queue
+    pub(crate) fn release(&self) {
+        self.state.lock().unwrap().depth -= 1;
+        self.space.notify_one();
+    }
+
+    pub(crate) fn set_limit(&self, limit: Option<(usize, BackpressurePolicy)>) {
+        self.state.lock().unwrap().limit =
+            limit.map(|(capacity, policy)| (capacity.max(1), policy));
+        self.space.notify_all();
+    }
+
+    pub(crate) fn depth(&self) -> usize {
+        self.state.lock().unwrap().depth
+    }
+
+    pub(crate) fn dropped(&self) -> usize {
+        self.state.lock().unwrap().dropped
+    }
+}
+
+/// Handle to a dispatch running on the worker pool
+///
+/// Returned by
+/// [`EventDispatcher::dispatch_background`](crate::EventDispatcher::dispatch_background).
+/// Dropping the handle does not cancel the dispatch.
+#[derive(Debug)]
+pub struct BackgroundDispatch {
+    receiver: Receiver<DispatchResult>,
+}
+
+impl BackgroundDispatch {
+    pub(crate) fn new(receiver: Receiver<DispatchResult>) -> Self {
+        Self { receiver }
+    }
+
+    /// Block until the dispatch finishes and return its result
+    ///
+    /// Returns `None` if a listener panicked or a full queue dropped the
+    /// dispatch.
+    pub fn wait(self) -> Option<DispatchResult> {
+        self.receiver.recv().ok()
+    }
+
+    /// Return the result if the dispatch has finished, without blocking
+    ///
+    /// Returns `Err(self)` if the dispatch is still running, so the handle
+    /// can be polled again, and `Ok(None)` if a listener panicked.
+    pub fn try_wait(self) -> Result<Option<DispatchResult>, Self> {
+        match self.receiver.try_recv() {
+            Ok(result) => Ok(Some(result)),
+            Err(TryRecvError::Disconnected) => Ok(None),
+            Err(TryRecvError::Empty) => Err(self),
+        }
+    }
+}
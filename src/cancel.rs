@@ -0,0 +1,117 @@
+//! Cancellable events
+
+use crate::sync::{AtomicBool, Ordering};
+use crate::Event;
+use std::fmt;
+
+/// Cancellation flag carried inside an event
+///
+/// Embed one in an event and return it from [`Event::cancellation`] to
+/// make the event cancellable. Listeners share the event by reference,
+/// so a high-priority listener that cancels it is observed by every
+/// later listener, and the final state is reported by
+/// [`DispatchResult::is_cancelled`](crate::DispatchResult::is_cancelled).
+/// Cancelling does not skip listeners; each decides what to do with a
+/// cancelled event.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Cancellable, Cancellation, Event, EventDispatcher, Priority};
+///
+/// #[derive(Debug, Clone, Default)]
+/// struct FileDeleting {
+///     path: String,
+///     cancellation: Cancellation,
+/// }
+///
+/// impl Event for FileDeleting {
+///     fn cancellation(&self) -> Option<&Cancellation> {
+///         Some(&self.cancellation)
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.on_with_priority(
+///     |event: &FileDeleting| {
+///         if event.path.starts_with("/etc") {
+///             event.cancel();
+///         }
+///     },
+///     Priority::High,
+/// );
+/// dispatcher.on(|event: &FileDeleting| {
+///     if !event.is_cancelled() {
+///         println!("deleting {}", event.path);
+///     }
+/// });
+///
+/// let result = dispatcher.dispatch(FileDeleting {
+///     path: "/etc/hosts".into(),
+///     ..Default::default()
+/// });
+/// assert!(result.is_cancelled());
+/// ```
+#[derive(Default)]
+pub struct Cancellation {
+    cancelled: AtomicBool,
+}
+
+impl Cancellation {
+    /// Create a flag that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the event as cancelled
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Check whether the event has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+impl Clone for Cancellation {
+    fn clone(&self) -> Self {
+        Self {
+            cancelled: AtomicBool::new(self.is_cancelled()),
+        }
+    }
+}
+
+impl fmt::Debug for Cancellation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cancellation")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Cancel and inspect events that carry a [`Cancellation`]
+///
+/// Implemented for every event, including `dyn Event`, so middleware and
+/// listeners can use it without knowing the concrete type. For events
+/// without a cancellation flag, `cancel` does nothing and `is_cancelled`
+/// returns `false`.
+pub trait Cancellable {
+    /// Cancel the event, if it is cancellable
+    fn cancel(&self);
+
+    /// Check whether the event has been cancelled
+    fn is_cancelled(&self) -> bool;
+}
+
+impl<T: Event + ?Sized> Cancellable for T {
+    fn cancel(&self) {
+        if let Some(cancellation) = self.cancellation() {
+            cancellation.cancel();
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation().is_some_and(Cancellation::is_cancelled)
+    }
+}
@@ -0,0 +1,77 @@
+//! Time source abstraction
+
+use crate::sync::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Source of the current time for the dispatcher
+///
+/// Every timestamp the dispatcher records is taken from its clock, so
+/// time-dependent behavior can be tested deterministically by swapping
+/// in a [`TestClock`].
+pub trait Clock: Send + Sync {
+    /// Get the current instant
+    fn now(&self) -> Instant;
+}
+
+/// Clock backed by [`Instant::now`]
+///
+/// This is the default clock of every dispatcher.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Manually controlled clock for tests
+///
+/// Time only moves when [`advance`](TestClock::advance) is called. Clones
+/// share the same time, so a test can keep one handle and give another
+/// to the dispatcher.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Clock, EventDispatcher, TestClock};
+/// use std::time::Duration;
+///
+/// let clock = TestClock::new();
+/// let dispatcher = EventDispatcher::with_clock(clock.clone());
+///
+/// let start = dispatcher.clock().now();
+/// clock.advance(Duration::from_secs(5));
+/// assert_eq!(dispatcher.clock().now() - start, Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestClock {
+    /// Create a test clock frozen at the current instant
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+}
@@ -0,0 +1,170 @@
+//! Merging bursts of same-key events before delivery
+
+use crate::sync::{AtomicUsize, Mutex, Ordering};
+use crate::{Event, EventDispatcher};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+struct Pending<T, K> {
+    key: K,
+    /// Only empty while a merge is running
+    event: Option<T>,
+    first_seen: Instant,
+}
+
+/// Merges events of one type that share a key within a time window
+///
+/// [`push`](Self::push) holds an event for `window`; events with the same
+/// key arriving meanwhile are folded into it with the merge function
+/// instead of queueing up or being dropped. Held events are dispatched,
+/// in the order their keys first arrived, by
+/// [`flush_due`](Self::flush_due) once their window has elapsed on the
+/// dispatcher's [`Clock`](crate::Clock), or by [`flush`](Self::flush) at
+/// any time. Nothing is dispatched on its own, and dropping the coalescer
+/// discards what it holds.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Coalescer, Event, EventDispatcher, TestClock};
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone)]
+/// struct Progress {
+///     job: u32,
+///     percent: u8,
+///     updates: u32,
+/// }
+///
+/// impl Event for Progress {}
+///
+/// let clock = TestClock::new();
+/// let dispatcher = Arc::new(EventDispatcher::with_clock(clock.clone()));
+/// let seen = Arc::new(Mutex::new(Vec::new()));
+/// let log = seen.clone();
+/// dispatcher.on(move |event: &Progress| {
+///     log.lock().unwrap().push((event.job, event.percent, event.updates));
+/// });
+///
+/// let progress = Coalescer::new(
+///     dispatcher.clone(),
+///     Duration::from_millis(100),
+///     |event: &Progress| event.job,
+///     |old: Progress, new: Progress| Progress {
+///         updates: old.updates + new.updates,
+///         ..new
+///     },
+/// );
+/// for percent in [10, 40, 90] {
+///     progress.push(Progress { job: 1, percent, updates: 1 });
+/// }
+/// progress.push(Progress { job: 2, percent: 5, updates: 1 });
+///
+/// assert_eq!(progress.flush_due(), 0);
+/// clock.advance(Duration::from_millis(100));
+/// assert_eq!(progress.flush_due(), 2);
+/// assert_eq!(*seen.lock().unwrap(), [(1, 90, 3), (2, 5, 1)]);
+/// ```
+pub struct Coalescer<T, K> {
+    dispatcher: Arc<EventDispatcher>,
+    window: Duration,
+    key: Box<dyn Fn(&T) -> K + Send + Sync>,
+    merge: Box<dyn Fn(T, T) -> T + Send + Sync>,
+    pending: Mutex<Vec<Pending<T, K>>>,
+    merged: AtomicUsize,
+}
+
+impl<T, K> fmt::Debug for Coalescer<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coalescer")
+            .field("window", &self.window)
+            .field("pending", &self.pending.lock().unwrap().len())
+            .field("merged", &self.merged.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl<T, K> Coalescer<T, K>
+where
+    T: Event,
+    K: Eq,
+{
+    /// Create a coalescer keyed by `key` and folding events with `merge`
+    ///
+    /// `merge` receives the held event and the newer one, in that order.
+    pub fn new<F, M>(dispatcher: Arc<EventDispatcher>, window: Duration, key: F, merge: M) -> Self
+    where
+        F: Fn(&T) -> K + Send + Sync + 'static,
+        M: Fn(T, T) -> T + Send + Sync + 'static,
+    {
+        Self {
+            dispatcher,
+            window,
+            key: Box::new(key),
+            merge: Box::new(merge),
+            pending: Mutex::new(Vec::new()),
+            merged: AtomicUsize::new(0),
+        }
+    }
+
+    /// Hold an event, merging it into a held event with the same key
+    ///
+    /// Returns `true` if the event was merged. A merge does not extend
+    /// the window of the held event.
+    pub fn push(&self, event: T) -> bool {
+        let key = (self.key)(&event);
+        let mut pending = self.pending.lock().unwrap();
+        if let Some(held) = pending.iter_mut().find(|held| held.key == key) {
+            let old = held.event.take().expect("held event is present");
+            held.event = Some((self.merge)(old, event));
+            self.merged.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        pending.push(Pending {
+            key,
+            event: Some(event),
+            first_seen: self.dispatcher.clock().now(),
+        });
+        false
+    }
+
+    /// Get the number of held events
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Get how many events have been merged into others so far
+    pub fn merged(&self) -> usize {
+        self.merged.load(Ordering::Relaxed)
+    }
+
+    /// Dispatch the held events whose window has elapsed, returning how many
+    pub fn flush_due(&self) -> usize {
+        let now = self.dispatcher.clock().now();
+        let due: Vec<_> = {
+            let mut pending = self.pending.lock().unwrap();
+            let (due, held) = std::mem::take(&mut *pending)
+                .into_iter()
+                .partition(|held| now.saturating_duration_since(held.first_seen) >= self.window);
+            *pending = held;
+            due
+        };
+        self.deliver(due)
+    }
+
+    /// Dispatch every held event now, returning how many
+    pub fn flush(&self) -> usize {
+        let held = std::mem::take(&mut *self.pending.lock().unwrap());
+        self.deliver(held)
+    }
+
+    fn deliver(&self, events: Vec<Pending<T, K>>) -> usize {
+        let count = events.len();
+        for event in events.into_iter().filter_map(|held| held.event) {
+            self.dispatcher.dispatch(event);
+        }
+        count
+    }
+}
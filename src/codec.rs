@@ -0,0 +1,460 @@
+//! Event serialization with schema versioning (requires "serde" feature)
+//!
+//! Events are encoded as [`SerializedEvent`] records carrying the stable
+//! name and version from [`EventSchema`]. When a record was written by an
+//! older version of the event, registered upcasters transform its JSON
+//! payload one version at a time until it matches the current struct, so
+//! persisted logs survive schema evolution.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mod_events::codec::{EventCodec, SerializedEvent};
+//! use mod_events::{Event, EventSchema};
+//! use serde::{Deserialize, Serialize};
+//! use serde_json::json;
+//!
+//! // Version 1 stored `name`; version 2 splits it into two fields.
+//! #[derive(Debug, Clone, Serialize, Deserialize)]
+//! struct UserRegistered {
+//!     first: String,
+//!     last: String,
+//! }
+//!
+//! impl Event for UserRegistered {}
+//!
+//! impl EventSchema for UserRegistered {
+//!     const NAME: &'static str = "user.registered";
+//!     const VERSION: u32 = 2;
+//! }
+//!
+//! let mut codec = EventCodec::new();
+//! codec.register::<UserRegistered>().unwrap();
+//! codec.register_upcaster("user.registered", 1, |mut payload| {
+//!     let name = payload["name"].as_str().unwrap_or_default().to_string();
+//!     let (first, last) = name.split_once(' ').unwrap_or((&name, ""));
+//!     payload = json!({ "first": first, "last": last });
+//!     Ok(payload)
+//! });
+//!
+//! let old = SerializedEvent {
+//!     name: "user.registered".to_string(),
+//!     version: 1,
+//!     payload: json!({ "name": "Ada Lovelace" }),
+//! };
+//!
+//! let event = codec.decode(old).unwrap();
+//! let user = event.downcast_ref::<UserRegistered>().unwrap();
+//! assert_eq!(user.last, "Lovelace");
+//! ```
+
+use crate::sync::RwLock;
+use crate::{Event, EventSchema, SchemaError, SchemaRegistry};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// An event in its serialized, versioned form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializedEvent {
+    /// Stable event name from [`EventSchema::NAME`]
+    pub name: String,
+    /// Schema version the payload was written with
+    pub version: u32,
+    /// The event encoded as JSON
+    pub payload: Value,
+}
+
+/// Errors raised while encoding or decoding events
+#[derive(Debug, thiserror::Error)]
+pub enum CodecError {
+    /// No event type is registered under this name
+    #[error("unknown event `{0}`")]
+    UnknownEvent(String),
+    /// The event type was never registered with the codec
+    #[error("event type {0} is not registered")]
+    NotRegistered(&'static str),
+    /// A record is too old and no upcaster covers one of its versions
+    #[error("no upcaster for `{name}` version {version}")]
+    MissingUpcaster {
+        /// Event name
+        name: String,
+        /// Version that could not be upgraded
+        version: u32,
+    },
+    /// A record was written by a newer version of the event
+    #[error("`{name}` version {version} is newer than supported version {supported}")]
+    UnsupportedVersion {
+        /// Event name
+        name: String,
+        /// Version of the record
+        version: u32,
+        /// Newest version this binary understands
+        supported: u32,
+    },
+    /// An upcaster rejected the payload
+    #[error("upcasting `{name}` from version {version} failed: {message}")]
+    Upcast {
+        /// Event name
+        name: String,
+        /// Version being upgraded
+        version: u32,
+        /// Reason given by the upcaster
+        message: String,
+    },
+    /// The payload could not be converted to or from JSON
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Schema registration failed
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+    /// Sealing or opening a record needs keys, and none are set
+    #[error("no encryption keys are configured")]
+    NoKeys,
+    /// A record names a key that is not in the key ring
+    #[error("unknown encryption key `{0}`")]
+    UnknownKey(String),
+    /// The encryptor failed to encrypt or decrypt a payload
+    #[error("encryption failed: {0}")]
+    Encryption(BoxError),
+}
+
+/// A [`SerializedEvent`] whose payload is encrypted
+///
+/// The name and version stay readable so records can be routed and
+/// upcast without the key; only the payload is confidential.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedEvent {
+    /// Stable event name from [`EventSchema::NAME`]
+    pub name: String,
+    /// Schema version the payload was written with
+    pub version: u32,
+    /// Id of the key the payload was encrypted with
+    pub key_id: String,
+    /// The encrypted JSON payload
+    pub ciphertext: Vec<u8>,
+}
+
+/// Encrypts event payloads with one key
+///
+/// Keys are held in a [`KeyRing`] set on a codec with
+/// [`EventCodec::set_keys`]; records are then encrypted by
+/// [`EventCodec::seal`] and decrypted by [`EventCodec::open`]. Every
+/// ciphertext is tagged with the id of the key that produced it, so keys
+/// can be rotated while records written under retired keys remain
+/// readable. The crate ships no cipher; implement this trait over the
+/// cryptography library of your choice.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::codec::{Encryptor, EventCodec, KeyRing};
+/// use mod_events::{Event, EventSchema};
+/// use serde::{Deserialize, Serialize};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct PatientAdmitted {
+///     name: String,
+/// }
+///
+/// impl Event for PatientAdmitted {}
+///
+/// impl EventSchema for PatientAdmitted {
+///     const NAME: &'static str = "patient.admitted";
+///     const VERSION: u32 = 1;
+/// }
+///
+/// /// Toy cipher for illustration only; use a real AEAD in production
+/// struct Xor(&'static str, u8);
+///
+/// impl Encryptor for Xor {
+///     fn key_id(&self) -> &str {
+///         self.0
+///     }
+///
+///     fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+///         Ok(plaintext.iter().map(|byte| byte ^ self.1).collect())
+///     }
+///
+///     fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+///         self.encrypt(ciphertext)
+///     }
+/// }
+///
+/// let keys = KeyRing::new(Xor("2025-01", 0x5a));
+/// let mut codec = EventCodec::new();
+/// codec.register::<PatientAdmitted>().unwrap();
+/// codec.set_keys(keys.clone());
+///
+/// let event = PatientAdmitted { name: "Ada".into() };
+/// let old = codec.seal(codec.encode(&event).unwrap()).unwrap();
+/// assert_eq!(old.key_id, "2025-01");
+/// assert!(!String::from_utf8_lossy(&old.ciphertext).contains("Ada"));
+///
+/// // Rotate: new records use the new key, old ones still open
+/// keys.rotate(Xor("2025-02", 0x3c));
+/// let new = codec.seal(codec.encode(&event).unwrap()).unwrap();
+/// assert_eq!(new.key_id, "2025-02");
+/// for record in [old, new] {
+///     let event = codec.decode(codec.open(record).unwrap()).unwrap();
+///     assert_eq!(event.downcast_ref::<PatientAdmitted>().unwrap().name, "Ada");
+/// }
+/// ```
+pub trait Encryptor: Send + Sync {
+    /// Id recorded with every payload this key encrypts
+    fn key_id(&self) -> &str;
+
+    /// Encrypt a payload
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, BoxError>;
+
+    /// Decrypt a payload this key encrypted
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, BoxError>;
+}
+
+/// Set of [`Encryptor`] keys supporting rotation
+///
+/// Encrypts with the current key and decrypts with whichever key a
+/// record names. Clones share the same keys, so keys can be rotated
+/// while codecs hold the ring.
+#[derive(Clone)]
+pub struct KeyRing {
+    keys: Arc<RwLock<KeyRingState>>,
+}
+
+struct KeyRingState {
+    current: Arc<dyn Encryptor>,
+    retired: HashMap<String, Arc<dyn Encryptor>>,
+}
+
+impl std::fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let keys = self.keys.read().unwrap();
+        f.debug_struct("KeyRing")
+            .field("current", &keys.current.key_id())
+            .field("retired", &keys.retired.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl KeyRing {
+    /// Create a ring encrypting with `key`
+    pub fn new(key: impl Encryptor + 'static) -> Self {
+        Self {
+            keys: Arc::new(RwLock::new(KeyRingState {
+                current: Arc::new(key),
+                retired: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Encrypt with `key` from now on, keeping the previous key for decryption
+    pub fn rotate(&self, key: impl Encryptor + 'static) {
+        let mut keys = self.keys.write().unwrap();
+        let previous = std::mem::replace(&mut keys.current, Arc::new(key));
+        keys.retired.insert(previous.key_id().to_string(), previous);
+    }
+
+    /// Forget a retired key; records encrypted with it can no longer be opened
+    pub fn retire(&self, key_id: &str) -> bool {
+        self.keys.write().unwrap().retired.remove(key_id).is_some()
+    }
+
+    /// Get the id of the key new payloads are encrypted with
+    pub fn current_key_id(&self) -> String {
+        self.keys.read().unwrap().current.key_id().to_string()
+    }
+
+    /// Encrypt with the current key, returning its id and the ciphertext
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<(String, Vec<u8>), CodecError> {
+        let keys = self.keys.read().unwrap();
+        let ciphertext = keys
+            .current
+            .encrypt(plaintext)
+            .map_err(CodecError::Encryption)?;
+        Ok((keys.current.key_id().to_string(), ciphertext))
+    }
+
+    /// Decrypt a payload encrypted with the key `key_id`
+    pub fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Result<Vec<u8>, CodecError> {
+        let keys = self.keys.read().unwrap();
+        let key = if keys.current.key_id() == key_id {
+            &keys.current
+        } else {
+            keys.retired
+                .get(key_id)
+                .ok_or_else(|| CodecError::UnknownKey(key_id.to_string()))?
+        };
+        key.decrypt(ciphertext).map_err(CodecError::Encryption)
+    }
+}
+
+type Encoder = Box<dyn Fn(&dyn Event) -> Result<Value, serde_json::Error> + Send + Sync>;
+type Decoder = Box<dyn Fn(Value) -> Result<Box<dyn Event>, serde_json::Error> + Send + Sync>;
+type Upcaster = Box<dyn Fn(Value) -> Result<Value, String> + Send + Sync>;
+
+/// Encodes and decodes registered event types, upcasting old versions
+#[derive(Default)]
+pub struct EventCodec {
+    schemas: SchemaRegistry,
+    encoders: HashMap<TypeId, Encoder>,
+    decoders: HashMap<&'static str, Decoder>,
+    upcasters: HashMap<(String, u32), Upcaster>,
+    keys: Option<KeyRing>,
+}
+
+impl std::fmt::Debug for EventCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventCodec")
+            .field("schemas", &self.schemas)
+            .field("upcaster_count", &self.upcasters.len())
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+impl EventCodec {
+    /// Create an empty codec
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an event type for encoding and decoding
+    pub fn register<T>(&mut self) -> Result<(), CodecError>
+    where
+        T: EventSchema + Serialize + DeserializeOwned,
+    {
+        self.schemas.register::<T>()?;
+        self.encoders.insert(
+            TypeId::of::<T>(),
+            Box::new(|event: &dyn Event| match event.downcast_ref::<T>() {
+                Some(event) => serde_json::to_value(event),
+                None => unreachable!("encoder looked up by type id"),
+            }),
+        );
+        self.decoders.insert(
+            T::NAME,
+            Box::new(|payload| Ok(Box::new(serde_json::from_value::<T>(payload)?))),
+        );
+        Ok(())
+    }
+
+    /// Register an upcaster turning version `from_version` of an event
+    /// into version `from_version + 1`
+    pub fn register_upcaster<F>(&mut self, name: impl Into<String>, from_version: u32, upcaster: F)
+    where
+        F: Fn(Value) -> Result<Value, String> + Send + Sync + 'static,
+    {
+        self.upcasters
+            .insert((name.into(), from_version), Box::new(upcaster));
+    }
+
+    /// Encrypt sealed records with `keys`
+    pub fn set_keys(&mut self, keys: KeyRing) {
+        self.keys = Some(keys);
+    }
+
+    /// Get the key ring, if one is set
+    pub fn keys(&self) -> Option<&KeyRing> {
+        self.keys.as_ref()
+    }
+
+    /// Encrypt a record's payload with the current key
+    pub fn seal(&self, record: SerializedEvent) -> Result<EncryptedEvent, CodecError> {
+        let keys = self.keys.as_ref().ok_or(CodecError::NoKeys)?;
+        let (key_id, ciphertext) = keys.encrypt(&serde_json::to_vec(&record.payload)?)?;
+        Ok(EncryptedEvent {
+            name: record.name,
+            version: record.version,
+            key_id,
+            ciphertext,
+        })
+    }
+
+    /// Decrypt a sealed record's payload
+    pub fn open(&self, record: EncryptedEvent) -> Result<SerializedEvent, CodecError> {
+        let keys = self.keys.as_ref().ok_or(CodecError::NoKeys)?;
+        let plaintext = keys.decrypt(&record.key_id, &record.ciphertext)?;
+        Ok(SerializedEvent {
+            name: record.name,
+            version: record.version,
+            payload: serde_json::from_slice(&plaintext)?,
+        })
+    }
+
+    /// Get the schemas of every registered event type
+    pub fn schemas(&self) -> &SchemaRegistry {
+        &self.schemas
+    }
+
+    /// Encode an event at its current version
+    pub fn encode<T: EventSchema + Serialize>(
+        &self,
+        event: &T,
+    ) -> Result<SerializedEvent, CodecError> {
+        Ok(SerializedEvent {
+            name: T::NAME.to_string(),
+            version: T::VERSION,
+            payload: serde_json::to_value(event)?,
+        })
+    }
+
+    /// Encode a type-erased event whose type has been registered
+    pub fn encode_dyn(&self, event: &dyn Event) -> Result<SerializedEvent, CodecError> {
+        let type_id = event.as_any().type_id();
+        let (Some(encoder), Some(info)) = (
+            self.encoders.get(&type_id),
+            self.schemas.get_type_id(type_id),
+        ) else {
+            return Err(CodecError::NotRegistered(event.event_name()));
+        };
+
+        Ok(SerializedEvent {
+            name: info.name.to_string(),
+            version: info.version,
+            payload: encoder(event)?,
+        })
+    }
+
+    /// Decode a record, upcasting it to the current version first
+    pub fn decode(&self, record: SerializedEvent) -> Result<Box<dyn Event>, CodecError> {
+        let SerializedEvent {
+            name,
+            mut version,
+            mut payload,
+        } = record;
+
+        let (Some(info), Some(decoder)) =
+            (self.schemas.get(&name), self.decoders.get(name.as_str()))
+        else {
+            return Err(CodecError::UnknownEvent(name));
+        };
+
+        if version > info.version {
+            return Err(CodecError::UnsupportedVersion {
+                name,
+                version,
+                supported: info.version,
+            });
+        }
+
+        while version < info.version {
+            let Some(upcaster) = self.upcasters.get(&(name.clone(), version)) else {
+                return Err(CodecError::MissingUpcaster { name, version });
+            };
+            payload = upcaster(payload).map_err(|message| CodecError::Upcast {
+                name: name.clone(),
+                version,
+                message,
+            })?;
+            version += 1;
+        }
+
+        Ok(decoder(payload)?)
+    }
+}
@@ -0,0 +1,77 @@
+//! Dispatching into several dispatchers at once
+
+use crate::{Dispatch, DispatchResult, Event, EventDispatcher};
+use std::sync::Arc;
+
+/// View that dispatches each event into several dispatchers
+///
+/// Unlike [`EventDispatcher::merge`], the dispatchers keep their own
+/// listeners, middleware and metrics; each event is dispatched into every
+/// member in order and the outcomes are combined into one
+/// [`DispatchResult`]. The combined result is blocked if any member's
+/// middleware blocked the event.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{CompositeDispatcher, Dispatch, Event, EventDispatcher};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone)]
+/// struct Shutdown;
+///
+/// impl Event for Shutdown {}
+///
+/// let app = Arc::new(EventDispatcher::new());
+/// let plugin = Arc::new(EventDispatcher::new());
+/// app.on(|_: &Shutdown| println!("flushing logs"));
+/// plugin.on(|_: &Shutdown| println!("closing plugin sockets"));
+///
+/// let bus = CompositeDispatcher::new().with(app.clone()).with(plugin.clone());
+/// assert_eq!(bus.dispatch(Shutdown).listener_count(), 2);
+/// ```
+#[derive(Clone, Default)]
+pub struct CompositeDispatcher {
+    members: Vec<Arc<EventDispatcher>>,
+}
+
+impl CompositeDispatcher {
+    /// Create a view with no members
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a member dispatcher
+    pub fn with(mut self, dispatcher: Arc<EventDispatcher>) -> Self {
+        self.push(dispatcher);
+        self
+    }
+
+    /// Add a member dispatcher
+    pub fn push(&mut self, dispatcher: Arc<EventDispatcher>) {
+        self.members.push(dispatcher);
+    }
+
+    /// Get the member dispatchers, in dispatch order
+    pub fn members(&self) -> &[Arc<EventDispatcher>] {
+        &self.members
+    }
+}
+
+impl Dispatch for CompositeDispatcher {
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        self.members
+            .iter()
+            .map(|member| member.dispatch_ref(&event))
+            .reduce(DispatchResult::combine)
+            .unwrap_or_else(|| DispatchResult::new(Vec::new()))
+    }
+}
+
+impl std::fmt::Debug for CompositeDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositeDispatcher")
+            .field("members", &self.members.len())
+            .finish()
+    }
+}
@@ -0,0 +1,167 @@
+//! Runtime dispatcher configuration
+
+use crate::{BackpressurePolicy, ErrorPolicy, PanicPolicy, QueueFairness};
+use std::time::Duration;
+
+/// Set of dispatcher settings applied together
+///
+/// Pass to [`EventDispatcher::reconfigure`](crate::EventDispatcher::reconfigure)
+/// to change settings on a running dispatcher; only the settings that
+/// were set are changed. [`EventDispatcher::config`](crate::EventDispatcher::config)
+/// returns the current settings with every field set. With the "serde"
+/// feature, configurations can be loaded from and saved to files.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{BackpressurePolicy, DispatcherConfig, ErrorPolicy, EventDispatcher};
+/// use std::time::Duration;
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.reconfigure(
+///     DispatcherConfig::new()
+///         .error_policy(ErrorPolicy::StopOnError)
+///         .backpressure(BackpressurePolicy::Fail)
+///         .background_threads(4)
+///         .latency_window(Duration::from_secs(30)),
+/// );
+///
+/// let config = dispatcher.config();
+/// assert_eq!(config.get_error_policy(), Some(ErrorPolicy::StopOnError));
+/// assert_eq!(config.get_background_threads(), Some(4));
+/// assert_eq!(config.get_metrics(), Some(true));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DispatcherConfig {
+    pub(crate) error_policy: Option<ErrorPolicy>,
+    pub(crate) panic_policy: Option<PanicPolicy>,
+    pub(crate) backpressure: Option<BackpressurePolicy>,
+    pub(crate) background_threads: Option<usize>,
+    pub(crate) queue_fairness: Option<QueueFairness>,
+    pub(crate) queue_aging: Option<Option<Duration>>,
+    pub(crate) latency_window: Option<Option<Duration>>,
+    pub(crate) metrics: Option<bool>,
+    pub(crate) meta_events: Option<bool>,
+    pub(crate) deterministic: Option<Option<u64>>,
+}
+
+impl DispatcherConfig {
+    /// Create a configuration that changes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set what happens when a listener returns an error
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = Some(policy);
+        self
+    }
+
+    /// Set what happens when a listener panics
+    pub fn panic_policy(mut self, policy: PanicPolicy) -> Self {
+        self.panic_policy = Some(policy);
+        self
+    }
+
+    /// Set what bounded subscriptions do when a consumer's buffer is full
+    pub fn backpressure(mut self, policy: BackpressurePolicy) -> Self {
+        self.backpressure = Some(policy);
+        self
+    }
+
+    /// Set how many threads run background dispatches
+    ///
+    /// Dispatches already queued still run on the previous threads.
+    pub fn background_threads(mut self, threads: usize) -> Self {
+        self.background_threads = Some(threads);
+        self
+    }
+
+    /// Set how queued background dispatches are ordered
+    pub fn queue_fairness(mut self, fairness: QueueFairness) -> Self {
+        self.queue_fairness = Some(fairness);
+        self
+    }
+
+    /// Set how fast queued dispatches gain priority, or `None` to disable aging
+    pub fn queue_aging(mut self, rate: Option<Duration>) -> Self {
+        self.queue_aging = Some(rate);
+        self
+    }
+
+    /// Track latency percentiles over a sliding window of this length
+    pub fn latency_window(mut self, window: Duration) -> Self {
+        self.latency_window = Some(Some(window));
+        self
+    }
+
+    /// Turn per-event metrics on or off
+    pub fn metrics(mut self, enabled: bool) -> Self {
+        self.metrics = Some(enabled);
+        self
+    }
+
+    /// Turn meta-events on or off
+    pub fn meta_events(mut self, enabled: bool) -> Self {
+        self.meta_events = Some(enabled);
+        self
+    }
+
+    /// Turn the deterministic ordering mode on with a seed, or off with `None`
+    pub fn deterministic(mut self, seed: Option<u64>) -> Self {
+        self.deterministic = Some(seed);
+        self
+    }
+
+    /// Get the error policy, if set
+    pub fn get_error_policy(&self) -> Option<ErrorPolicy> {
+        self.error_policy
+    }
+
+    /// Get the panic policy, if set
+    pub fn get_panic_policy(&self) -> Option<PanicPolicy> {
+        self.panic_policy
+    }
+
+    /// Get the backpressure policy, if set
+    pub fn get_backpressure(&self) -> Option<BackpressurePolicy> {
+        self.backpressure
+    }
+
+    /// Get the number of background threads, if set
+    pub fn get_background_threads(&self) -> Option<usize> {
+        self.background_threads
+    }
+
+    /// Get the queue fairness, if set
+    pub fn get_queue_fairness(&self) -> Option<QueueFairness> {
+        self.queue_fairness
+    }
+
+    /// Get the queue aging rate, if set
+    pub fn get_queue_aging(&self) -> Option<Option<Duration>> {
+        self.queue_aging
+    }
+
+    /// Get the latency window, if set; `Some(None)` means tracking is off
+    pub fn get_latency_window(&self) -> Option<Option<Duration>> {
+        self.latency_window
+    }
+
+    /// Get whether metrics are on, if set
+    pub fn get_metrics(&self) -> Option<bool> {
+        self.metrics
+    }
+
+    /// Get whether meta-events are on, if set
+    pub fn get_meta_events(&self) -> Option<bool> {
+        self.meta_events
+    }
+
+    /// Get the deterministic ordering seed, if set; `Some(None)` means the mode is off
+    pub fn get_deterministic(&self) -> Option<Option<u64>> {
+        self.deterministic
+    }
+}
@@ -0,0 +1,63 @@
+//! Per-dispatch context
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Values attached to a single dispatch
+///
+/// Carries request-scoped data such as the acting principal to
+/// [`Middleware`](crate::Middleware) without putting it in every event.
+/// Values are stored by type, one per type.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::DispatchContext;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct TenantId(u32);
+///
+/// let context = DispatchContext::new().with(TenantId(7));
+/// assert_eq!(context.get::<TenantId>(), Some(&TenantId(7)));
+/// assert!(context.get::<String>().is_none());
+/// ```
+#[derive(Default)]
+pub struct DispatchContext {
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl std::fmt::Debug for DispatchContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DispatchContext")
+            .field("value_count", &self.values.len())
+            .finish()
+    }
+}
+
+impl DispatchContext {
+    /// Create an empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a value, replacing any previous value of the same type
+    pub fn with<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Insert a value, returning the previous value of the same type
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|previous| previous.downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    /// Get the value of type `T`, if present
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+}
@@ -1,7 +1,10 @@
 //! Core event system traits and types
 
+use crate::DispatchResult;
 use std::any::{Any, TypeId};
 use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Core trait that all events must implement
 ///
@@ -19,15 +22,15 @@ use std::fmt;
 ///     email: String,
 /// }
 ///
-/// impl Event for UserRegistered {
-///     fn as_any(&self) -> &dyn std::any::Any {
-///         self
-///     }
-/// }
+/// impl Event for UserRegistered {}
 /// ```
-pub trait Event: Any + Send + Sync + fmt::Debug {
+pub trait Event: Any + AsAny + Send + Sync + fmt::Debug {
     /// Returns the event as Any for downcasting
-    fn as_any(&self) -> &dyn Any;
+    ///
+    /// Provided automatically; there is no need to implement it.
+    fn as_any(&self) -> &dyn Any {
+        self.as_any_ref()
+    }
 
     /// Returns a unique identifier for this event type
     fn type_id(&self) -> TypeId {
@@ -38,20 +41,337 @@ pub trait Event: Any + Send + Sync + fmt::Debug {
     fn event_name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
+
+    /// Returns the event's cancellation flag, if it is cancellable
+    ///
+    /// See [`Cancellation`](crate::Cancellation). Defaults to `None`.
+    fn cancellation(&self) -> Option<&crate::Cancellation> {
+        None
+    }
+
+    /// Returns the id tying this event to a request or workflow, if any
+    ///
+    /// Recorded by [`EventHistory`](crate::EventHistory) so related events
+    /// can be queried together. Defaults to `None`.
+    fn correlation_id(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Downcasting helpers for type-erased events
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct Login {
+///     user: String,
+/// }
+///
+/// impl Event for Login {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.add_middleware(|event| match event.downcast_ref::<Login>() {
+///     Some(login) => !login.user.is_empty(),
+///     None => true,
+/// });
+///
+/// let boxed: Box<dyn Event> = Box::new(Login { user: "alice".into() });
+/// assert!(boxed.is::<Login>());
+/// let login: Box<Login> = boxed.downcast().unwrap();
+/// assert_eq!(login.user, "alice");
+/// ```
+impl dyn Event {
+    /// Check whether the event is of type `T`
+    pub fn is<T: Event>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Get a reference to the event as `T`, if it is one
+    pub fn downcast_ref<T: Event>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    /// Convert the boxed event into a `Box<T>`
+    ///
+    /// Returns the original box if the event is not a `T`.
+    pub fn downcast<T: Event>(self: Box<Self>) -> Result<Box<T>, Box<dyn Event>> {
+        if self.is::<T>() {
+            let any: Box<dyn Any> = self;
+            Ok(any.downcast::<T>().expect("type checked above"))
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Conversion to `&dyn Any`, implemented for every sized type
+///
+/// Supplies the default [`Event::as_any`]; it is never implemented by hand.
+#[doc(hidden)]
+pub trait AsAny {
+    fn as_any_ref(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
 }
 
 /// Unique identifier for event listeners
 ///
 /// This is returned when subscribing to events and can be used
-/// to unsubscribe specific listeners later.
+/// to unsubscribe specific listeners later. Ids are bound to the
+/// dispatcher that issued them; other dispatchers ignore them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ListenerId {
+    pub(crate) dispatcher: usize,
     pub(crate) id: usize,
     pub(crate) type_id: TypeId,
 }
 
 impl ListenerId {
-    pub(crate) fn new(id: usize, type_id: TypeId) -> Self {
-        Self { id, type_id }
+    pub(crate) fn new(dispatcher: usize, id: usize, type_id: TypeId) -> Self {
+        Self {
+            dispatcher,
+            id,
+            type_id,
+        }
+    }
+}
+
+impl fmt::Display for ListenerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.id)
+    }
+}
+
+/// Listener identifier that remembers its event type
+///
+/// Returned by the typed subscribe methods. It converts into a plain
+/// [`ListenerId`] wherever one is accepted, and compares equal to the
+/// `ListenerId` it wraps.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, ListenerId, TypedListenerId};
+///
+/// #[derive(Debug, Clone)]
+/// struct Tick;
+///
+/// impl Event for Tick {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// let id: TypedListenerId<Tick> = dispatcher.on(|_: &Tick| {});
+///
+/// let untyped: ListenerId = id.into();
+/// assert_eq!(id, untyped);
+/// assert!(dispatcher.unsubscribe(id));
+///
+/// // Ids from another dispatcher are ignored
+/// let other = EventDispatcher::new();
+/// let foreign = other.on(|_: &Tick| {});
+/// assert!(!dispatcher.unsubscribe(foreign));
+/// ```
+pub struct TypedListenerId<T> {
+    id: ListenerId,
+    _event: PhantomData<fn() -> T>,
+}
+
+impl<T: Event> TypedListenerId<T> {
+    pub(crate) fn new(id: ListenerId) -> Self {
+        Self {
+            id,
+            _event: PhantomData,
+        }
+    }
+
+    /// Get the untyped id
+    pub fn id(&self) -> ListenerId {
+        self.id
+    }
+}
+
+impl<T> Clone for TypedListenerId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TypedListenerId<T> {}
+
+impl<T> PartialEq for TypedListenerId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for TypedListenerId<T> {}
+
+impl<T> std::hash::Hash for TypedListenerId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T> PartialEq<ListenerId> for TypedListenerId<T> {
+    fn eq(&self, other: &ListenerId) -> bool {
+        self.id == *other
+    }
+}
+
+impl<T> PartialEq<TypedListenerId<T>> for ListenerId {
+    fn eq(&self, other: &TypedListenerId<T>) -> bool {
+        *self == other.id
+    }
+}
+
+impl<T> From<TypedListenerId<T>> for ListenerId {
+    fn from(id: TypedListenerId<T>) -> Self {
+        id.id
+    }
+}
+
+impl<T> fmt::Debug for TypedListenerId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedListenerId")
+            .field("event", &std::any::type_name::<T>())
+            .field("id", &self.id.id)
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for TypedListenerId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.id.fmt(f)
+    }
+}
+
+/// Owned handle to an event that is dispatched without copying it
+///
+/// Implemented for `&'static T`, `Cow<'static, T>`, `Arc<T>` and `Box<T>`,
+/// so constant or interned events can go through every dispatch mode,
+/// including the background queue, with no clone or allocation. Listeners
+/// subscribed to `T` receive the referenced event.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher};
+/// use std::borrow::Cow;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone)]
+/// struct Tick;
+///
+/// impl Event for Tick {}
+///
+/// static TICK: Tick = Tick;
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// dispatcher.on(|_: &Tick| {});
+///
+/// assert_eq!(dispatcher.dispatch_borrowed(&TICK).listener_count(), 1);
+/// assert_eq!(dispatcher.dispatch_borrowed(Cow::Borrowed(&TICK)).listener_count(), 1);
+/// let queued = dispatcher.dispatch_background_borrowed(&TICK);
+/// assert_eq!(queued.wait().unwrap().listener_count(), 1);
+/// ```
+pub trait EventRef: Send + Sync + 'static {
+    /// The referenced event type
+    type Event: Event;
+
+    /// Borrow the event
+    fn event(&self) -> &Self::Event;
+}
+
+impl<T: Event> EventRef for &'static T {
+    type Event = T;
+
+    fn event(&self) -> &T {
+        self
+    }
+}
+
+impl<T: Event + Clone> EventRef for std::borrow::Cow<'static, T> {
+    type Event = T;
+
+    fn event(&self) -> &T {
+        self
+    }
+}
+
+impl<T: Event> EventRef for Arc<T> {
+    type Event = T;
+
+    fn event(&self) -> &T {
+        self
+    }
+}
+
+impl<T: Event> EventRef for Box<T> {
+    type Event = T;
+
+    fn event(&self) -> &T {
+        self
+    }
+}
+
+/// Abstraction over anything that can dispatch events
+///
+/// Application code can depend on `Dispatch` instead of a concrete
+/// [`EventDispatcher`](crate::EventDispatcher), so tests can substitute a
+/// recording mock such as `testing::MockDispatcher` (requires the
+/// "test-utils" feature).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Dispatch, Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct UserRegistered {
+///     user_id: u64,
+/// }
+///
+/// impl Event for UserRegistered {}
+///
+/// struct SignupService<D: Dispatch> {
+///     events: D,
+/// }
+///
+/// impl<D: Dispatch> SignupService<D> {
+///     fn register(&self, user_id: u64) {
+///         self.events.emit(UserRegistered { user_id });
+///     }
+/// }
+///
+/// let service = SignupService {
+///     events: EventDispatcher::new(),
+/// };
+/// service.register(7);
+/// ```
+pub trait Dispatch: Send + Sync {
+    /// Dispatch an event and report the outcome
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult;
+
+    /// Dispatch an event without waiting for the outcome
+    fn emit<T: Event>(&self, event: T) {
+        let _ = self.dispatch(event);
+    }
+}
+
+impl<D: Dispatch + ?Sized> Dispatch for &D {
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        (**self).dispatch(event)
+    }
+}
+
+impl<D: Dispatch + ?Sized> Dispatch for Arc<D> {
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        (**self).dispatch(event)
     }
 }
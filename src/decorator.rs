@@ -0,0 +1,228 @@
+//! Composable listener decorators
+
+use crate::{Event, EventListener, Priority};
+use std::time::{Duration, Instant};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Closures taking the event by reference are listeners too
+impl<T, F> EventListener<T> for F
+where
+    T: Event,
+    F: Fn(&T) -> Result<(), BoxError> + Send + Sync,
+{
+    fn handle(&self, event: &T) -> Result<(), BoxError> {
+        self(event)
+    }
+}
+
+/// Error reported by [`ListenerExt::with_timeout`] when a listener overruns
+#[derive(Debug, thiserror::Error)]
+#[error("listener took {elapsed:?}, over its {limit:?} limit")]
+pub struct ListenerOverran {
+    /// The configured limit
+    pub limit: Duration,
+    /// How long the listener actually took
+    pub elapsed: Duration,
+}
+
+/// Decorators for any [`EventListener`], including closures
+///
+/// Each method wraps the listener in another `EventListener` with the
+/// same priority, so decorators compose and the result can be registered
+/// with [`EventDispatcher::subscribe_listener`](crate::EventDispatcher::subscribe_listener).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, ListenerExt};
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone)]
+/// struct Upload;
+///
+/// impl Event for Upload {}
+///
+/// let attempts = Arc::new(AtomicU32::new(0));
+/// let counter = attempts.clone();
+/// let flaky = move |_: &Upload| -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+///         return Err("connection reset".into());
+///     }
+///     Ok(())
+/// };
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.subscribe_listener(
+///     flaky
+///         .with_retry(3)
+///         .with_timeout(Duration::from_secs(5))
+///         .with_timing(|elapsed| println!("upload handled in {elapsed:?}")),
+/// );
+///
+/// assert!(dispatcher.dispatch(Upload).all_succeeded());
+/// assert_eq!(attempts.load(Ordering::SeqCst), 3);
+/// ```
+pub trait ListenerExt<T: Event>: EventListener<T> + Sized {
+    /// Report how long each call took to `report`
+    fn with_timing<F>(self, report: F) -> Timed<Self, F>
+    where
+        F: Fn(Duration) + Send + Sync,
+    {
+        Timed {
+            listener: self,
+            report,
+        }
+    }
+
+    /// Retry failed calls up to `retries` more times
+    ///
+    /// The last error is returned if every attempt fails.
+    fn with_retry(self, retries: u32) -> Retry<Self> {
+        Retry {
+            listener: self,
+            retries,
+        }
+    }
+
+    /// Fail calls that take longer than `limit`
+    ///
+    /// A synchronous listener cannot be interrupted, so the call runs to
+    /// completion and is then reported as a [`ListenerOverran`] error.
+    fn with_timeout(self, limit: Duration) -> Timeout<Self> {
+        Timeout {
+            listener: self,
+            limit,
+        }
+    }
+
+    /// Log each call and its outcome through the `log` facade (requires "log" feature)
+    ///
+    /// Calls are logged at `level` under `name`; failures at `Error`.
+    #[cfg(feature = "log")]
+    fn with_logging(self, name: &'static str, level: log::Level) -> Logged<Self> {
+        Logged {
+            listener: self,
+            name,
+            level,
+        }
+    }
+}
+
+impl<T: Event, L: EventListener<T>> ListenerExt<T> for L {}
+
+/// Listener decorated by [`ListenerExt::with_timing`]
+#[derive(Debug, Clone)]
+pub struct Timed<L, F> {
+    listener: L,
+    report: F,
+}
+
+impl<T, L, F> EventListener<T> for Timed<L, F>
+where
+    T: Event,
+    L: EventListener<T>,
+    F: Fn(Duration) + Send + Sync,
+{
+    fn handle(&self, event: &T) -> Result<(), BoxError> {
+        let started = Instant::now();
+        let result = self.listener.handle(event);
+        (self.report)(started.elapsed());
+        result
+    }
+
+    fn priority(&self) -> Priority {
+        self.listener.priority()
+    }
+}
+
+/// Listener decorated by [`ListenerExt::with_retry`]
+#[derive(Debug, Clone)]
+pub struct Retry<L> {
+    listener: L,
+    retries: u32,
+}
+
+impl<T: Event, L: EventListener<T>> EventListener<T> for Retry<L> {
+    fn handle(&self, event: &T) -> Result<(), BoxError> {
+        let mut result = self.listener.handle(event);
+        for _ in 0..self.retries {
+            if result.is_ok() {
+                break;
+            }
+            result = self.listener.handle(event);
+        }
+        result
+    }
+
+    fn priority(&self) -> Priority {
+        self.listener.priority()
+    }
+}
+
+/// Listener decorated by [`ListenerExt::with_timeout`]
+#[derive(Debug, Clone)]
+pub struct Timeout<L> {
+    listener: L,
+    limit: Duration,
+}
+
+impl<T: Event, L: EventListener<T>> EventListener<T> for Timeout<L> {
+    fn handle(&self, event: &T) -> Result<(), BoxError> {
+        let started = Instant::now();
+        self.listener.handle(event)?;
+        let elapsed = started.elapsed();
+        if elapsed > self.limit {
+            return Err(Box::new(ListenerOverran {
+                limit: self.limit,
+                elapsed,
+            }));
+        }
+        Ok(())
+    }
+
+    fn priority(&self) -> Priority {
+        self.listener.priority()
+    }
+}
+
+/// Listener decorated by [`ListenerExt::with_logging`] (requires "log" feature)
+#[cfg(feature = "log")]
+#[derive(Debug, Clone)]
+pub struct Logged<L> {
+    listener: L,
+    name: &'static str,
+    level: log::Level,
+}
+
+#[cfg(feature = "log")]
+impl<T: Event, L: EventListener<T>> EventListener<T> for Logged<L> {
+    fn handle(&self, event: &T) -> Result<(), BoxError> {
+        let started = Instant::now();
+        let result = self.listener.handle(event);
+        let elapsed = started.elapsed();
+        match &result {
+            Ok(()) => log::log!(
+                self.level,
+                "{} handled {} in {:?}",
+                self.name,
+                event.event_name(),
+                elapsed
+            ),
+            Err(error) => log::error!(
+                "{} failed on {} after {:?}: {}",
+                self.name,
+                event.event_name(),
+                elapsed,
+                error
+            ),
+        }
+        result
+    }
+
+    fn priority(&self) -> Priority {
+        self.listener.priority()
+    }
+}
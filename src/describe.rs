@@ -0,0 +1,76 @@
+//! Descriptions of a dispatcher's configuration and topology
+
+use crate::{Delivery, DispatcherConfig, Priority};
+
+/// Snapshot of a dispatcher's configuration and wiring
+///
+/// Returned by [`EventDispatcher::describe`](crate::EventDispatcher::describe).
+/// Listeners and middleware are described by name; the closures
+/// themselves are not captured. With the "serde" feature the descriptor
+/// serializes, e.g. to JSON for diagnostics endpoints or generated
+/// documentation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DispatcherDescriptor {
+    /// Current settings
+    pub config: DispatcherConfig,
+    /// Names of the middleware, in the order they run
+    pub middleware: Vec<&'static str>,
+    /// Number of registered observers
+    pub observers: usize,
+    /// Number of registered listener hooks
+    pub listener_hooks: usize,
+    /// Every known event type, sorted by display name
+    pub event_types: Vec<EventTypeDescriptor>,
+}
+
+/// One event type in a [`DispatcherDescriptor`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EventTypeDescriptor {
+    /// Full type name
+    pub type_name: &'static str,
+    /// Type name without module paths
+    pub display_name: String,
+    /// Whether the type is broadcast or consumed by one listener
+    pub delivery: Delivery,
+    /// Number of dispatches so far, if metrics are on
+    pub dispatch_count: usize,
+    /// Listeners in the order they run
+    pub listeners: Vec<ListenerDescriptor>,
+}
+
+/// One listener in an [`EventTypeDescriptor`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ListenerDescriptor {
+    /// Numeric part of the listener id
+    pub id: usize,
+    /// Name of the listener
+    pub name: &'static str,
+    /// Priority the listener was subscribed with
+    pub priority: Priority,
+    /// How the listener is invoked
+    pub kind: ListenerKind,
+    /// First-match group the listener belongs to
+    pub first_match: Option<&'static str>,
+    /// Load-balanced group the listener belongs to
+    pub balanced: Option<&'static str>,
+    /// Namespace the listener was registered in
+    pub namespace: Option<String>,
+    /// Tenant the listener is limited to
+    pub tenant: Option<String>,
+}
+
+/// How a described listener is invoked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ListenerKind {
+    /// Synchronous listener
+    Sync,
+    /// Async listener
+    Async,
+    /// Responder returning a value to
+    /// [`dispatch_collect`](crate::EventDispatcher::dispatch_collect)
+    Responder,
+}
@@ -1,20 +1,188 @@
 //! Main event dispatcher implementation
 
+use crate::sync::{AtomicBool, AtomicUsize, Mutex, Ordering, RwLock};
 use crate::{
-    DispatchResult, Event, EventMetadata, ListenerId, ListenerWrapper, MiddlewareManager, Priority,
+    display_type_name, is_meta_event, ActiveNamespace, Aggregator, BackgroundDispatch,
+    BackpressurePolicy, BlockReason, Cancellable, Clock, CounterSnapshot, Delivery, Dispatch,
+    DispatchContext, DispatchCounters, DispatchObserver, DispatchRate, DispatchResult,
+    DispatcherConfig, DispatcherDescriptor, DispatcherStats, DynamicEvent, ErrorPolicy, Event,
+    EventBlocked, EventDropped, EventGroup, EventListener, EventMetadata, EventRef,
+    EventTypeDescriptor, EventVolume, Handled, HealthReport, HealthThresholds, HookId,
+    IntoListenerResult, LatencySeries, LatencyWindow, ListenerAdded, ListenerClosed,
+    ListenerDescriptor, ListenerError, ListenerHook, ListenerId, ListenerInfo, ListenerKind,
+    ListenerOptions, ListenerWrapper, LoadBalance, ManagedListener, MemoryFootprint, Namespace,
+    PanicPolicy, Priority, QueueFairness, QueueSaturated, RateLimiter, ResponderWrapper,
+    SharedListener, SlowListener, SlowListenerHook, SubscribeError, SubscriptionGuard, SystemClock,
+    TenantId, TenantMetrics, TypeQueue, TypedListenerId, WorkerPool, TOP_EVENT_TYPES,
 };
 use std::any::TypeId;
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "async")]
-use crate::AsyncListenerWrapper;
+use crate::{
+    AsyncBridge, AsyncEventListener, AsyncListenerWrapper, AsyncListenersSkipped, ListenerPanicked,
+    ManagedAsyncListener, SharedAsyncListener,
+};
+#[cfg(feature = "middleware")]
+use crate::{Middleware, MiddlewareManager};
 #[cfg(feature = "async")]
 use std::future::Future;
 #[cfg(feature = "async")]
 use std::pin::Pin;
 
+/// Listener hooks registered on a dispatcher, with their removal handles
+type ListenerHooks = Arc<Vec<(HookId, Arc<dyn ListenerHook>)>>;
+
+/// Split listeners of one priority into waves that respect their dependencies
+///
+/// Each listener lands in the first wave after every dependency it shares
+/// the group with; dependencies always sit ahead of their dependents in
+/// the table, so one pass in order is enough.
+fn dependency_waves(listeners: Vec<&ListenerWrapper>) -> Vec<Vec<&ListenerWrapper>> {
+    let mut levels: HashMap<usize, usize> = HashMap::new();
+    let mut floors: HashMap<usize, usize> = HashMap::new();
+    let mut waves: Vec<Vec<&ListenerWrapper>> = Vec::new();
+    for listener in listeners {
+        let level = listener
+            .depends_on
+            .iter()
+            .filter_map(|id| levels.get(id))
+            .max()
+            .map_or(0, |level| level + 1)
+            .max(floors.get(&listener.id).copied().unwrap_or(0));
+        levels.insert(listener.id, level);
+        for &id in &listener.precedes {
+            let floor = floors.entry(id).or_default();
+            *floor = (*floor).max(level + 1);
+        }
+        if waves.len() <= level {
+            waves.resize_with(level + 1, Vec::new);
+        }
+        waves[level].push(listener);
+    }
+    waves
+}
+
+/// Order a wave of independent listeners by a hash of `seed` and their ids
+///
+/// Uses the SplitMix64 finalizer, so the order depends only on the seed
+/// and the subscription order that assigned the ids.
+fn seeded_order(mut wave: Vec<&ListenerWrapper>, seed: u64) -> Vec<&ListenerWrapper> {
+    wave.sort_by_cached_key(|listener| {
+        let mut z = seed ^ (listener.id as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        (z ^ (z >> 31), listener.id)
+    });
+    wave
+}
+
+/// Check whether a listener's result skips the listeners after it
+fn halts(policy: ErrorPolicy, panics: PanicPolicy, result: &Result<(), ListenerError>) -> bool {
+    match result {
+        Ok(()) => false,
+        Err(ListenerError::Panic { .. }) if panics == PanicPolicy::Abort => true,
+        Err(_) => policy == ErrorPolicy::StopOnError,
+    }
+}
+
+/// Turn-counter keys for the sync and async listeners of a unicast event type
+const UNICAST_POOL: &str = "";
+#[cfg(feature = "async")]
+const UNICAST_ASYNC_POOL: &str = "async";
+
+/// Strategies and turn counters of load-balanced groups
+#[derive(Default)]
+struct BalanceState {
+    strategies: HashMap<&'static str, LoadBalance>,
+    turns: HashMap<(TypeId, &'static str), usize>,
+}
+
+/// Listener run by `dispatch_async`, in priority order across both kinds
+#[cfg(feature = "async")]
+enum AsyncStep {
+    /// Sync listener, looked up by id when its turn comes
+    Sync,
+    Async(AsyncHandler, Arc<tokio::sync::Semaphore>),
+}
+
+/// When a sync listener started, if observed, and what it returned
+#[cfg(feature = "async")]
+type SyncCall = (
+    Option<Instant>,
+    Result<Handled, Box<dyn std::error::Error + Send + Sync>>,
+);
+
+/// Per-dispatch state of the sync listeners run by an async dispatch
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct SyncRun {
+    handled_groups: Vec<&'static str>,
+    rate_limited: usize,
+    spent: Vec<ListenerId>,
+    /// Listeners unsubscribed before their turn came
+    gone: Vec<ListenerId>,
+}
+
+/// Run `futures` with at most `limit` in flight, returning their outputs
+/// in the order given
+#[cfg(feature = "async")]
+async fn run_bounded<F: Future>(futures: Vec<F>, limit: usize) -> Vec<F::Output> {
+    let total = futures.len();
+    let mut waiting = futures.into_iter().enumerate();
+    let mut running: Vec<(usize, Pin<Box<F>>)> = Vec::with_capacity(limit.min(total));
+    let mut outputs: Vec<Option<F::Output>> = (0..total).map(|_| None).collect();
+    std::future::poll_fn(|cx| loop {
+        while running.len() < limit {
+            match waiting.next() {
+                Some((index, future)) => running.push((index, Box::pin(future))),
+                None => break,
+            }
+        }
+        if running.is_empty() {
+            return std::task::Poll::Ready(());
+        }
+        let before = running.len();
+        running.retain_mut(|(index, future)| match future.as_mut().poll(cx) {
+            std::task::Poll::Ready(output) => {
+                outputs[*index] = Some(output);
+                false
+            }
+            std::task::Poll::Pending => true,
+        });
+        if running.len() == before {
+            return std::task::Poll::Pending;
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
+
+/// Await an async listener, returning a panic while it is polled as a
+/// [`ListenerPanicked`] error if `catch` is set
+#[cfg(feature = "async")]
+async fn catch_async_panic<F>(future: F, catch: bool) -> F::Output
+where
+    F: Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>,
+{
+    if !catch {
+        return future.await;
+    }
+    let mut future = std::pin::pin!(future);
+    std::future::poll_fn(|cx| {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| future.as_mut().poll(cx)))
+            .unwrap_or_else(|payload| {
+                std::task::Poll::Ready(Err(ListenerPanicked::from_payload(payload).into()))
+            })
+    })
+    .await
+}
+
+/// Source of dispatcher identities, so listener ids cannot be used across dispatchers
+static NEXT_DISPATCHER_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
 // Type aliases for complex types
 #[cfg(feature = "async")]
 type AsyncResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
@@ -40,11 +208,7 @@ type AsyncHandler = Arc<
 ///     message: String,
 /// }
 ///
-/// impl Event for MyEvent {
-///     fn as_any(&self) -> &dyn std::any::Any {
-///         self
-///     }
-/// }
+/// impl Event for MyEvent {}
 ///
 /// let dispatcher = EventDispatcher::new();
 ///
@@ -57,27 +221,100 @@ type AsyncHandler = Arc<
 /// });
 /// ```
 pub struct EventDispatcher {
-    listeners: Arc<RwLock<HashMap<TypeId, Vec<ListenerWrapper>>>>,
+    id: usize,
+    listeners: Arc<RwLock<HashMap<TypeId, Vec<Arc<ListenerWrapper>>>>>,
     #[cfg(feature = "async")]
     async_listeners: Arc<RwLock<HashMap<TypeId, Vec<AsyncListenerWrapper>>>>,
+    #[cfg(feature = "async")]
+    async_bridge: RwLock<AsyncBridge>,
+    responders: RwLock<HashMap<(TypeId, TypeId), Vec<ResponderWrapper>>>,
     next_id: AtomicUsize,
     metrics: Arc<RwLock<HashMap<TypeId, EventMetadata>>>,
+    #[cfg(feature = "middleware")]
     middleware: Arc<RwLock<MiddlewareManager>>,
+    clock: Arc<dyn Clock>,
+    counters: Arc<DispatchCounters>,
+    rate: DispatchRate,
+    health_baseline: Mutex<CounterSnapshot>,
+    error_policy: RwLock<ErrorPolicy>,
+    panic_policy: RwLock<PanicPolicy>,
+    /// Seed of the deterministic ordering mode, if it is on
+    deterministic: RwLock<Option<u64>>,
+    backpressure: Arc<RwLock<BackpressurePolicy>>,
+    observers: RwLock<Arc<Vec<Arc<dyn DispatchObserver>>>>,
+    listener_hooks: RwLock<ListenerHooks>,
+    balance: Mutex<BalanceState>,
+    delivery: RwLock<HashMap<TypeId, Delivery>>,
+    exports: RwLock<HashMap<Arc<str>, HashSet<TypeId>>>,
+    latency_window: Mutex<Option<LatencyWindow>>,
+    queues: RwLock<HashMap<TypeId, Arc<TypeQueue>>>,
+    meta_events: AtomicBool,
+    metrics_enabled: AtomicBool,
+    tenant_metrics: RwLock<HashMap<TenantId, TenantMetrics>>,
+    workers: Mutex<WorkerPool>,
+    #[cfg(feature = "async")]
+    watches: RwLock<HashMap<TypeId, Box<dyn std::any::Any + Send + Sync>>>,
 }
 
 impl EventDispatcher {
     /// Create a new event dispatcher
     pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    /// Create a new event dispatcher that reads time from `clock`
+    ///
+    /// Use a [`TestClock`](crate::TestClock) to make metrics timestamps
+    /// deterministic in tests.
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(clock);
+        let counters = DispatchCounters::default();
+        let health_baseline = Mutex::new(counters.snapshot(clock.now()));
+        let rate = DispatchRate::new(clock.now());
         Self {
+            id: NEXT_DISPATCHER_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
             listeners: Arc::new(RwLock::new(HashMap::new())),
             #[cfg(feature = "async")]
             async_listeners: Arc::new(RwLock::new(HashMap::new())),
+            #[cfg(feature = "async")]
+            async_bridge: RwLock::new(AsyncBridge::default()),
+            responders: RwLock::new(HashMap::new()),
             next_id: AtomicUsize::new(0),
             metrics: Arc::new(RwLock::new(HashMap::new())),
+            #[cfg(feature = "middleware")]
             middleware: Arc::new(RwLock::new(MiddlewareManager::new())),
+            clock: clock.clone(),
+            counters: Arc::new(counters),
+            rate,
+            health_baseline,
+            error_policy: RwLock::new(ErrorPolicy::default()),
+            panic_policy: RwLock::new(PanicPolicy::default()),
+            deterministic: RwLock::new(None),
+            backpressure: Arc::new(RwLock::new(BackpressurePolicy::default())),
+            observers: RwLock::new(Arc::new(Vec::new())),
+            listener_hooks: RwLock::new(Arc::new(Vec::new())),
+            balance: Mutex::new(BalanceState::default()),
+            delivery: RwLock::new(HashMap::new()),
+            exports: RwLock::new(HashMap::new()),
+            latency_window: Mutex::new(None),
+            queues: RwLock::new(HashMap::new()),
+            meta_events: AtomicBool::new(false),
+            metrics_enabled: AtomicBool::new(true),
+            tenant_metrics: RwLock::new(HashMap::new()),
+            workers: Mutex::new(WorkerPool::new(
+                std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+                clock,
+            )),
+            #[cfg(feature = "async")]
+            watches: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Get the clock this dispatcher reads time from
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
     // Subscribe to an event with a closure that can return errors
     ///
     /// # Example
@@ -90,11 +327,7 @@ impl EventDispatcher {
     ///     message: String,
     /// }
     ///
-    /// impl Event for MyEvent {
-    ///     fn as_any(&self) -> &dyn std::any::Any {
-    ///         self
-    ///     }
-    /// }
+    /// impl Event for MyEvent {}
     ///
     /// let dispatcher = EventDispatcher::new();
     /// dispatcher.subscribe(|event: &MyEvent| {
@@ -106,7 +339,7 @@ impl EventDispatcher {
     ///     Ok(())
     /// });
     /// ```
-    pub fn subscribe<T, F>(&self, listener: F) -> ListenerId
+    pub fn subscribe<T, F>(&self, listener: F) -> TypedListenerId<T>
     where
         T: Event + 'static,
         F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
@@ -114,342 +347,4333 @@ impl EventDispatcher {
         self.subscribe_with_priority(listener, Priority::Normal)
     }
 
-    /// Subscribe to an event with a specific priority
-    pub fn subscribe_with_priority<T, F>(&self, listener: F, priority: Priority) -> ListenerId
+    /// Subscribe a listener that is removed when the returned guard drops
+    ///
+    /// Like [`subscribe`](Self::subscribe), for listeners owned by a
+    /// component with a shorter lifetime than the dispatcher. See
+    /// [`SubscriptionGuard`].
+    pub fn subscribe_scoped<T, F>(self: &Arc<Self>, listener: F) -> SubscriptionGuard
     where
         T: Event + 'static,
         F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
     {
-        let type_id = TypeId::of::<T>();
-        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
-
-        let wrapper = ListenerWrapper::new(listener, priority, id);
-
-        let mut listeners = self.listeners.write().unwrap();
-        let event_listeners = listeners.entry(type_id).or_default();
-        event_listeners.push(wrapper);
-
-        // Sort by priority (highest first)
-        event_listeners.sort_by(|a, b| b.priority.cmp(&a.priority));
-
-        // Update metrics
-        drop(listeners); // Drop the lock before calling update_listener_count
-        self.update_listener_count::<T>();
-
-        ListenerId::new(id, type_id)
+        SubscriptionGuard::new(self, self.subscribe(listener))
     }
 
-    /// Subscribe to an event with simple closure (no error handling)
+    /// Subscribe to at most the next `n` events, then remove the listener
+    ///
+    /// Suits handshake-style protocols that only care about the first few
+    /// occurrences of an event. Equivalent to subscribing with
+    /// [`ListenerOptions::take`]; see [`once`](Self::once) for a single
+    /// event.
+    ///
+    /// # Panics
     ///
-    /// This is the most convenient method for simple event handling.
+    /// Panics if `n` is zero.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use mod_events::{EventDispatcher, Event};
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::{Arc, Mutex};
     ///
     /// #[derive(Debug, Clone)]
-    /// struct MyEvent {
-    ///     message: String,
+    /// struct Ack {
+    ///     seq: u32,
     /// }
     ///
-    /// impl Event for MyEvent {
-    ///     fn as_any(&self) -> &dyn std::any::Any {
-    ///         self
-    ///     }
-    /// }
+    /// impl Event for Ack {}
     ///
     /// let dispatcher = EventDispatcher::new();
-    /// dispatcher.on(|event: &MyEvent| {
-    ///     println!("Received: {}", event.message);
+    /// let acks = Arc::new(Mutex::new(Vec::new()));
+    /// let log = acks.clone();
+    /// dispatcher.subscribe_n(3, move |event: &Ack| {
+    ///     log.lock().unwrap().push(event.seq);
+    ///     Ok(())
     /// });
+    ///
+    /// for seq in 1..=5 {
+    ///     dispatcher.emit(Ack { seq });
+    /// }
+    /// assert_eq!(*acks.lock().unwrap(), [1, 2, 3]);
+    /// assert_eq!(dispatcher.listener_count::<Ack>(), 0);
     /// ```
-    pub fn on<T, F>(&self, listener: F) -> ListenerId
+    pub fn subscribe_n<T, F>(&self, n: usize, listener: F) -> TypedListenerId<T>
     where
         T: Event + 'static,
-        F: Fn(&T) + Send + Sync + 'static,
+        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
     {
-        self.subscribe(move |event: &T| {
-            listener(event);
-            Ok(())
-        })
+        self.insert_listener_with_options(
+            listener,
+            ListenerOptions::new().take(n),
+            std::any::type_name::<F>(),
+        )
+        .expect("listeners without ordering constraints can always be placed")
     }
 
-    /// Subscribe to an async event (requires "async" feature)
-    #[cfg(feature = "async")]
-    pub fn subscribe_async<T, F, Fut>(&self, listener: F) -> ListenerId
+    /// Subscribe to an event with a specific priority
+    pub fn subscribe_with_priority<T, F>(
+        &self,
+        listener: F,
+        priority: Priority,
+    ) -> TypedListenerId<T>
     where
         T: Event + 'static,
-        F: Fn(&T) -> Fut + Send + Sync + 'static,
-        Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>
-            + Send
-            + 'static,
+        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
     {
-        self.subscribe_async_with_priority(listener, Priority::Normal)
+        self.insert_listener(listener, priority, std::any::type_name::<F>())
     }
 
-    /// Subscribe to an async event with priority (requires "async" feature)
-    #[cfg(feature = "async")]
-    pub fn subscribe_async_with_priority<T, F, Fut>(
+    /// Subscribe with a closure returning any error type
+    ///
+    /// Accepts `Result<(), E>` for any `E` convertible into the boxed
+    /// error, so handlers can use `?` on their own error types without
+    /// `.map_err(Into::into)`. Closures that only ever return `Ok(())`
+    /// cannot infer `E`; use [`subscribe`](Self::subscribe) for those.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct PortChanged {
+    ///     port: String,
+    /// }
+    ///
+    /// impl Event for PortChanged {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.subscribe_fallible(|event: &PortChanged| {
+    ///     let port: u16 = event.port.parse()?;
+    ///     println!("Listening on {}", port);
+    ///     Ok::<_, std::num::ParseIntError>(())
+    /// });
+    ///
+    /// let result = dispatcher.dispatch(PortChanged {
+    ///     port: "http".to_string(),
+    /// });
+    /// assert!(result.errors()[0].is::<std::num::ParseIntError>());
+    /// ```
+    pub fn subscribe_fallible<T, F, E>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.subscribe_fallible_with_priority(listener, Priority::Normal)
+    }
+
+    /// Subscribe with a closure returning any error type and a specific priority
+    pub fn subscribe_fallible_with_priority<T, F, E>(
         &self,
         listener: F,
         priority: Priority,
-    ) -> ListenerId
+    ) -> TypedListenerId<T>
     where
         T: Event + 'static,
-        F: Fn(&T) -> Fut + Send + Sync + 'static,
-        Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>
-            + Send
-            + 'static,
+        F: Fn(&T) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
     {
-        let type_id = TypeId::of::<T>();
-        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
-
-        let wrapper = AsyncListenerWrapper::new(listener, priority, id);
-
-        let mut async_listeners = self.async_listeners.write().unwrap();
-        let event_listeners = async_listeners.entry(type_id).or_default();
-        event_listeners.push(wrapper);
+        self.insert_listener(
+            move |event: &T| listener(event).map_err(Into::into),
+            priority,
+            std::any::type_name::<F>(),
+        )
+    }
 
-        // Sort by priority (highest first)
-        event_listeners.sort_by(|a, b| b.priority.cmp(&a.priority));
+    /// Subscribe to an event with per-listener options
+    ///
+    /// See [`ListenerOptions`] for what can be configured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options' ordering constraints cannot be met; use
+    /// [`try_subscribe_with_options`](Self::try_subscribe_with_options)
+    /// to handle that case.
+    pub fn subscribe_with_options<T, F>(
+        &self,
+        listener: F,
+        options: ListenerOptions,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        self.try_subscribe_with_options(listener, options)
+            .unwrap_or_else(|error| panic!("invalid listener options: {error}"))
+    }
 
-        // Update metrics
-        drop(async_listeners); // Drop the lock before calling update_listener_count
-        self.update_listener_count::<T>();
+    /// Subscribe to an event with per-listener options, checking ordering constraints
+    ///
+    /// Fails if a listener named in [`ListenerOptions::after`] or
+    /// [`ListenerOptions::before`] is not subscribed to `T`, or if the
+    /// constraints contradict each other or the listener's priority.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, ListenerOptions, SubscribeError, Priority};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Request;
+    ///
+    /// impl Event for Request {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// let order = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let log = order.clone();
+    /// let handler = dispatcher.on(move |_: &Request| log.lock().unwrap().push("handler"));
+    ///
+    /// let log = order.clone();
+    /// dispatcher
+    ///     .try_subscribe_with_options(
+    ///         move |_: &Request| {
+    ///             log.lock().unwrap().push("auth");
+    ///             Ok(())
+    ///         },
+    ///         ListenerOptions::new().name("auth").before(handler),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// dispatcher.emit(Request);
+    /// assert_eq!(*order.lock().unwrap(), ["auth", "handler"]);
+    ///
+    /// // A high-priority listener cannot run after a normal-priority one
+    /// let conflict = dispatcher.try_subscribe_with_options(
+    ///     |_: &Request| Ok(()),
+    ///     ListenerOptions::new().priority(Priority::High).after_named("auth"),
+    /// );
+    /// assert!(matches!(conflict, Err(SubscribeError::Conflict(_))));
+    /// ```
+    pub fn try_subscribe_with_options<T, F>(
+        &self,
+        listener: F,
+        options: ListenerOptions,
+    ) -> Result<TypedListenerId<T>, SubscribeError>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        let name = options.name.unwrap_or(std::any::type_name::<F>());
+        self.insert_listener_with_options(listener, options, name)
+    }
 
-        ListenerId::new(id, type_id)
+    /// Subscribe an [`EventListener`] implementation
+    ///
+    /// The listener's [`priority`](EventListener::priority) is used and its
+    /// lifecycle callbacks are invoked. See
+    /// [`ListenerExt`](crate::ListenerExt) for decorators to wrap it with.
+    pub fn subscribe_listener<T, L>(&self, listener: L) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        L: EventListener<T> + 'static,
+    {
+        self.insert_managed_listener(listener, std::any::type_name::<L>())
     }
 
-    /// Dispatch an event synchronously
+    /// Subscribe a shared [`EventListener`] trait object
     ///
-    /// Returns a `DispatchResult` containing information about the dispatch.
+    /// Like [`subscribe_listener`](Self::subscribe_listener), for listeners
+    /// chosen at runtime or kept by the caller to inspect their state. The
+    /// listener's [`priority`](EventListener::priority) is used and its
+    /// lifecycle callbacks are invoked.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use mod_events::{EventDispatcher, Event};
+    /// use mod_events::{Event, EventDispatcher, EventListener, Priority};
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use std::sync::Arc;
     ///
     /// #[derive(Debug, Clone)]
-    /// struct MyEvent {
-    ///     message: String,
+    /// struct Payment {
+    ///     cents: u64,
+    /// }
+    ///
+    /// impl Event for Payment {}
+    ///
+    /// #[derive(Default)]
+    /// struct Ledger {
+    ///     total: AtomicU64,
     /// }
     ///
-    /// impl Event for MyEvent {
-    ///     fn as_any(&self) -> &dyn std::any::Any {
-    ///         self
+    /// impl EventListener<Payment> for Ledger {
+    ///     fn handle(&self, event: &Payment) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///         self.total.fetch_add(event.cents, Ordering::SeqCst);
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn priority(&self) -> Priority {
+    ///         Priority::High
     ///     }
     /// }
     ///
+    /// let ledger = Arc::new(Ledger::default());
     /// let dispatcher = EventDispatcher::new();
-    /// let result = dispatcher.dispatch(MyEvent {
-    ///     message: "Hello".to_string(),
-    /// });
+    /// let listener: Arc<dyn EventListener<Payment>> = ledger.clone();
+    /// dispatcher.add_listener(listener);
     ///
-    /// if result.all_succeeded() {
-    ///     println!("All listeners handled the event successfully");
-    /// }
+    /// dispatcher.emit(Payment { cents: 250 });
+    /// dispatcher.emit(Payment { cents: 100 });
+    /// assert_eq!(ledger.total.load(Ordering::SeqCst), 350);
     /// ```
-    pub fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
-        // Update metrics
-        self.update_metrics(&event);
-
-        // Check middleware
-        if !self.check_middleware(&event) {
-            return DispatchResult::blocked();
-        }
-
-        let type_id = TypeId::of::<T>();
-        let listeners = self.listeners.read().unwrap();
-        let mut results = Vec::new();
-
-        if let Some(event_listeners) = listeners.get(&type_id) {
-            results.reserve(event_listeners.len());
-            for listener in event_listeners {
-                results.push((listener.handler)(&event));
-            }
-        }
-
-        DispatchResult::new(results)
+    pub fn add_listener<T: Event + 'static>(
+        &self,
+        listener: Arc<dyn EventListener<T>>,
+    ) -> TypedListenerId<T> {
+        self.insert_managed_listener(
+            SharedListener(listener),
+            std::any::type_name::<dyn EventListener<T>>(),
+        )
     }
 
-    /// Dispatch an event asynchronously (requires "async" feature)
-    #[cfg(feature = "async")]
-    pub async fn dispatch_async<T: Event>(&self, event: T) -> DispatchResult {
-        // Update metrics
-        self.update_metrics(&event);
+    fn insert_managed_listener<T, L>(&self, listener: L, name: &'static str) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        L: EventListener<T> + 'static,
+    {
+        let priority = listener.priority();
+        let managed = Arc::new(ManagedListener::new(listener));
+        let handler = managed.clone();
+        let id = self.insert_listener(move |event: &T| handler.handle(event), priority, name);
+        managed.listener().on_subscribe(id.id());
+        id
+    }
 
-        // Check middleware
-        if !self.check_middleware(&event) {
-            return DispatchResult::blocked();
-        }
+    /// Subscribe a responder that answers an event with a value
+    ///
+    /// Responders run only for [`dispatch_collect`](Self::dispatch_collect)
+    /// and [`dispatch_aggregate`](Self::dispatch_aggregate) with the same
+    /// response type `R`; ordinary dispatch does not invoke them.
+    pub fn respond<T, R, F>(&self, responder: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        R: 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+    {
+        self.respond_with_priority(responder, Priority::Normal)
+    }
 
+    /// Subscribe a responder with a specific priority
+    pub fn respond_with_priority<T, R, F>(
+        &self,
+        responder: F,
+        priority: Priority,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        R: 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+    {
         let type_id = TypeId::of::<T>();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let handler: Box<dyn Fn(&T) -> R + Send + Sync> = Box::new(responder);
 
-        // Collect cloned handlers without holding the lock
-        let handlers: Vec<AsyncHandler> = {
-            let async_listeners = self.async_listeners.read().unwrap();
-            if let Some(event_listeners) = async_listeners.get(&type_id) {
-                event_listeners
-                    .iter()
-                    .map(|listener| listener.handler.clone())
-                    .collect()
-            } else {
-                Vec::new()
-            }
-        }; // Lock is dropped here
-
-        // Now execute all handlers without holding any locks
-        let mut results = Vec::with_capacity(handlers.len());
+        let mut responders = self.responders.write().unwrap();
+        let event_responders = responders.entry((type_id, TypeId::of::<R>())).or_default();
+        event_responders.push(ResponderWrapper {
+            handler: Arc::new(handler),
+            priority,
+            id,
+            name: std::any::type_name::<F>(),
+        });
+        event_responders.sort_by_key(|r| std::cmp::Reverse(r.priority));
 
-        for handler in handlers {
-            let future = handler(&event);
-            results.push(future.await);
-        }
+        drop(responders);
+        self.update_listener_count::<T>();
+        self.listener_added::<T>(id, std::any::type_name::<F>());
 
-        DispatchResult::new(results)
+        TypedListenerId::new(self.listener_id(id, type_id))
     }
 
-    /// Fire and forget - dispatch without waiting for results
+    /// Subscribe to an event through a channel
     ///
-    /// This is the most efficient way to dispatch events when you don't
-    /// need to check the results.
+    /// Every dispatched `T` is cloned into the returned receiver, so a
+    /// thread can consume events with an ordinary `recv` loop instead of a
+    /// callback. The channel is unbounded. Once the receiver is dropped,
+    /// the next dispatch removes the listener.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use mod_events::{EventDispatcher, Event};
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::Arc;
     ///
     /// #[derive(Debug, Clone)]
-    /// struct MyEvent {
-    ///     message: String,
+    /// struct Job {
+    ///     id: u32,
     /// }
     ///
-    /// impl Event for MyEvent {
-    ///     fn as_any(&self) -> &dyn std::any::Any {
-    ///         self
-    ///     }
+    /// impl Event for Job {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// let jobs = dispatcher.subscribe_channel::<Job>();
+    ///
+    /// let worker = std::thread::spawn(move || jobs.iter().take(3).map(|job| job.id).sum::<u32>());
+    ///
+    /// for id in 1..=3 {
+    ///     dispatcher.emit(Job { id });
     /// }
+    /// assert_eq!(worker.join().unwrap(), 6);
     ///
-    /// let dispatcher = EventDispatcher::new();
-    /// dispatcher.emit(MyEvent {
-    ///     message: "Fire and forget".to_string(),
-    /// });
+    /// // The worker dropped its receiver
+    /// dispatcher.emit(Job { id: 4 });
+    /// assert_eq!(dispatcher.listener_count::<Job>(), 0);
     /// ```
-    pub fn emit<T: Event>(&self, event: T) {
-        let _ = self.dispatch(event);
+    pub fn subscribe_channel<T>(&self) -> std::sync::mpsc::Receiver<T>
+    where
+        T: Event + Clone + 'static,
+    {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.insert_listener(
+            move |event: &T| {
+                sender
+                    .send(event.clone())
+                    .map_err(|_| ListenerClosed.into())
+            },
+            Priority::Normal,
+            "channel",
+        );
+        receiver
     }
 
-    /// Add middleware that can block events
-    ///
-    /// Middleware functions receive events and return `true` to allow
-    /// processing or `false` to block the event.
-    ///
-    /// # Example
-    ///
+    pub(crate) fn insert_listener<T, F>(
+        &self,
+        listener: F,
+        priority: Priority,
+        name: &'static str,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        self.insert_listener_with_options(listener, ListenerOptions::new().priority(priority), name)
+            .expect("listeners without ordering constraints can always be placed")
+    }
+
+    fn insert_listener_with_options<T, F, R>(
+        &self,
+        listener: F,
+        options: ListenerOptions,
+        name: &'static str,
+    ) -> Result<TypedListenerId<T>, SubscribeError>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        let type_id = TypeId::of::<T>();
+        if (options.first_match.is_some() || options.balanced.is_some())
+            && self.delivery_of(type_id) == Delivery::Unicast
+        {
+            return Err(SubscribeError::Unicast(std::any::type_name::<T>()));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        let mut wrapper = ListenerWrapper::new(listener, options.priority, id, name);
+        wrapper.rate_limiter = options.rate_limit.map(RateLimiter::new);
+        wrapper.group = options.first_match;
+        wrapper.balanced = options.balanced;
+        wrapper.namespace = options.namespace.clone();
+        wrapper.tenant = options.tenant.clone();
+        wrapper.deliveries_left = options.deliveries.map(AtomicUsize::new);
+        wrapper.event_name = options.event_name.clone();
+
+        let mut listeners = self.listeners.write().unwrap();
+        let event_listeners = listeners.entry(type_id).or_default();
+
+        // Keep listeners sorted by priority (highest first), honoring ordering constraints
+        let existing: Vec<_> = event_listeners
+            .iter()
+            .map(|l| (l.id, l.name, l.priority))
+            .collect();
+        let placement = options.placement(self.id, type_id, &existing)?;
+        wrapper.depends_on = placement.after;
+        wrapper.precedes = placement.before;
+        event_listeners.insert(placement.position, Arc::new(wrapper));
+
+        // Update metrics
+        drop(listeners); // Drop the lock before calling update_listener_count
+        self.update_listener_count::<T>();
+        self.listener_added::<T>(id, name);
+
+        Ok(TypedListenerId::new(self.listener_id(id, type_id)))
+    }
+
+    /// Subscribe with a closure returning `anyhow::Result` (requires "anyhow" feature)
+    ///
+    /// The error chain and context are preserved: the dispatch result's
+    /// error displays the outermost context and exposes the rest through
+    /// [`source`](std::error::Error::source).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use anyhow::Context;
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct ConfigChanged {
+    ///     path: String,
+    /// }
+    ///
+    /// impl Event for ConfigChanged {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.subscribe_anyhow(|event: &ConfigChanged| {
+    ///     std::fs::read_to_string(&event.path)
+    ///         .with_context(|| format!("reloading {}", event.path))?;
+    ///     Ok(())
+    /// });
+    ///
+    /// let result = dispatcher.dispatch(ConfigChanged {
+    ///     path: "/nonexistent/app.toml".to_string(),
+    /// });
+    /// let error = result.errors()[0];
+    /// assert_eq!(error.to_string(), "reloading /nonexistent/app.toml");
+    /// assert!(error.source().is_some());
+    /// ```
+    #[cfg(feature = "anyhow")]
+    pub fn subscribe_anyhow<T, F>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> anyhow::Result<()> + Send + Sync + 'static,
+    {
+        self.subscribe_anyhow_with_priority(listener, Priority::Normal)
+    }
+
+    /// Subscribe with a closure returning `anyhow::Result` and a specific priority
+    /// (requires "anyhow" feature)
+    #[cfg(feature = "anyhow")]
+    pub fn subscribe_anyhow_with_priority<T, F>(
+        &self,
+        listener: F,
+        priority: Priority,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> anyhow::Result<()> + Send + Sync + 'static,
+    {
+        self.subscribe_fallible_with_priority(listener, priority)
+    }
+
+    /// Subscribe to an event with a simple closure
+    ///
+    /// This is the most convenient method for simple event handling. The
+    /// closure may return `()` or any `Result<(), E>` accepted by
+    /// [`IntoListenerResult`]; when it only ever returns `Ok(())`, annotate
+    /// the error type or use [`subscribe`](Self::subscribe) instead.
+    /// Async listeners still use [`subscribe_async`](Self::subscribe_async).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{EventDispatcher, Event};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct MyEvent {
+    ///     message: String,
+    /// }
+    ///
+    /// impl Event for MyEvent {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.on(|event: &MyEvent| {
+    ///     println!("Received: {}", event.message);
+    /// });
+    /// ```
+    pub fn on<T, F, R>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        self.on_with_priority(listener, Priority::Normal)
+    }
+
+    /// Subscribe with a closure like [`on`](Self::on), with a specific priority
+    ///
+    /// Closures returning [`Handled`] can consume the event before
+    /// lower-priority listeners see it.
+    pub fn on_with_priority<T, F, R>(&self, listener: F, priority: Priority) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        self.insert_listener_with_options(
+            listener,
+            ListenerOptions::new().priority(priority),
+            std::any::type_name::<F>(),
+        )
+        .expect("listeners without ordering constraints can always be placed")
+    }
+
+    /// Subscribe a closure that handles only the next event of `T`
+    ///
+    /// The listener is removed after its first delivery, so it is never
+    /// invoked twice, even by concurrent dispatches. Removal happens once
+    /// the delivering dispatch has finished with the listener table, so
+    /// the closure may itself subscribe, unsubscribe or dispatch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::mpsc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Connected {
+    ///     session: u32,
+    /// }
+    ///
+    /// impl Event for Connected {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// let (ready, session) = mpsc::channel();
+    /// dispatcher.once(move |event: &Connected| ready.send(event.session));
+    ///
+    /// assert_eq!(dispatcher.dispatch(Connected { session: 7 }).listener_count(), 1);
+    /// assert_eq!(dispatcher.dispatch(Connected { session: 8 }).listener_count(), 0);
+    /// assert_eq!(dispatcher.listener_count::<Connected>(), 0);
+    /// assert_eq!(session.try_iter().collect::<Vec<_>>(), [7]);
+    /// ```
+    ///
+    /// Subscribing from inside the closure:
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Connected;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Message;
+    ///
+    /// impl Event for Connected {}
+    /// impl Event for Message {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// let handle = dispatcher.clone();
+    /// dispatcher.once(move |_: &Connected| {
+    ///     handle.on(|_: &Message| {});
+    /// });
+    ///
+    /// dispatcher.dispatch(Connected);
+    /// assert_eq!(dispatcher.listener_count::<Message>(), 1);
+    /// ```
+    pub fn once<T, F, R>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: FnOnce(&T) -> R + Send + 'static,
+        R: IntoListenerResult,
+    {
+        let listener = Mutex::new(Some(listener));
+        self.insert_listener_with_options(
+            move |event: &T| match listener.lock().unwrap().take() {
+                Some(listener) => listener(event).into_handled(),
+                None => Ok(Handled::Continue),
+            },
+            ListenerOptions::new().take(1),
+            std::any::type_name::<F>(),
+        )
+        .expect("listeners without ordering constraints can always be placed")
+    }
+
+    /// Subscribe with a closure like [`on`](Self::on), with per-listener options
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options' ordering constraints cannot be met, like
+    /// [`subscribe_with_options`](Self::subscribe_with_options).
+    pub fn on_with_options<T, F, R>(
+        &self,
+        listener: F,
+        options: ListenerOptions,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        let name = options.name.unwrap_or(std::any::type_name::<F>());
+        self.insert_listener_with_options(listener, options, name)
+            .unwrap_or_else(|error| panic!("invalid listener options: {error}"))
+    }
+
+    /// Get a view of this dispatcher scoped to the namespace `name`
+    ///
+    /// See [`Namespace`] for how namespaced listeners and events are
+    /// isolated from one another.
+    pub fn namespace(&self, name: impl Into<Arc<str>>) -> Namespace<'_> {
+        Namespace {
+            dispatcher: self,
+            name: name.into(),
+        }
+    }
+
+    /// Subscribe one closure to every event type in a group
+    ///
+    /// The group is an enum defined with [`event_group!`](crate::event_group);
+    /// each member event is cloned into its variant before the closure runs.
+    /// Returns one id per member type.
+    pub fn on_each<G, F, R>(&self, listener: F) -> Vec<ListenerId>
+    where
+        G: EventGroup,
+        F: Fn(G) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        self.on_each_with_priority(listener, Priority::Normal)
+    }
+
+    /// Subscribe one closure to every event type in a group with a specific priority
+    pub fn on_each_with_priority<G, F, R>(&self, listener: F, priority: Priority) -> Vec<ListenerId>
+    where
+        G: EventGroup,
+        F: Fn(G) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        G::subscribe_group(
+            self,
+            Arc::new(move |event| listener(event).into_listener_result()),
+            priority,
+        )
+    }
+
+    /// Subscribe to dynamic events with the given name
+    ///
+    /// The listener only receives [`DynamicEvent`]s whose name matches
+    /// exactly; dispatches of other names do not count it in their
+    /// result. Unsubscribe with the returned `ListenerId` as usual.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{DynamicEvent, EventDispatcher};
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.subscribe_named("order.placed", |event: &DynamicEvent| {
+    ///     println!("Order payload is {} bytes", event.payload().len());
+    ///     Ok(())
+    /// });
+    /// dispatcher.subscribe_named("order.shipped", |_: &DynamicEvent| Ok(()));
+    ///
+    /// let result = dispatcher.dispatch(DynamicEvent::new("order.placed", b"42".to_vec()));
+    /// assert_eq!(result.success_count(), 1);
+    /// ```
+    pub fn subscribe_named<F>(
+        &self,
+        name: impl Into<String>,
+        listener: F,
+    ) -> TypedListenerId<DynamicEvent>
+    where
+        F: Fn(&DynamicEvent) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.subscribe_named_with_priority(name, listener, Priority::Normal)
+    }
+
+    /// Subscribe to dynamic events with the given name and priority
+    pub fn subscribe_named_with_priority<F>(
+        &self,
+        name: impl Into<String>,
+        listener: F,
+        priority: Priority,
+    ) -> TypedListenerId<DynamicEvent>
+    where
+        F: Fn(&DynamicEvent) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let mut options = ListenerOptions::new().priority(priority);
+        options.event_name = Some(name.into());
+        self.insert_listener_with_options(listener, options, std::any::type_name::<F>())
+            .expect("listeners without ordering constraints can always be placed")
+    }
+
+    /// Watch the most recent event of type `T` (requires "async" feature)
+    ///
+    /// The receiver holds `None` until a `T` is dispatched, then the latest
+    /// `T`, and is notified on every change. Suited to configuration and
+    /// state events where only the current value matters. All watchers of
+    /// a type share one listener, so a watcher created later still sees
+    /// the latest event dispatched since the first `watch::<T>()` call.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct ConfigChanged {
+    ///     log_level: &'static str,
+    /// }
+    ///
+    /// impl Event for ConfigChanged {}
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let dispatcher = EventDispatcher::new();
+    /// let mut config = dispatcher.watch::<ConfigChanged>();
+    /// assert!(config.borrow().is_none());
+    ///
+    /// dispatcher.emit(ConfigChanged { log_level: "info" });
+    /// dispatcher.emit(ConfigChanged { log_level: "debug" });
+    ///
+    /// config.changed().await.unwrap();
+    /// assert_eq!(config.borrow().as_ref().unwrap().log_level, "debug");
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn watch<T>(&self) -> tokio::sync::watch::Receiver<Option<T>>
+    where
+        T: Event + Clone + 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        if let Some(sender) = self.watches.read().unwrap().get(&type_id) {
+            if let Some(sender) = sender.downcast_ref::<tokio::sync::watch::Sender<Option<T>>>() {
+                return sender.subscribe();
+            }
+        }
+
+        let mut watches = self.watches.write().unwrap();
+        if let Some(sender) = watches
+            .get(&type_id)
+            .and_then(|sender| sender.downcast_ref::<tokio::sync::watch::Sender<Option<T>>>())
+        {
+            return sender.subscribe();
+        }
+
+        let (sender, receiver) = tokio::sync::watch::channel(None);
+        let latest = sender.clone();
+        self.insert_listener(
+            move |event: &T| {
+                latest.send_replace(Some(event.clone()));
+                Ok(())
+            },
+            Priority::Normal,
+            "watch",
+        );
+        watches.insert(type_id, Box::new(sender));
+        receiver
+    }
+
+    /// Subscribe to an event through a bounded tokio channel (requires "async" feature)
+    ///
+    /// Every dispatched `T` is cloned into a buffer of `capacity` events
+    /// owned by this consumer alone. When the buffer is full, the
+    /// dispatcher's [`BackpressurePolicy`] decides whether the event is
+    /// dropped, reported as a listener error, or waited for. Waiting is
+    /// only possible outside a tokio runtime; inside one, a full buffer
+    /// is reported as an error instead. Once the receiver is dropped, the
+    /// next dispatch removes the listener.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{BackpressurePolicy, Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Tick(u32);
+    ///
+    /// impl Event for Tick {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.set_backpressure_policy(BackpressurePolicy::Fail);
+    /// let mut ticks = dispatcher.subscribe_mpsc::<Tick>(2);
+    ///
+    /// assert!(dispatcher.dispatch(Tick(1)).all_succeeded());
+    /// assert!(dispatcher.dispatch(Tick(2)).all_succeeded());
+    /// assert!(dispatcher.dispatch(Tick(3)).has_errors());
+    ///
+    /// assert_eq!(ticks.try_recv().unwrap().0, 1);
+    /// assert_eq!(ticks.try_recv().unwrap().0, 2);
+    /// assert!(ticks.try_recv().is_err());
+    ///
+    /// drop(ticks);
+    /// dispatcher.dispatch(Tick(4));
+    /// assert_eq!(dispatcher.listener_count::<Tick>(), 0);
+    /// ```
+    ///
+    /// Blocking is not possible inside a runtime:
+    ///
+    /// ```rust
+    /// # use mod_events::{BackpressurePolicy, Event, EventDispatcher};
+    /// # #[derive(Debug, Clone)]
+    /// # struct Tick(u32);
+    /// # impl Event for Tick {}
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.set_backpressure_policy(BackpressurePolicy::Block);
+    /// let _ticks = dispatcher.subscribe_mpsc::<Tick>(1);
+    ///
+    /// assert!(dispatcher.dispatch(Tick(1)).all_succeeded());
+    /// assert!(dispatcher.dispatch(Tick(2)).has_errors());
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn subscribe_mpsc<T>(&self, capacity: usize) -> tokio::sync::mpsc::Receiver<T>
+    where
+        T: Event + Clone + 'static,
+    {
+        use tokio::sync::mpsc::error::TrySendError;
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+        let backpressure = self.backpressure.clone();
+        self.insert_listener(
+            move |event: &T| {
+                let policy = *backpressure.read().unwrap();
+                match sender.try_send(event.clone()) {
+                    Ok(()) => Ok(()),
+                    Err(TrySendError::Closed(_)) => Err(ListenerClosed.into()),
+                    Err(TrySendError::Full(event)) => match policy {
+                        BackpressurePolicy::DropNewest => Ok(()),
+                        BackpressurePolicy::Fail => Err("subscriber buffer is full".into()),
+                        // `blocking_send` panics inside a runtime
+                        BackpressurePolicy::Block
+                            if tokio::runtime::Handle::try_current().is_ok() =>
+                        {
+                            Err("subscriber buffer is full; cannot block inside a runtime".into())
+                        }
+                        BackpressurePolicy::Block => match sender.blocking_send(event) {
+                            Ok(()) => Ok(()),
+                            Err(_) => Err(ListenerClosed.into()),
+                        },
+                    },
+                }
+            },
+            Priority::Normal,
+            "mpsc",
+        );
+        receiver
+    }
+
+    /// Subscribe to an async event (requires "async" feature)
+    #[cfg(feature = "async")]
+    pub fn subscribe_async<T, F, Fut>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>
+            + Send
+            + 'static,
+    {
+        self.subscribe_async_with_priority(listener, Priority::Normal)
+    }
+
+    /// Subscribe to an async event with priority (requires "async" feature)
+    #[cfg(feature = "async")]
+    pub fn subscribe_async_with_priority<T, F, Fut>(
+        &self,
+        listener: F,
+        priority: Priority,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>
+            + Send
+            + 'static,
+    {
+        self.subscribe_async_with_options(listener, ListenerOptions::new().priority(priority))
+    }
+
+    /// Subscribe to an async event with per-listener options (requires "async" feature)
+    ///
+    /// Honors the priority, name and
+    /// [`max_concurrency`](ListenerOptions::max_concurrency) options; rate
+    /// limits apply to sync listeners only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, ListenerOptions};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct RowChanged;
+    ///
+    /// impl Event for RowChanged {}
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// let in_flight = Arc::new(AtomicUsize::new(0));
+    /// let peak = Arc::new(AtomicUsize::new(0));
+    ///
+    /// let (current, highest) = (in_flight.clone(), peak.clone());
+    /// dispatcher.subscribe_async_with_options(
+    ///     move |_: &RowChanged| {
+    ///         let (current, highest) = (current.clone(), highest.clone());
+    ///         async move {
+    ///             let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+    ///             highest.fetch_max(now, Ordering::SeqCst);
+    ///             tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    ///             current.fetch_sub(1, Ordering::SeqCst);
+    ///             Ok(())
+    ///         }
+    ///     },
+    ///     ListenerOptions::new().name("db-writer").max_concurrency(1),
+    /// );
+    ///
+    /// let tasks: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let dispatcher = dispatcher.clone();
+    ///         tokio::spawn(async move { dispatcher.dispatch_async(RowChanged).await })
+    ///     })
+    ///     .collect();
+    /// for task in tasks {
+    ///     task.await.unwrap();
+    /// }
+    /// assert_eq!(peak.load(Ordering::SeqCst), 1);
+    /// # });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options' ordering constraints cannot be met among the
+    /// async listeners of `T`.
+    #[cfg(feature = "async")]
+    pub fn subscribe_async_with_options<T, F, Fut>(
+        &self,
+        listener: F,
+        options: ListenerOptions,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>
+            + Send
+            + 'static,
+    {
+        let name = options.name.unwrap_or(std::any::type_name::<F>());
+        self.insert_async_listener(AsyncListenerWrapper::closure(listener), options, name)
+    }
+
+    /// Subscribe an [`AsyncEventListener`] implementation (requires "async" feature)
+    ///
+    /// The listener's [`priority`](AsyncEventListener::priority) is used and
+    /// its lifecycle callbacks are invoked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{AsyncEventListener, AsyncEventResult, Event, EventDispatcher};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Upload;
+    ///
+    /// impl Event for Upload {}
+    ///
+    /// #[derive(Default)]
+    /// struct Uploader {
+    ///     connections: AtomicUsize,
+    ///     uploads: AtomicUsize,
+    /// }
+    ///
+    /// impl AsyncEventListener<Upload> for Arc<Uploader> {
+    ///     fn handle<'a>(&'a self, _event: &'a Upload) -> AsyncEventResult<'a> {
+    ///         Box::pin(async move {
+    ///             self.uploads.fetch_add(1, Ordering::SeqCst);
+    ///             Ok(())
+    ///         })
+    ///     }
+    ///
+    ///     fn on_first_event<'a>(&'a self, _event: &'a Upload) -> AsyncEventResult<'a> {
+    ///         Box::pin(async move {
+    ///             self.connections.fetch_add(1, Ordering::SeqCst);
+    ///             Ok(())
+    ///         })
+    ///     }
+    ///
+    ///     fn on_unsubscribe(&self) {
+    ///         self.connections.fetch_sub(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let uploader = Arc::new(Uploader::default());
+    /// let dispatcher = EventDispatcher::new();
+    /// let id = dispatcher.subscribe_async_listener(uploader.clone());
+    ///
+    /// dispatcher.dispatch_async(Upload).await;
+    /// dispatcher.dispatch_async(Upload).await;
+    /// assert_eq!(uploader.connections.load(Ordering::SeqCst), 1);
+    /// assert_eq!(uploader.uploads.load(Ordering::SeqCst), 2);
+    ///
+    /// dispatcher.unsubscribe(id);
+    /// assert_eq!(uploader.connections.load(Ordering::SeqCst), 0);
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn subscribe_async_listener<T, L>(&self, listener: L) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        L: AsyncEventListener<T> + 'static,
+    {
+        self.insert_managed_async_listener(listener, std::any::type_name::<L>())
+    }
+
+    /// Subscribe a shared [`AsyncEventListener`] trait object (requires "async" feature)
+    ///
+    /// Like [`subscribe_async_listener`](Self::subscribe_async_listener),
+    /// for struct-based handlers chosen at runtime or kept by the caller
+    /// to inspect their state. The listener's
+    /// [`priority`](AsyncEventListener::priority) is used and its
+    /// lifecycle callbacks are invoked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{AsyncEventListener, AsyncEventResult, Event, EventDispatcher};
+    /// use std::sync::Arc;
+    /// use tokio::sync::Mutex;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Indexed {
+    ///     doc: String,
+    /// }
+    ///
+    /// impl Event for Indexed {}
+    ///
+    /// #[derive(Default)]
+    /// struct SearchIndex {
+    ///     docs: Mutex<Vec<String>>,
+    /// }
+    ///
+    /// impl AsyncEventListener<Indexed> for SearchIndex {
+    ///     fn handle<'a>(&'a self, event: &'a Indexed) -> AsyncEventResult<'a> {
+    ///         Box::pin(async move {
+    ///             self.docs.lock().await.push(event.doc.clone());
+    ///             Ok(())
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let index = Arc::new(SearchIndex::default());
+    /// let dispatcher = EventDispatcher::new();
+    /// let listener: Arc<dyn AsyncEventListener<Indexed>> = index.clone();
+    /// dispatcher.add_async_listener(listener);
+    ///
+    /// dispatcher.dispatch_async(Indexed { doc: "readme".into() }).await;
+    /// assert_eq!(*index.docs.lock().await, ["readme"]);
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn add_async_listener<T: Event + 'static>(
+        &self,
+        listener: Arc<dyn AsyncEventListener<T>>,
+    ) -> TypedListenerId<T> {
+        self.insert_managed_async_listener(
+            SharedAsyncListener(listener),
+            std::any::type_name::<dyn AsyncEventListener<T>>(),
+        )
+    }
+
+    #[cfg(feature = "async")]
+    fn insert_managed_async_listener<T, L>(
+        &self,
+        listener: L,
+        name: &'static str,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        L: AsyncEventListener<T> + 'static,
+    {
+        let options = ListenerOptions::new().priority(listener.priority());
+        let managed = Arc::new(ManagedAsyncListener::new(listener));
+        let id = self.insert_async_listener(
+            AsyncListenerWrapper::managed(managed.clone()),
+            options,
+            name,
+        );
+        managed.listener().on_subscribe(id.id());
+        id
+    }
+
+    #[cfg(feature = "async")]
+    fn insert_async_listener<T: Event + 'static>(
+        &self,
+        handler: Arc<crate::AsyncEventHandler>,
+        options: ListenerOptions,
+        name: &'static str,
+    ) -> TypedListenerId<T> {
+        let type_id = TypeId::of::<T>();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        let wrapper = AsyncListenerWrapper::new(
+            handler,
+            options.priority,
+            id,
+            name,
+            options.max_concurrency.unwrap_or(1),
+        );
+
+        let mut async_listeners = self.async_listeners.write().unwrap();
+        let event_listeners = async_listeners.entry(type_id).or_default();
+
+        // Keep listeners sorted by priority (highest first), honoring ordering constraints
+        let existing: Vec<_> = event_listeners
+            .iter()
+            .map(|l| (l.id, l.name, l.priority))
+            .collect();
+        let placement = options
+            .placement(self.id, type_id, &existing)
+            .unwrap_or_else(|error| panic!("invalid listener options: {error}"));
+        event_listeners.insert(placement.position, wrapper);
+
+        // Update metrics
+        drop(async_listeners); // Drop the lock before calling update_listener_count
+        self.update_listener_count::<T>();
+        self.listener_added::<T>(id, name);
+
+        TypedListenerId::new(self.listener_id(id, type_id))
+    }
+
+    /// Dispatch an event synchronously
+    ///
+    /// Returns a `DispatchResult` containing information about the dispatch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{EventDispatcher, Event};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct MyEvent {
+    ///     message: String,
+    /// }
+    ///
+    /// impl Event for MyEvent {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// let result = dispatcher.dispatch(MyEvent {
+    ///     message: "Hello".to_string(),
+    /// });
+    ///
+    /// if result.all_succeeded() {
+    ///     println!("All listeners handled the event successfully");
+    /// }
+    /// ```
+    pub fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        self.dispatch_event(&event)
+    }
+
+    /// Dispatch an event through an [`EventRef`] without copying it
+    ///
+    /// See [`EventRef`] for the supported handles.
+    pub fn dispatch_borrowed<E: EventRef>(&self, event: E) -> DispatchResult {
+        self.dispatch_event(event.event())
+    }
+
+    /// Dispatch an event and collect every `R` response
+    ///
+    /// Responders registered with [`respond`](Self::respond) run in
+    /// priority order. If middleware blocks the event, no responders run
+    /// and the result is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct HealthCheck;
+    ///
+    /// impl Event for HealthCheck {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.respond(|_: &HealthCheck| "db: ok");
+    /// dispatcher.respond(|_: &HealthCheck| "cache: ok");
+    ///
+    /// assert_eq!(dispatcher.dispatch_collect::<_, &str>(HealthCheck), ["db: ok", "cache: ok"]);
+    /// ```
+    pub fn dispatch_collect<T: Event, R: 'static>(&self, event: T) -> Vec<R> {
+        self.dispatch_aggregate(event, Vec::new())
+    }
+
+    /// Dispatch an event and reduce the `R` responses with an [`Aggregator`]
+    ///
+    /// Responses are folded as each responder returns, and dispatch stops
+    /// as soon as the aggregator breaks. If middleware blocks the event,
+    /// no responders run and the aggregator is finished without input.
+    /// Responders may subscribe or unsubscribe while they run.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Discover;
+    ///
+    /// impl Event for Discover {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// let handle = dispatcher.clone();
+    /// // Registers a second node the first time it is asked
+    /// dispatcher.respond(move |_: &Discover| {
+    ///     if handle.listener_count::<Discover>() == 1 {
+    ///         handle.respond(|_: &Discover| "node-b");
+    ///     }
+    ///     "node-a"
+    /// });
+    ///
+    /// assert_eq!(dispatcher.dispatch_aggregate(Discover, Vec::<&str>::new()), ["node-a"]);
+    /// assert_eq!(dispatcher.dispatch_collect::<_, &str>(Discover), ["node-a", "node-b"]);
+    /// ```
+    pub fn dispatch_aggregate<T, R, A>(&self, event: T, mut aggregator: A) -> A::Output
+    where
+        T: Event,
+        R: 'static,
+        A: Aggregator<R>,
+    {
+        let type_id = TypeId::of::<T>();
+        self.update_metrics(&event);
+        if self
+            .check_middleware(&event, &DispatchContext::default())
+            .is_err()
+        {
+            return aggregator.finish();
+        }
+
+        // Snapshot the handlers so responders run without the table locked
+        let handlers: Vec<_> = self
+            .responders
+            .read()
+            .unwrap()
+            .get(&(type_id, TypeId::of::<R>()))
+            .map(|event_responders| {
+                event_responders
+                    .iter()
+                    .map(|responder| responder.handler.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        for handler in handlers {
+            let handler = handler
+                .downcast_ref::<Box<dyn Fn(&T) -> R + Send + Sync>>()
+                .expect("responders are keyed by event and response type");
+            if aggregator.fold(handler(&event)).is_break() {
+                break;
+            }
+        }
+        aggregator.finish()
+    }
+
+    /// Dispatch an event by reference
+    ///
+    /// The caller keeps ownership, so the event can be reused or returned
+    /// to an [`EventPool`](crate::EventPool) after dispatch.
+    pub fn dispatch_ref<T: Event>(&self, event: &T) -> DispatchResult {
+        self.dispatch_event(event)
+    }
+
+    pub(crate) fn dispatch_event<T: Event>(&self, event: &T) -> DispatchResult {
+        self.dispatch_event_within(
+            event,
+            None,
+            self.error_policy(),
+            &DispatchContext::default(),
+        )
+    }
+
+    /// Dispatch an event, stopping at the first listener error
+    ///
+    /// Behaves like [`dispatch`](Self::dispatch) under
+    /// [`ErrorPolicy::StopOnError`] regardless of the dispatcher's policy.
+    /// Listeners that never ran are reported by [`DispatchResult::skipped`].
+    pub fn dispatch_until_err<T: Event>(&self, event: T) -> DispatchResult {
+        self.dispatch_event_within(
+            &event,
+            None,
+            ErrorPolicy::StopOnError,
+            &DispatchContext::default(),
+        )
+    }
+
+    /// Dispatch an event within a time budget
+    ///
+    /// Listeners run in priority order until `budget` has been exceeded;
+    /// the remaining lower-priority listeners are skipped and reported by
+    /// [`DispatchResult::skipped`]. A listener that is already running is
+    /// never interrupted, so the budget can be overrun by at most one
+    /// listener. Time is measured with the dispatcher's [`Clock`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Priority, TestClock};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Frame;
+    ///
+    /// impl Event for Frame {}
+    ///
+    /// let clock = TestClock::new();
+    /// let dispatcher = EventDispatcher::with_clock(clock.clone());
+    ///
+    /// dispatcher.subscribe_with_priority(
+    ///     move |_: &Frame| {
+    ///         clock.advance(Duration::from_millis(3));
+    ///         Ok(())
+    ///     },
+    ///     Priority::High,
+    /// );
+    /// let cosmetic = dispatcher.subscribe_with_priority(|_: &Frame| Ok(()), Priority::Low);
+    ///
+    /// let result = dispatcher.dispatch_with_budget(Frame, Duration::from_millis(2));
+    /// assert_eq!(result.listener_count(), 1);
+    /// assert_eq!(result.skipped(), &[cosmetic]);
+    /// ```
+    pub fn dispatch_with_budget<T: Event>(&self, event: T, budget: Duration) -> DispatchResult {
+        let deadline = self.clock.now() + budget;
+        self.dispatch_event_within(
+            &event,
+            Some(deadline),
+            self.error_policy(),
+            &DispatchContext::default(),
+        )
+    }
+
+    /// Dispatch a boxed event, routing by its runtime type
+    ///
+    /// For deserialization layers and plugin hosts that only have a
+    /// `Box<dyn Event>`. Listeners of the concrete type run exactly as if
+    /// the event had been passed to [`dispatch`](Self::dispatch).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Ping;
+    ///
+    /// impl Event for Ping {}
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Pong;
+    ///
+    /// impl Event for Pong {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.on(|_: &Pong| println!("pong"));
+    ///
+    /// let incoming: Vec<Box<dyn Event>> = vec![Box::new(Ping), Box::new(Pong)];
+    /// let handled: usize = incoming
+    ///     .into_iter()
+    ///     .map(|event| dispatcher.dispatch_boxed(event).listener_count())
+    ///     .sum();
+    /// assert_eq!(handled, 1);
+    /// ```
+    pub fn dispatch_boxed(&self, event: Box<dyn Event>) -> DispatchResult {
+        self.dispatch_dyn(&*event)
+    }
+
+    /// Dispatch an event through a `&dyn Event` reference
+    ///
+    /// Routes by the event's runtime type, for forwarding bridges and
+    /// middleware that re-emit events without knowing their concrete type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Audit;
+    ///
+    /// impl Event for Audit {}
+    ///
+    /// let primary = EventDispatcher::new();
+    /// let mirror = Arc::new(EventDispatcher::new());
+    /// mirror.on(|_: &Audit| println!("mirrored"));
+    ///
+    /// let forward = mirror.clone();
+    /// primary.add_middleware(move |event| {
+    ///     forward.dispatch_dyn(event);
+    ///     true
+    /// });
+    ///
+    /// primary.emit(Audit);
+    /// assert_eq!(mirror.metrics().values().next().unwrap().dispatch_count, 1);
+    /// ```
+    pub fn dispatch_dyn(&self, event: &dyn Event) -> DispatchResult {
+        self.dispatch_event_within(
+            event,
+            None,
+            self.error_policy(),
+            &DispatchContext::default(),
+        )
+    }
+
+    /// Dispatch an event with a [`DispatchContext`] visible to middleware
+    ///
+    /// Use this to pass request-scoped data, such as the acting principal
+    /// checked by [`AuthzMiddleware`](crate::AuthzMiddleware).
+    pub fn dispatch_with_context<T: Event>(
+        &self,
+        event: T,
+        context: &DispatchContext,
+    ) -> DispatchResult {
+        self.dispatch_event_within(&event, None, self.error_policy(), context)
+    }
+
+    fn dispatch_event_within(
+        &self,
+        event: &dyn Event,
+        deadline: Option<Instant>,
+        policy: ErrorPolicy,
+        context: &DispatchContext,
+    ) -> DispatchResult {
+        let type_id = event.as_any().type_id();
+        let started = self.clock.now();
+
+        // Update metrics
+        self.update_metrics(event);
+
+        // Check middleware
+        if let Err(reason) = self.check_middleware(event, context) {
+            let result =
+                self.record_outcome(type_id, event, started, DispatchResult::blocked(reason));
+            self.record_tenant(context, &result);
+            return result;
+        }
+
+        let observers = self.observers();
+        let hooks = self.listener_hooks();
+        let mut results = Vec::new();
+        let mut skipped = Vec::new();
+        let mut stopped_by = None;
+        let mut rate_limited = 0;
+        let mut handled_groups = Vec::new();
+        let mut spent = Vec::new();
+        let panics = self.panic_policy();
+
+        {
+            let event_listeners = self.eligible_listeners(type_id, event, context);
+            results.reserve(event_listeners.len());
+            for (index, listener) in event_listeners.iter().enumerate() {
+                if deadline.is_some_and(|deadline| self.clock.now() > deadline) {
+                    skipped.extend(
+                        event_listeners[index..]
+                            .iter()
+                            .map(|listener| self.listener_id(listener.id, type_id)),
+                    );
+                    break;
+                }
+                let id = self.listener_id(listener.id, type_id);
+                if listener
+                    .group
+                    .is_some_and(|group| handled_groups.contains(&group))
+                {
+                    skipped.push(id);
+                    continue;
+                }
+                if let Some(limiter) = &listener.rate_limiter {
+                    if !limiter.try_acquire(self.clock.now()) {
+                        skipped.push(id);
+                        rate_limited += 1;
+                        continue;
+                    }
+                }
+                // Another dispatch took the last delivery
+                if !listener.claim_delivery() {
+                    skipped.push(id);
+                    continue;
+                }
+                let info = ListenerInfo {
+                    id,
+                    name: listener.name,
+                    priority: listener.priority,
+                };
+                let listener_started =
+                    self.start_listener(observers.as_ref(), hooks.as_ref(), event, &info);
+                let result = listener.call(event, panics.catches());
+                if listener.is_spent() {
+                    spent.push(id);
+                }
+                let stop = matches!(result, Ok(Handled::Stop));
+                if let (Some(group), Ok(Handled::Continue | Handled::Stop)) =
+                    (listener.group, &result)
+                {
+                    handled_groups.push(group);
+                }
+                let result = result
+                    .map(drop)
+                    .map_err(|source| ListenerError::from_listener(id, event.event_name(), source));
+                self.observe_listener(
+                    observers.as_ref(),
+                    hooks.as_ref(),
+                    listener_started,
+                    event,
+                    &info,
+                    &result,
+                );
+                let halt = halts(policy, panics, &result);
+                results.push((id, listener.name, result));
+
+                if stop {
+                    stopped_by = Some(id);
+                }
+                if stop || halt {
+                    skipped.extend(
+                        event_listeners[index + 1..]
+                            .iter()
+                            .map(|listener| self.listener_id(listener.id, type_id)),
+                    );
+                    break;
+                }
+            }
+        }
+        self.remove_spent(spent);
+
+        #[cfg(feature = "async")]
+        let async_skipped = {
+            let halted = stopped_by.is_some()
+                || results
+                    .iter()
+                    .any(|(_, _, result)| halts(policy, panics, result));
+            self.bridge_async(event, policy, halted, &mut results, &mut skipped)
+        };
+
+        if rate_limited > 0 && self.metrics_enabled() {
+            if let Some(meta) = self.metrics.write().unwrap().get_mut(&type_id) {
+                meta.add_rate_limited(rate_limited);
+            }
+        }
+
+        let result = DispatchResult::new(results)
+            .with_skipped(skipped)
+            .with_stopped_by(stopped_by);
+        #[cfg(feature = "async")]
+        let result = result.with_async_skipped(async_skipped);
+        let result = self.record_outcome(type_id, event, started, result);
+        self.record_tenant(context, &result);
+        result
+    }
+
+    /// Run the async listeners of a synchronous dispatch through the
+    /// [`AsyncBridge`], returning how many were left out
+    ///
+    /// Listeners of a halted dispatch are reported as skipped instead.
+    #[cfg(feature = "async")]
+    fn bridge_async(
+        &self,
+        event: &dyn Event,
+        policy: ErrorPolicy,
+        halted: bool,
+        results: &mut Vec<(ListenerId, &'static str, Result<(), ListenerError>)>,
+        skipped: &mut Vec<ListenerId>,
+    ) -> usize {
+        let type_id = event.as_any().type_id();
+        let mut handlers: Vec<(ListenerInfo, AsyncHandler, Arc<tokio::sync::Semaphore>)> = {
+            let async_listeners = self.async_listeners.read().unwrap();
+            let Some(event_listeners) = async_listeners.get(&type_id) else {
+                return 0;
+            };
+            event_listeners
+                .iter()
+                .map(|listener| {
+                    let info = ListenerInfo {
+                        id: self.listener_id(listener.id, type_id),
+                        name: listener.name,
+                        priority: listener.priority,
+                    };
+                    (info, listener.handler.clone(), listener.permits.clone())
+                })
+                .collect()
+        };
+        if !handlers.is_empty() && self.delivery_of(type_id) == Delivery::Unicast {
+            // A sync listener already consumed the event
+            if !results.is_empty() {
+                return 0;
+            }
+            let mut balance = self.balance.lock().unwrap();
+            let turn = balance
+                .turns
+                .entry((type_id, UNICAST_ASYNC_POOL))
+                .or_default();
+            let chosen = *turn % handlers.len();
+            *turn = chosen + 1;
+            handlers = vec![handlers.swap_remove(chosen)];
+        }
+        if halted {
+            skipped.extend(handlers.iter().map(|(info, _, _)| info.id));
+            return 0;
+        }
+        let bridge = self.async_bridge.read().unwrap().clone();
+        let handle = match bridge {
+            AsyncBridge::Block(handle) if tokio::runtime::Handle::try_current().is_err() => handle,
+            AsyncBridge::Warn if !is_meta_event(type_id) => {
+                let skipped = handlers.len();
+                self.emit_meta(|| AsyncListenersSkipped {
+                    event: event.event_name(),
+                    skipped,
+                });
+                return skipped;
+            }
+            AsyncBridge::Error => {
+                results.extend(handlers.iter().map(|(info, _, _)| {
+                    let error = ListenerError::AsyncUnsupported {
+                        listener: info.id,
+                        event: event.event_name(),
+                    };
+                    (info.id, info.name, Err(error))
+                }));
+                return 0;
+            }
+            _ => return handlers.len(),
+        };
+
+        let panics = self.panic_policy();
+        let observers = self.observers();
+        let hooks = self.listener_hooks();
+        handle.block_on(async {
+            for (index, (info, handler, permits)) in handlers.iter().enumerate() {
+                let listener_started =
+                    self.start_listener(observers.as_ref(), hooks.as_ref(), event, info);
+                let result = {
+                    // Held until the listener finishes; the semaphore is never closed
+                    let _permit = permits.acquire().await.ok();
+                    catch_async_panic(handler(event), panics.catches()).await
+                };
+                let result = result.map_err(|source| {
+                    ListenerError::from_listener(info.id, event.event_name(), source)
+                });
+                self.observe_listener(
+                    observers.as_ref(),
+                    hooks.as_ref(),
+                    listener_started,
+                    event,
+                    info,
+                    &result,
+                );
+                let halt = halts(policy, panics, &result);
+                results.push((info.id, info.name, result));
+                if halt {
+                    skipped.extend(handlers[index + 1..].iter().map(|(info, _, _)| info.id));
+                    break;
+                }
+            }
+        });
+        0
+    }
+
+    /// Set how synchronous dispatch treats async listeners (requires "async" feature)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{AsyncBridge, AsyncListenersSkipped, Event, EventDispatcher, ListenerError};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Saved;
+    ///
+    /// impl Event for Saved {}
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.on(|_: &Saved| {});
+    /// dispatcher.subscribe_async(|_: &Saved| async { Ok(()) });
+    ///
+    /// // By default sync dispatch leaves async listeners out, and says so
+    /// let result = dispatcher.dispatch(Saved);
+    /// assert_eq!((result.listener_count(), result.async_skipped()), (1, 1));
+    ///
+    /// dispatcher.set_async_bridge(AsyncBridge::Block(runtime.handle().clone()));
+    /// let result = dispatcher.dispatch(Saved);
+    /// assert_eq!((result.listener_count(), result.async_skipped()), (2, 0));
+    ///
+    /// // Or refuse, so async listeners cannot be missed unnoticed
+    /// dispatcher.set_async_bridge(AsyncBridge::Error);
+    /// let result = dispatcher.dispatch(Saved);
+    /// assert!(matches!(result.listener_errors()[0], ListenerError::AsyncUnsupported { .. }));
+    ///
+    /// // Or skip with a warning meta-event
+    /// let warnings = dispatcher.subscribe_channel::<AsyncListenersSkipped>();
+    /// dispatcher.set_meta_events(true);
+    /// dispatcher.set_async_bridge(AsyncBridge::Warn);
+    /// assert_eq!(dispatcher.dispatch(Saved).async_skipped(), 1);
+    /// assert_eq!(warnings.try_recv().unwrap().skipped, 1);
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn set_async_bridge(&self, bridge: AsyncBridge) {
+        *self.async_bridge.write().unwrap() = bridge;
+    }
+
+    /// Get how synchronous dispatch treats async listeners (requires "async" feature)
+    #[cfg(feature = "async")]
+    pub fn async_bridge(&self) -> AsyncBridge {
+        self.async_bridge.read().unwrap().clone()
+    }
+
+    /// Dispatch an event on the rayon thread pool (requires "rayon" feature)
+    ///
+    /// Listeners sharing a priority run in parallel; higher priorities
+    /// finish before lower ones start, so priority still orders work that
+    /// depends on earlier listeners. Within a priority, a listener waits
+    /// for those it was ordered after with [`ListenerOptions::after`].
+    /// Results are reported in execution order. Under
+    /// [`ErrorPolicy::StopOnError`], a failure skips the listeners that
+    /// have not started.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Priority};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct ImageUploaded {
+    ///     pixels: Vec<u8>,
+    /// }
+    ///
+    /// impl Event for ImageUploaded {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.subscribe_with_priority(
+    ///     |_: &ImageUploaded| Ok(()),
+    ///     Priority::High,
+    /// );
+    /// for _ in 0..4 {
+    ///     dispatcher.on(|image: &ImageUploaded| {
+    ///         let _brightness: u64 = image.pixels.iter().map(|&p| p as u64).sum();
+    ///     });
+    /// }
+    ///
+    /// let result = dispatcher.dispatch_par(ImageUploaded { pixels: vec![7; 1024] });
+    /// assert_eq!(result.success_count(), 5);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn dispatch_par<T: Event>(&self, event: T) -> DispatchResult {
+        use rayon::prelude::*;
+
+        let catch = self.panic_policy().catches();
+        self.dispatch_in_priority_groups(&event, |group, event| {
+            group
+                .par_iter()
+                .map(|listener| listener.call(event, catch))
+                .collect()
+        })
+    }
+
+    /// Dispatch an event across at most `max_threads` scoped OS threads
+    ///
+    /// Needs neither rayon nor an async runtime. Listeners sharing a
+    /// priority are split into `max_threads` chunks that run in parallel;
+    /// higher priorities finish before lower ones start, and listeners
+    /// ordered with [`ListenerOptions::after`] or
+    /// [`before`](ListenerOptions::before) run once their dependencies
+    /// have finished. Threads are
+    /// spawned per dispatch, so this suits heavy per-event work such as
+    /// batch processing rather than high event rates. A panicking listener
+    /// panics the dispatching thread, as with [`dispatch`](Self::dispatch),
+    /// unless the [`PanicPolicy`] catches it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct FileFound {
+    ///     path: String,
+    /// }
+    ///
+    /// impl Event for FileFound {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// let processed = Arc::new(AtomicUsize::new(0));
+    /// for _ in 0..8 {
+    ///     let processed = processed.clone();
+    ///     dispatcher.on(move |file: &FileFound| {
+    ///         assert!(!file.path.is_empty());
+    ///         processed.fetch_add(1, Ordering::SeqCst);
+    ///     });
+    /// }
+    ///
+    /// let result = dispatcher.dispatch_parallel(FileFound { path: "a.txt".into() }, 4);
+    /// assert_eq!(result.success_count(), 8);
+    /// assert_eq!(processed.load(Ordering::SeqCst), 8);
+    /// ```
+    ///
+    /// Dependencies between listeners of one priority form a graph:
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, ListenerOptions};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct AssetChanged;
+    ///
+    /// impl Event for AssetChanged {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// let done = Arc::new(Mutex::new(Vec::new()));
+    /// let step = |name: &'static str| {
+    ///     let done = done.clone();
+    ///     move |_: &AssetChanged| {
+    ///         done.lock().unwrap().push(name);
+    ///         Ok(())
+    ///     }
+    /// };
+    ///
+    /// // decode -> (resize, thumbnail) -> upload
+    /// let decode = dispatcher.subscribe_with_options(step("decode"), ListenerOptions::new());
+    /// let resize = dispatcher.subscribe_with_options(step("resize"), ListenerOptions::new().after(decode));
+    /// let thumbnail = dispatcher.subscribe_with_options(step("thumbnail"), ListenerOptions::new().after(decode));
+    /// dispatcher.subscribe_with_options(
+    ///     step("upload"),
+    ///     ListenerOptions::new().after(resize).after(thumbnail),
+    /// );
+    ///
+    /// assert!(dispatcher.dispatch_parallel(AssetChanged, 2).all_succeeded());
+    /// let done = done.lock().unwrap();
+    /// assert_eq!(done.first(), Some(&"decode"));
+    /// assert_eq!(done.last(), Some(&"upload"));
+    /// ```
+    pub fn dispatch_parallel<T: Event>(&self, event: T, max_threads: usize) -> DispatchResult {
+        let max_threads = max_threads.max(1);
+        let catch = self.panic_policy().catches();
+
+        self.dispatch_in_priority_groups(&event, |group, event| {
+            if group.len() <= 1 || max_threads == 1 {
+                return group
+                    .iter()
+                    .map(|listener| listener.call(event, catch))
+                    .collect();
+            }
+
+            let chunk_size = group.len().div_ceil(max_threads);
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = group
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            chunk
+                                .iter()
+                                .map(|listener| listener.call(event, catch))
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .flat_map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    /// Dispatch an event, handing each run of equal-priority listeners to
+    /// `run_group` and waiting for it before moving to the next priority
+    fn dispatch_in_priority_groups<F>(&self, event: &dyn Event, run_group: F) -> DispatchResult
+    where
+        F: Fn(
+            &[&ListenerWrapper],
+            &dyn Event,
+        ) -> Vec<Result<Handled, Box<dyn std::error::Error + Send + Sync>>>,
+    {
+        let type_id = event.as_any().type_id();
+        let started = self.clock.now();
+
+        self.update_metrics(event);
+
+        if let Err(reason) = self.check_middleware(event, &DispatchContext::default()) {
+            return self.record_outcome(type_id, event, started, DispatchResult::blocked(reason));
+        }
+
+        let policy = self.error_policy();
+        let panics = self.panic_policy();
+        let seed = self.deterministic();
+        let mut results = Vec::new();
+        let mut skipped = Vec::new();
+        let mut stopped_by = None;
+        let mut rate_limited = 0;
+        let mut spent = Vec::new();
+
+        {
+            let event_listeners =
+                self.eligible_listeners(type_id, event, &DispatchContext::default());
+            results.reserve(event_listeners.len());
+            let mut remaining = &event_listeners[..];
+            while let Some(first) = remaining.first() {
+                let len = remaining
+                    .iter()
+                    .take_while(|listener| listener.priority == first.priority)
+                    .count();
+                let (group, rest) = remaining.split_at(len);
+                remaining = rest;
+
+                let mut runnable = Vec::with_capacity(group.len());
+                for listener in group.iter().map(|listener| &**listener) {
+                    if let Some(limiter) = &listener.rate_limiter {
+                        if !limiter.try_acquire(self.clock.now()) {
+                            skipped.push(self.listener_id(listener.id, type_id));
+                            rate_limited += 1;
+                            continue;
+                        }
+                    }
+                    if !listener.claim_delivery() {
+                        skipped.push(self.listener_id(listener.id, type_id));
+                        continue;
+                    }
+                    runnable.push(listener);
+                }
+
+                let mut halted = false;
+                let mut waves = dependency_waves(runnable).into_iter();
+                for wave in waves.by_ref() {
+                    let outcomes = match seed {
+                        Some(seed) => {
+                            let wave = seeded_order(wave, seed);
+                            let outcomes = wave
+                                .iter()
+                                .map(|listener| listener.call(event, panics.catches()));
+                            wave.iter().copied().zip(outcomes).collect::<Vec<_>>()
+                        }
+                        None => {
+                            let outcomes = run_group(&wave, event);
+                            wave.into_iter().zip(outcomes).collect()
+                        }
+                    };
+                    for (listener, result) in outcomes {
+                        let id = self.listener_id(listener.id, type_id);
+                        if listener.is_spent() {
+                            spent.push(id);
+                        }
+                        if matches!(result, Ok(Handled::Stop)) && stopped_by.is_none() {
+                            stopped_by = Some(id);
+                        }
+                        let result = result.map(drop).map_err(|source| {
+                            ListenerError::from_listener(id, event.event_name(), source)
+                        });
+                        halted |= halts(policy, panics, &result);
+                        results.push((id, listener.name, result));
+                    }
+                    if stopped_by.is_some() || halted {
+                        break;
+                    }
+                }
+
+                if stopped_by.is_some() || halted {
+                    skipped.extend(
+                        waves
+                            .flatten()
+                            .chain(remaining.iter().map(|listener| &**listener))
+                            .map(|listener| self.listener_id(listener.id, type_id)),
+                    );
+                    break;
+                }
+            }
+        }
+        self.remove_spent(spent);
+
+        if rate_limited > 0 && self.metrics_enabled() {
+            if let Some(meta) = self.metrics.write().unwrap().get_mut(&type_id) {
+                meta.add_rate_limited(rate_limited);
+            }
+        }
+
+        self.record_outcome(
+            type_id,
+            event,
+            started,
+            DispatchResult::new(results)
+                .with_skipped(skipped)
+                .with_stopped_by(stopped_by),
+        )
+    }
+
+    /// Dispatch an event asynchronously (requires "async" feature)
+    ///
+    /// Runs sync and async listeners together in priority order, sync
+    /// listeners first among equals, and reports them all in one result.
+    /// Sync listeners run on the calling task. Use
+    /// [`dispatch_async_timeout`](Self::dispatch_async_timeout) to keep a
+    /// slow listener from holding up the dispatch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Priority};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct OrderPlaced;
+    ///
+    /// impl Event for OrderPlaced {}
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let dispatcher = EventDispatcher::new();
+    /// let steps = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let log = steps.clone();
+    /// dispatcher.on_with_priority(move |_: &OrderPlaced| log.lock().unwrap().push("validate"), Priority::High);
+    /// let log = steps.clone();
+    /// dispatcher.subscribe_async_with_priority(
+    ///     move |_: &OrderPlaced| {
+    ///         let log = log.clone();
+    ///         async move {
+    ///             log.lock().unwrap().push("charge");
+    ///             Ok(())
+    ///         }
+    ///     },
+    ///     Priority::Normal,
+    /// );
+    /// let log = steps.clone();
+    /// dispatcher.on_with_priority(move |_: &OrderPlaced| log.lock().unwrap().push("audit"), Priority::Low);
+    ///
+    /// let result = dispatcher.dispatch_async(OrderPlaced).await;
+    /// assert_eq!(result.success_count(), 3);
+    /// assert_eq!(*steps.lock().unwrap(), ["validate", "charge", "audit"]);
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn dispatch_async<T: Event>(&self, event: T) -> DispatchResult {
+        self.dispatch_event_async(&event).await
+    }
+
+    /// Dispatch an event through an [`EventRef`] asynchronously (requires "async" feature)
+    #[cfg(feature = "async")]
+    pub async fn dispatch_async_borrowed<E: EventRef>(&self, event: E) -> DispatchResult {
+        self.dispatch_event_async(event.event()).await
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn dispatch_event_async<T: Event>(&self, event: &T) -> DispatchResult {
+        self.dispatch_event_async_within(event, None).await
+    }
+
+    /// Dispatch an event asynchronously with an overall timeout (requires "async" feature)
+    ///
+    /// Resolves once every listener has finished or `timeout` has elapsed,
+    /// whichever comes first. The listener running at the deadline is
+    /// cancelled; it and every listener that never started are recorded as
+    /// [`ListenerError::Timeout`] failures and reported by
+    /// [`DispatchResult::timed_out`]. Must be called within a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Shutdown;
+    ///
+    /// impl Event for Shutdown {}
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.subscribe_async(|_: &Shutdown| async { Ok(()) });
+    /// let stuck = dispatcher.subscribe_async(|_: &Shutdown| async {
+    ///     std::future::pending::<()>().await;
+    ///     Ok(())
+    /// });
+    ///
+    /// let result = dispatcher
+    ///     .dispatch_async_timeout(Shutdown, Duration::from_millis(10))
+    ///     .await;
+    /// assert_eq!(result.success_count(), 1);
+    /// assert_eq!(result.timed_out(), &[stuck]);
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn dispatch_async_timeout<T: Event>(
+        &self,
+        event: T,
+        timeout: Duration,
+    ) -> DispatchResult {
+        self.dispatch_event_async_within(&event, Some(timeout))
+            .await
+    }
+
+    #[cfg(feature = "async")]
+    async fn dispatch_event_async_within<T: Event>(
+        &self,
+        event: &T,
+        timeout: Option<Duration>,
+    ) -> DispatchResult {
+        let deadline = timeout.map(|timeout| tokio::time::Instant::now() + timeout);
+
+        let type_id = TypeId::of::<T>();
+        let started = self.clock.now();
+
+        // Update metrics
+        self.update_metrics(event);
+
+        // Check middleware
+        if let Err(reason) = self.check_middleware(event, &DispatchContext::default()) {
+            return self.record_outcome(type_id, event, started, DispatchResult::blocked(reason));
+        }
+
+        let steps = self.async_steps(type_id, event);
+
+        // Now execute every listener without holding any locks across awaits
+        let mut results = Vec::with_capacity(steps.len());
+        let mut timed_out = Vec::new();
+        let mut skipped = Vec::new();
+        let mut stopped_by = None;
+        let mut run = SyncRun::default();
+        let policy = self.error_policy();
+        let panics = self.panic_policy();
+        let observers = self.observers();
+        let hooks = self.listener_hooks();
+
+        for (index, (info, step)) in steps.iter().enumerate() {
+            let (listener_started, outcome) = match step {
+                AsyncStep::Sync => {
+                    if let (Some(deadline), Some(timeout)) = (deadline, timeout) {
+                        if tokio::time::Instant::now() >= deadline {
+                            self.time_out_steps(event, &steps[index..], timeout, &mut results);
+                            timed_out.extend(steps[index..].iter().map(|(info, _)| info.id));
+                            break;
+                        }
+                    }
+                    match self.call_sync_step(
+                        event,
+                        info,
+                        &mut run,
+                        panics.catches(),
+                        observers.as_ref(),
+                        hooks.as_ref(),
+                    ) {
+                        Some(called) => called,
+                        None => {
+                            skipped.push(info.id);
+                            continue;
+                        }
+                    }
+                }
+                AsyncStep::Async(handler, permits) => {
+                    let listener_started =
+                        self.start_listener(observers.as_ref(), hooks.as_ref(), event, info);
+                    let future = async {
+                        // Held until the listener finishes; the semaphore is never closed
+                        let _permit = permits.acquire().await.ok();
+                        catch_async_panic(handler(event), panics.catches()).await
+                    };
+                    let result = match (deadline, timeout) {
+                        (Some(deadline), Some(timeout)) => {
+                            match tokio::time::timeout_at(deadline, future).await {
+                                Ok(result) => result,
+                                Err(_) => {
+                                    self.time_out_steps(
+                                        event,
+                                        &steps[index..],
+                                        timeout,
+                                        &mut results,
+                                    );
+                                    timed_out
+                                        .extend(steps[index..].iter().map(|(info, _)| info.id));
+                                    break;
+                                }
+                            }
+                        }
+                        _ => future.await,
+                    };
+                    (listener_started, result.map(|()| Handled::Continue))
+                }
+            };
+
+            let stop = matches!(outcome, Ok(Handled::Stop));
+            let result = self.finish_step(
+                event,
+                info,
+                listener_started,
+                outcome,
+                observers.as_ref(),
+                hooks.as_ref(),
+            );
+            let halt = halts(policy, panics, &result);
+            results.push((info.id, info.name, result));
+
+            if stop {
+                stopped_by = Some(info.id);
+            }
+            if stop || halt {
+                skipped.extend(steps[index + 1..].iter().map(|(info, _)| info.id));
+                break;
+            }
+        }
+        skipped.retain(|id| !run.gone.contains(id));
+        self.finish_sync_run(type_id, run);
+
+        self.record_outcome(
+            type_id,
+            event,
+            started,
+            DispatchResult::new(results)
+                .with_skipped(skipped)
+                .with_stopped_by(stopped_by)
+                .with_timed_out(timed_out),
+        )
+    }
+
+    /// Dispatch an event asynchronously, running up to `limit` async
+    /// listeners at once (requires "async" feature)
+    ///
+    /// Async listeners sharing a priority run concurrently, at most
+    /// `limit` in flight, and higher priorities finish before lower ones
+    /// start, so an event with hundreds of listeners cannot flood the
+    /// services they call. Sync listeners run first within their
+    /// priority, on the calling task. A listener's own
+    /// [`max_concurrency`](ListenerOptions::max_concurrency) still
+    /// applies. Values of `limit` below one are treated as one.
+    ///
+    /// With [`ErrorPolicy::StopOnError`], a failure skips the lower
+    /// priorities; listeners already running alongside it finish. A sync
+    /// listener that fails or stops propagation also skips the rest of
+    /// its priority, async listeners included, as none of them has
+    /// started yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Invalidate;
+    ///
+    /// impl Event for Invalidate {}
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let dispatcher = EventDispatcher::new();
+    /// let in_flight = Arc::new(AtomicUsize::new(0));
+    /// let peak = Arc::new(AtomicUsize::new(0));
+    /// for _ in 0..20 {
+    ///     let (in_flight, peak) = (in_flight.clone(), peak.clone());
+    ///     dispatcher.subscribe_async(move |_: &Invalidate| {
+    ///         let (in_flight, peak) = (in_flight.clone(), peak.clone());
+    ///         async move {
+    ///             let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+    ///             peak.fetch_max(now, Ordering::SeqCst);
+    ///             tokio::time::sleep(Duration::from_millis(5)).await;
+    ///             in_flight.fetch_sub(1, Ordering::SeqCst);
+    ///             Ok(())
+    ///         }
+    ///     });
+    /// }
+    ///
+    /// let result = dispatcher.dispatch_async_with_limit(Invalidate, 4).await;
+    /// assert_eq!(result.success_count(), 20);
+    /// assert_eq!(peak.load(Ordering::SeqCst), 4);
+    /// # });
+    /// ```
+    ///
+    /// A failing sync listener in a mixed priority group:
+    ///
+    /// ```rust
+    /// use mod_events::{ErrorPolicy, Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Flush;
+    ///
+    /// impl Event for Flush {}
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.set_error_policy(ErrorPolicy::StopOnError);
+    /// dispatcher.subscribe(|_: &Flush| Err("disk full".into()));
+    /// let audit = dispatcher.subscribe(|_: &Flush| Ok(()));
+    /// let upload = dispatcher.subscribe_async(|_: &Flush| async { Ok(()) });
+    ///
+    /// let result = dispatcher.dispatch_async_with_limit(Flush, 4).await;
+    /// assert_eq!(result.error_count(), 1);
+    /// assert_eq!(result.success_count(), 0);
+    /// assert_eq!(result.skipped(), [audit.id(), upload.id()]);
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn dispatch_async_with_limit<T: Event>(
+        &self,
+        event: T,
+        limit: usize,
+    ) -> DispatchResult {
+        let limit = limit.max(1);
+        let event = &event;
+        let type_id = TypeId::of::<T>();
+        let started = self.clock.now();
+
+        self.update_metrics(event);
+
+        if let Err(reason) = self.check_middleware(event, &DispatchContext::default()) {
+            return self.record_outcome(type_id, event, started, DispatchResult::blocked(reason));
+        }
+
+        let steps = self.async_steps(type_id, event);
+        let mut results = Vec::with_capacity(steps.len());
+        let mut skipped = Vec::new();
+        let mut stopped_by = None;
+        let mut run = SyncRun::default();
+        let policy = self.error_policy();
+        let panics = self.panic_policy();
+        let observers = self.observers();
+        let hooks = self.listener_hooks();
+
+        let mut remaining = &steps[..];
+        while let Some((first, _)) = remaining.first() {
+            let len = remaining
+                .iter()
+                .take_while(|(info, _)| info.priority == first.priority)
+                .count();
+            let (group, rest) = remaining.split_at(len);
+            remaining = rest;
+
+            let mut halted = false;
+            let mut concurrent = Vec::new();
+            let mut queued = Vec::new();
+            for (index, (info, step)) in group.iter().enumerate() {
+                let AsyncStep::Async(handler, permits) = step else {
+                    let Some((listener_started, outcome)) = self.call_sync_step(
+                        event,
+                        info,
+                        &mut run,
+                        panics.catches(),
+                        observers.as_ref(),
+                        hooks.as_ref(),
+                    ) else {
+                        skipped.push(info.id);
+                        continue;
+                    };
+                    let stop = matches!(outcome, Ok(Handled::Stop));
+                    let result = self.finish_step(
+                        event,
+                        info,
+                        listener_started,
+                        outcome,
+                        observers.as_ref(),
+                        hooks.as_ref(),
+                    );
+                    halted |= halts(policy, panics, &result);
+                    results.push((info.id, info.name, result));
+                    if stop {
+                        stopped_by = Some(info.id);
+                    }
+                    if stop || halted {
+                        // Queued async listeners have not started yet
+                        skipped.append(&mut queued);
+                        skipped.extend(group[index + 1..].iter().map(|(info, _)| info.id));
+                        break;
+                    }
+                    continue;
+                };
+                queued.push(info.id);
+                let (observers, hooks) = (observers.as_ref(), hooks.as_ref());
+                concurrent.push(async move {
+                    let listener_started = self.start_listener(observers, hooks, event, info);
+                    // Held until the listener finishes; the semaphore is never closed
+                    let _permit = permits.acquire().await.ok();
+                    (
+                        info,
+                        listener_started,
+                        catch_async_panic(handler(event), panics.catches()).await,
+                    )
+                });
+            }
+
+            if stopped_by.is_none() && !halted {
+                for (info, listener_started, outcome) in run_bounded(concurrent, limit).await {
+                    let result = self.finish_step(
+                        event,
+                        info,
+                        listener_started,
+                        outcome.map(|()| Handled::Continue),
+                        observers.as_ref(),
+                        hooks.as_ref(),
+                    );
+                    halted |= halts(policy, panics, &result);
+                    results.push((info.id, info.name, result));
+                }
+            }
+
+            if stopped_by.is_some() || halted {
+                skipped.extend(remaining.iter().map(|(info, _)| info.id));
+                break;
+            }
+        }
+        skipped.retain(|id| !run.gone.contains(id));
+        self.finish_sync_run(type_id, run);
+
+        self.record_outcome(
+            type_id,
+            event,
+            started,
+            DispatchResult::new(results)
+                .with_skipped(skipped)
+                .with_stopped_by(stopped_by),
+        )
+    }
+
+    /// Collect the sync and async listeners of an async dispatch, in the
+    /// order they run
+    #[cfg(feature = "async")]
+    fn async_steps(&self, type_id: TypeId, event: &dyn Event) -> Vec<(ListenerInfo, AsyncStep)> {
+        let mut steps: Vec<(ListenerInfo, AsyncStep)> = self
+            .eligible_listeners(type_id, event, &DispatchContext::default())
+            .into_iter()
+            .map(|listener| {
+                let info = ListenerInfo {
+                    id: self.listener_id(listener.id, type_id),
+                    name: listener.name,
+                    priority: listener.priority,
+                };
+                (info, AsyncStep::Sync)
+            })
+            .collect();
+
+        // Collect cloned handlers without holding the lock
+        let mut handlers: Vec<(ListenerInfo, AsyncStep)> = {
+            let async_listeners = self.async_listeners.read().unwrap();
+            if let Some(event_listeners) = async_listeners.get(&type_id) {
+                event_listeners
+                    .iter()
+                    .map(|listener| {
+                        let info = ListenerInfo {
+                            id: self.listener_id(listener.id, type_id),
+                            name: listener.name,
+                            priority: listener.priority,
+                        };
+                        let step =
+                            AsyncStep::Async(listener.handler.clone(), listener.permits.clone());
+                        (info, step)
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        }; // Lock is dropped here
+
+        if !handlers.is_empty() && self.delivery_of(type_id) == Delivery::Unicast {
+            if steps.is_empty() {
+                let mut balance = self.balance.lock().unwrap();
+                let turn = balance
+                    .turns
+                    .entry((type_id, UNICAST_ASYNC_POOL))
+                    .or_default();
+                let chosen = *turn % handlers.len();
+                *turn = chosen + 1;
+                handlers = vec![handlers.swap_remove(chosen)];
+            } else {
+                // A sync listener was chosen for the event
+                handlers.clear();
+            }
+        }
+
+        // Interleave by priority; sync listeners go first among equals
+        for handler in handlers {
+            let position = steps.partition_point(|(info, _)| info.priority >= handler.0.priority);
+            steps.insert(position, handler);
+        }
+        steps
+    }
+
+    /// Call a sync listener of an async dispatch
+    ///
+    /// Returns `None` if the listener was passed over: its first-match
+    /// group already handled the event, it is rate limited or out of
+    /// deliveries, or it was unsubscribed meanwhile (recorded in
+    /// `run.gone`). The listener table is only locked while looking the
+    /// listener up.
+    #[cfg(feature = "async")]
+    fn call_sync_step(
+        &self,
+        event: &dyn Event,
+        info: &ListenerInfo,
+        run: &mut SyncRun,
+        catch: bool,
+        observers: Option<&Arc<Vec<Arc<dyn DispatchObserver>>>>,
+        hooks: Option<&ListenerHooks>,
+    ) -> Option<SyncCall> {
+        let listener = self
+            .listeners
+            .read()
+            .unwrap()
+            .get(&info.id.type_id)
+            .and_then(|event_listeners| {
+                event_listeners
+                    .iter()
+                    .find(|listener| listener.id == info.id.id)
+                    .cloned()
+            });
+        let Some(listener) = listener else {
+            run.gone.push(info.id);
+            return None;
+        };
+        if listener
+            .group
+            .is_some_and(|group| run.handled_groups.contains(&group))
+        {
+            return None;
+        }
+        if let Some(limiter) = &listener.rate_limiter {
+            if !limiter.try_acquire(self.clock.now()) {
+                run.rate_limited += 1;
+                return None;
+            }
+        }
+        if !listener.claim_delivery() {
+            return None;
+        }
+        let listener_started = self.start_listener(observers, hooks, event, info);
+        let result = listener.call(event, catch);
+        if listener.is_spent() {
+            run.spent.push(info.id);
+        }
+        if let (Some(group), Ok(Handled::Continue | Handled::Stop)) = (listener.group, &result) {
+            run.handled_groups.push(group);
+        }
+        Some((listener_started, result))
+    }
+
+    /// Turn a listener's outcome into its recorded result, reporting it
+    /// to observers and hooks
+    #[cfg(feature = "async")]
+    fn finish_step(
+        &self,
+        event: &dyn Event,
+        info: &ListenerInfo,
+        listener_started: Option<Instant>,
+        outcome: Result<Handled, Box<dyn std::error::Error + Send + Sync>>,
+        observers: Option<&Arc<Vec<Arc<dyn DispatchObserver>>>>,
+        hooks: Option<&ListenerHooks>,
+    ) -> Result<(), ListenerError> {
+        let result = outcome
+            .map(drop)
+            .map_err(|source| ListenerError::from_listener(info.id, event.event_name(), source));
+        self.observe_listener(observers, hooks, listener_started, event, info, &result);
+        result
+    }
+
+    /// Remove spent listeners and record rate-limited calls once an async
+    /// dispatch is over
+    #[cfg(feature = "async")]
+    fn finish_sync_run(&self, type_id: TypeId, run: SyncRun) {
+        self.remove_spent(run.spent);
+        if run.rate_limited > 0 && self.metrics_enabled() {
+            if let Some(meta) = self.metrics.write().unwrap().get_mut(&type_id) {
+                meta.add_rate_limited(run.rate_limited);
+            }
+        }
+    }
+
+    /// Record every listener of `steps` as timed out
+    #[cfg(feature = "async")]
+    fn time_out_steps(
+        &self,
+        event: &dyn Event,
+        steps: &[(ListenerInfo, AsyncStep)],
+        timeout: Duration,
+        results: &mut Vec<(ListenerId, &'static str, Result<(), ListenerError>)>,
+    ) {
+        for (info, _) in steps {
+            results.push((
+                info.id,
+                info.name,
+                Err(ListenerError::Timeout {
+                    listener: info.id,
+                    event: event.event_name(),
+                    timeout,
+                }),
+            ));
+        }
+    }
+
+    /// Fire and forget - dispatch without waiting for results
+    ///
+    /// This is the most efficient way to dispatch events when you don't
+    /// need to check the results.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{EventDispatcher, Event};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct MyEvent {
+    ///     message: String,
+    /// }
+    ///
+    /// impl Event for MyEvent {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.emit(MyEvent {
+    ///     message: "Fire and forget".to_string(),
+    /// });
+    /// ```
+    pub fn emit<T: Event>(&self, event: T) {
+        let _ = self.dispatch(event);
+    }
+
+    /// Dispatch an event on the dispatcher's worker pool
+    ///
+    /// Returns immediately; sync listeners run on a background thread.
+    /// The returned [`BackgroundDispatch`] yields the [`DispatchResult`]
+    /// once every listener has run. The pool starts on first use with one
+    /// thread per available CPU; see
+    /// [`set_background_threads`](Self::set_background_threads).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct ReportRequested {
+    ///     rows: usize,
+    /// }
+    ///
+    /// impl Event for ReportRequested {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// dispatcher.on(|report: &ReportRequested| {
+    ///     let _total: usize = (0..report.rows).sum();
+    /// });
+    ///
+    /// let handle = dispatcher.dispatch_background(ReportRequested { rows: 10_000 });
+    /// // ... the caller keeps working ...
+    /// let result = handle.wait().unwrap();
+    /// assert!(result.all_succeeded());
+    /// ```
+    pub fn dispatch_background<T: Event>(self: &Arc<Self>, event: T) -> BackgroundDispatch {
+        self.dispatch_background_with_priority(event, Priority::Normal)
+    }
+
+    /// Dispatch an event on the worker pool with a queue priority
+    ///
+    /// The priority only orders the queue under
+    /// [`QueueFairness::Priority`]; other policies ignore it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Priority, QueueFairness};
+    /// use std::sync::{Arc, Barrier, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Hold;
+    ///
+    /// impl Event for Hold {}
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Job(&'static str);
+    ///
+    /// impl Event for Job {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// dispatcher.set_background_threads(1);
+    /// dispatcher.set_queue_fairness(QueueFairness::Priority);
+    /// // A job waiting 50ms climbs one priority level
+    /// dispatcher.set_queue_aging(Some(Duration::from_millis(50)));
+    ///
+    /// let order = Arc::new(Mutex::new(Vec::new()));
+    /// let seen = order.clone();
+    /// dispatcher.on(move |job: &Job| seen.lock().unwrap().push(job.0));
+    ///
+    /// let release = Arc::new(Barrier::new(2));
+    /// let held = release.clone();
+    /// dispatcher.on(move |_: &Hold| {
+    ///     held.wait();
+    /// });
+    /// dispatcher.dispatch_background(Hold);
+    ///
+    /// let batch = dispatcher.dispatch_background_with_priority(Job("batch"), Priority::Low);
+    /// std::thread::sleep(Duration::from_millis(120));
+    /// let urgent = dispatcher.dispatch_background_with_priority(Job("urgent"), Priority::High);
+    /// let normal = dispatcher.dispatch_background_with_priority(Job("normal"), Priority::Normal);
+    /// release.wait();
+    /// for handle in [batch, urgent, normal] {
+    ///     handle.wait().unwrap();
+    /// }
+    ///
+    /// // The aged batch job caught up with the urgent one and was queued first
+    /// assert_eq!(*order.lock().unwrap(), ["batch", "urgent", "normal"]);
+    /// ```
+    pub fn dispatch_background_with_priority<T: Event>(
+        self: &Arc<Self>,
+        event: T,
+        priority: Priority,
+    ) -> BackgroundDispatch {
+        self.dispatch_background_with(Owned(event), priority)
+    }
+
+    /// Dispatch an event through an [`EventRef`] on the worker pool
+    ///
+    /// Queues the handle itself, so `'static` and shared events are never
+    /// copied.
+    pub fn dispatch_background_borrowed<E: EventRef>(
+        self: &Arc<Self>,
+        event: E,
+    ) -> BackgroundDispatch {
+        self.dispatch_background_with(event, Priority::Normal)
+    }
+
+    fn dispatch_background_with<E: EventRef>(
+        self: &Arc<Self>,
+        event: E,
+        priority: Priority,
+    ) -> BackgroundDispatch {
+        type T<E> = <E as EventRef>::Event;
+
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        if self.deterministic().is_some() {
+            let _ = sender.send(self.dispatch_event(event.event()));
+            return BackgroundDispatch::new(receiver);
+        }
+        let queue = self.type_queue(TypeId::of::<T<E>>());
+        let event_name = std::any::type_name::<T<E>>();
+        match queue.reserve() {
+            Ok(None) => {}
+            Ok(Some(capacity)) => self.emit_meta(|| QueueSaturated {
+                event: event_name,
+                capacity,
+            }),
+            Err(policy) => {
+                self.emit_meta(|| EventDropped {
+                    event: event_name,
+                    dropped: queue.dropped(),
+                });
+                if policy == BackpressurePolicy::Fail {
+                    let _ = sender.send(DispatchResult::blocked(BlockReason {
+                        middleware: "background queue",
+                        reason: format!("queue for {} is full", display_type_name(event_name)),
+                    }));
+                }
+                return BackgroundDispatch::new(receiver);
+            }
+        }
+        let dispatcher = self.clone();
+        let queued_at = self.clock.now();
+        self.workers.lock().unwrap().execute(
+            TypeId::of::<T<E>>(),
+            priority,
+            Box::new(move || {
+                queue.release();
+                let waited = dispatcher.clock.now().saturating_duration_since(queued_at);
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    dispatcher.dispatch_event(event.event())
+                }));
+                if dispatcher.metrics_enabled() {
+                    if let Some(meta) = dispatcher
+                        .metrics
+                        .write()
+                        .unwrap()
+                        .get_mut(&TypeId::of::<T<E>>())
+                    {
+                        meta.add_queue_wait(waited);
+                    }
+                }
+                if let Ok(result) = result {
+                    let _ = sender.send(result);
+                }
+            }),
+        );
+        BackgroundDispatch::new(receiver)
+    }
+
+    /// Set how many threads run [`dispatch_background`](Self::dispatch_background) work
+    ///
+    /// Values below one are treated as one. Threads of the previous pool
+    /// finish the dispatches already queued on them and then exit.
+    pub fn set_background_threads(&self, threads: usize) {
+        self.workers.lock().unwrap().resize(threads);
+    }
+
+    /// Get how many threads run background dispatches
+    pub fn background_threads(&self) -> usize {
+        self.workers.lock().unwrap().threads()
+    }
+
+    /// Set how queued background dispatches are ordered
+    ///
+    /// See [`QueueFairness`]. Applies to dispatches queued from now on.
+    pub fn set_queue_fairness(&self, fairness: QueueFairness) {
+        self.workers.lock().unwrap().set_fairness(fairness);
+    }
+
+    /// Get how queued background dispatches are ordered
+    pub fn queue_fairness(&self) -> QueueFairness {
+        self.workers.lock().unwrap().fairness()
+    }
+
+    /// Set how fast queued dispatches gain priority while they wait
+    ///
+    /// Under [`QueueFairness::Priority`], each full `rate` a dispatch has
+    /// been queued raises it by one [`Priority`] level, so low-priority
+    /// work is eventually delivered under a constant stream of
+    /// high-priority work. `None` (the default) disables aging. Waiting
+    /// time is measured on the dispatcher's [`Clock`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Priority, QueueFairness, TestClock};
+    /// use std::sync::{Arc, Barrier, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Hold;
+    ///
+    /// impl Event for Hold {}
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Report;
+    ///
+    /// impl Event for Report {}
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Alert;
+    ///
+    /// impl Event for Alert {}
+    ///
+    /// let clock = TestClock::new();
+    /// let dispatcher = Arc::new(EventDispatcher::with_clock(clock.clone()));
+    /// dispatcher.set_background_threads(1);
+    /// dispatcher.set_queue_fairness(QueueFairness::Priority);
+    /// dispatcher.set_queue_aging(Some(Duration::from_secs(1)));
+    ///
+    /// let order = Arc::new(Mutex::new(Vec::new()));
+    /// let (reports, alerts) = (order.clone(), order.clone());
+    /// dispatcher.on(move |_: &Report| reports.lock().unwrap().push("report"));
+    /// dispatcher.on(move |_: &Alert| alerts.lock().unwrap().push("alert"));
+    ///
+    /// // Keep the only worker busy while the queue fills up
+    /// let release = Arc::new(Barrier::new(2));
+    /// let held = release.clone();
+    /// dispatcher.on(move |_: &Hold| {
+    ///     held.wait();
+    /// });
+    /// dispatcher.dispatch_background_with_priority(Hold, Priority::Critical);
+    ///
+    /// // Three seconds of waiting lift the report from low above high
+    /// let report = dispatcher.dispatch_background_with_priority(Report, Priority::Low);
+    /// clock.advance(Duration::from_secs(3));
+    /// let alert = dispatcher.dispatch_background_with_priority(Alert, Priority::High);
+    ///
+    /// release.wait();
+    /// report.wait().unwrap();
+    /// alert.wait().unwrap();
+    /// assert_eq!(*order.lock().unwrap(), ["report", "alert"]);
+    /// ```
+    pub fn set_queue_aging(&self, rate: Option<Duration>) {
+        self.workers.lock().unwrap().set_aging(rate);
+    }
+
+    /// Get the queue aging rate
+    pub fn queue_aging(&self) -> Option<Duration> {
+        self.workers.lock().unwrap().aging()
+    }
+
+    /// Raise queued dispatches of `T` by a custom amount while they wait
+    ///
+    /// Under [`QueueFairness::Priority`], `boost` maps how long a dispatch
+    /// has waited to priority points added to its queue priority; adjacent
+    /// [`Priority`] levels are 25 points apart. Replaces
+    /// [aging](Self::set_queue_aging) for `T`. Time spent queued is
+    /// reported in [`EventMetadata::max_queue_wait`] and related fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Priority, QueueFairness};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct BatchExport;
+    ///
+    /// impl Event for BatchExport {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// dispatcher.set_queue_fairness(QueueFairness::Priority);
+    /// // Batch exports may wait, but after five seconds they outrank everything
+    /// dispatcher.set_queue_boost::<BatchExport>(|waited| {
+    ///     if waited >= Duration::from_secs(5) { 200 } else { 0 }
+    /// });
+    /// dispatcher.on(|_: &BatchExport| {});
+    ///
+    /// dispatcher
+    ///     .dispatch_background_with_priority(BatchExport, Priority::Lowest)
+    ///     .wait()
+    ///     .unwrap();
+    /// let meta = dispatcher.metrics().into_values().next().unwrap();
+    /// assert_eq!(meta.queued_count, 1);
+    /// assert!(meta.max_queue_wait < Duration::from_secs(5));
+    /// ```
+    pub fn set_queue_boost<T: Event>(
+        &self,
+        boost: impl Fn(Duration) -> u32 + Send + Sync + 'static,
+    ) {
+        self.workers
+            .lock()
+            .unwrap()
+            .set_boost(TypeId::of::<T>(), Arc::new(boost));
+    }
+
+    /// Emit events about the dispatcher's own activity
+    ///
+    /// When enabled, the dispatcher dispatches [`ListenerAdded`],
+    /// [`EventBlocked`], [`EventDropped`] and [`QueueSaturated`] to itself,
+    /// so alerting on bus health can subscribe to them like any other
+    /// event. Meta-events are dispatched synchronously on the thread that
+    /// caused them. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventBlocked, EventDispatcher, ListenerAdded};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Transfer {
+    ///     amount: u64,
+    /// }
+    ///
+    /// impl Event for Transfer {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.set_meta_events(true);
+    ///
+    /// let alerts = Arc::new(Mutex::new(Vec::new()));
+    /// let blocked = alerts.clone();
+    /// dispatcher.on(move |event: &EventBlocked| {
+    ///     blocked.lock().unwrap().push(event.reason.to_string());
+    /// });
+    /// let added = alerts.clone();
+    /// dispatcher.on(move |event: &ListenerAdded| {
+    ///     added.lock().unwrap().push(format!("listener added for {}", event.event));
+    /// });
+    ///
+    /// dispatcher.add_middleware(|event: &dyn Event| {
+    ///     event.downcast_ref::<Transfer>().is_none_or(|transfer| transfer.amount < 10_000)
+    /// });
+    /// dispatcher.on(|_: &Transfer| {});
+    /// dispatcher.emit(Transfer { amount: 50_000 });
+    ///
+    /// // The `ListenerAdded` listener also saw its own subscription
+    /// let alerts = alerts.lock().unwrap();
+    /// assert_eq!(alerts.len(), 3);
+    /// assert!(alerts[1].ends_with("Transfer"));
+    /// assert!(alerts[2].starts_with("blocked by"));
+    /// ```
+    pub fn set_meta_events(&self, enabled: bool) {
+        self.meta_events.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check whether meta-events are emitted
+    pub fn meta_events(&self) -> bool {
+        self.meta_events.load(Ordering::Relaxed)
+    }
+
+    fn emit_meta<E: Event>(&self, event: impl FnOnce() -> E) {
+        if self.meta_events() {
+            self.dispatch_event(&event());
+        }
+    }
+
+    fn listener_added<T: Event>(&self, id: usize, name: &'static str) {
+        self.emit_meta(|| ListenerAdded {
+            listener: self.listener_id(id, TypeId::of::<T>()),
+            event: std::any::type_name::<T>(),
+            name,
+        });
+    }
+
+    /// Cap how many background dispatches of `T` may be queued at once
+    ///
+    /// Once `capacity` dispatches of `T` are waiting for a worker, `policy`
+    /// decides what happens to the next one, without affecting other
+    /// event types: [`BackpressurePolicy::DropNewest`] discards it and its
+    /// handle resolves to `None`, [`BackpressurePolicy::Fail`] discards it
+    /// and resolves the handle to a blocked result, and
+    /// [`BackpressurePolicy::Block`] makes the caller wait for room.
+    /// Capacities below one are treated as one. `None` removes the cap.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{BackpressurePolicy, Event, EventDispatcher};
+    /// use std::sync::{Arc, Barrier};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Hold;
+    ///
+    /// impl Event for Hold {}
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Telemetry;
+    ///
+    /// impl Event for Telemetry {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// dispatcher.set_background_threads(1);
+    /// dispatcher.set_queue_capacity::<Telemetry>(Some((2, BackpressurePolicy::DropNewest)));
+    /// dispatcher.on(|_: &Telemetry| {});
+    ///
+    /// // Keep the only worker busy while the queue fills up
+    /// let release = Arc::new(Barrier::new(2));
+    /// let held = release.clone();
+    /// dispatcher.on(move |_: &Hold| {
+    ///     held.wait();
+    /// });
+    /// let hold = dispatcher.dispatch_background(Hold);
+    ///
+    /// let handles: Vec<_> = (0..5).map(|_| dispatcher.dispatch_background(Telemetry)).collect();
+    /// assert_eq!(dispatcher.queue_depth::<Telemetry>(), 2);
+    /// assert_eq!(dispatcher.queue_dropped::<Telemetry>(), 3);
+    ///
+    /// release.wait();
+    /// hold.wait().unwrap();
+    /// let delivered = handles.into_iter().filter_map(|handle| handle.wait()).count();
+    /// assert_eq!(delivered, 2);
+    /// ```
+    pub fn set_queue_capacity<T: Event>(&self, limit: Option<(usize, BackpressurePolicy)>) {
+        self.type_queue(TypeId::of::<T>()).set_limit(limit);
+    }
+
+    /// Get how many background dispatches of `T` are waiting for a worker
+    pub fn queue_depth<T: Event>(&self) -> usize {
+        self.queues
+            .read()
+            .unwrap()
+            .get(&TypeId::of::<T>())
+            .map_or(0, |queue| queue.depth())
+    }
+
+    /// Get how many background dispatches of `T` a full queue has discarded
+    ///
+    /// See [`set_queue_capacity`](Self::set_queue_capacity).
+    pub fn queue_dropped<T: Event>(&self) -> usize {
+        self.queues
+            .read()
+            .unwrap()
+            .get(&TypeId::of::<T>())
+            .map_or(0, |queue| queue.dropped())
+    }
+
+    fn type_queue(&self, type_id: TypeId) -> Arc<TypeQueue> {
+        if let Some(queue) = self.queues.read().unwrap().get(&type_id) {
+            return queue.clone();
+        }
+        self.queues
+            .write()
+            .unwrap()
+            .entry(type_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Set how many consecutive dispatches of `T` run per round-robin turn
+    ///
+    /// Weights default to 1; values below one are treated as one. Only
+    /// used with [`QueueFairness::RoundRobin`].
+    pub fn set_queue_weight<T: Event>(&self, weight: u32) {
+        self.workers
+            .lock()
+            .unwrap()
+            .set_weight(TypeId::of::<T>(), weight);
+    }
+
+    /// Set whether `T` is broadcast to every listener or consumed by one
+    ///
+    /// Fails if switching to [`Delivery::Unicast`] while a listener of `T`
+    /// belongs to a first-match or load-balanced group; once `T` is
+    /// unicast, subscribing such listeners fails the same way.
+    pub fn set_delivery<T: Event + 'static>(
+        &self,
+        delivery: Delivery,
+    ) -> Result<(), SubscribeError> {
+        let type_id = TypeId::of::<T>();
+        if delivery == Delivery::Unicast {
+            let grouped = self
+                .listeners
+                .read()
+                .unwrap()
+                .get(&type_id)
+                .is_some_and(|listeners| {
+                    listeners
+                        .iter()
+                        .any(|listener| listener.group.is_some() || listener.balanced.is_some())
+                });
+            if grouped {
+                return Err(SubscribeError::Unicast(std::any::type_name::<T>()));
+            }
+        }
+        self.delivery.write().unwrap().insert(type_id, delivery);
+        Ok(())
+    }
+
+    /// Get the delivery mode of `T`
+    pub fn delivery<T: Event + 'static>(&self) -> Delivery {
+        self.delivery_of(TypeId::of::<T>())
+    }
+
+    /// Set how a load-balanced group picks the listener for each event
+    ///
+    /// Applies to the group `group` for every event type. Groups default
+    /// to [`LoadBalance::RoundRobin`].
+    pub fn set_load_balance(&self, group: &'static str, strategy: LoadBalance) {
+        self.balance
+            .lock()
+            .unwrap()
+            .strategies
+            .insert(group, strategy);
+    }
+
+    /// Set what happens when a listener returns an error
+    ///
+    /// Applies to every subsequent dispatch, sync and async.
+    pub fn set_error_policy(&self, policy: ErrorPolicy) {
+        *self.error_policy.write().unwrap() = policy;
+    }
+
+    /// Get the current error policy
+    pub fn error_policy(&self) -> ErrorPolicy {
+        *self.error_policy.read().unwrap()
+    }
+
+    /// Set what happens when a listener panics
+    ///
+    /// By default a panic unwinds through the dispatching call. With
+    /// [`PanicPolicy::Capture`] or [`PanicPolicy::Abort`] the panic is
+    /// caught at the listener and reported as
+    /// [`ListenerError::Panic`]; the dispatcher stays usable either way.
+    /// Applies to every subsequent dispatch, sync and async. Background
+    /// dispatches never unwind into the caller; under
+    /// [`PanicPolicy::Propagate`] a panic there ends the dispatch without
+    /// a result.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, ListenerError, PanicPolicy};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Render;
+    ///
+    /// impl Event for Render {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.set_panic_policy(PanicPolicy::Abort);
+    ///
+    /// let widget = dispatcher.subscribe(|_: &Render| -> Result<(), _> { panic!("bad layout") });
+    /// let never_ran = dispatcher.subscribe(|_: &Render| Ok(()));
+    ///
+    /// let result = dispatcher.dispatch(Render);
+    /// match result.listener_errors()[0] {
+    ///     ListenerError::Panic { listener, message, .. } => {
+    ///         assert_eq!(*listener, widget);
+    ///         assert_eq!(message, "bad layout");
+    ///     }
+    ///     other => panic!("unexpected failure: {other}"),
+    /// }
+    /// assert_eq!(result.skipped(), &[never_ran]);
+    /// ```
+    pub fn set_panic_policy(&self, policy: PanicPolicy) {
+        *self.panic_policy.write().unwrap() = policy;
+    }
+
+    /// Get the current panic policy
+    pub fn panic_policy(&self) -> PanicPolicy {
+        *self.panic_policy.read().unwrap()
+    }
+
+    /// Turn the deterministic ordering mode on with a seed, or off with `None`
+    ///
+    /// Sequential dispatch always runs listeners in a fixed order. In this
+    /// mode the parallel-capable paths do too, so runs can be reproduced
+    /// exactly, for example to chase a race or to keep networked
+    /// simulations in lockstep:
+    ///
+    /// - [`dispatch_parallel`](Self::dispatch_parallel) and `dispatch_par`
+    ///   run every listener on the calling thread. Listeners that may run
+    ///   concurrently are ordered by the seed, so different seeds explore
+    ///   different interleavings and the same seed repeats one; priorities
+    ///   and ordering constraints are still honored.
+    /// - [`dispatch_background`](Self::dispatch_background) dispatches
+    ///   inline and returns a finished handle, bypassing the worker pool
+    ///   and queue limits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Tick;
+    ///
+    /// impl Event for Tick {}
+    ///
+    /// let run = |seed| {
+    ///     let dispatcher = EventDispatcher::new();
+    ///     dispatcher.set_deterministic(Some(seed));
+    ///     let order = Arc::new(Mutex::new(Vec::new()));
+    ///     for player in 0..8 {
+    ///         let order = order.clone();
+    ///         dispatcher.on(move |_: &Tick| order.lock().unwrap().push(player));
+    ///     }
+    ///     dispatcher.dispatch_parallel(Tick, 4);
+    ///     let order = order.lock().unwrap().clone();
+    ///     order
+    /// };
+    ///
+    /// assert_eq!(run(42), run(42));
+    /// ```
+    pub fn set_deterministic(&self, seed: Option<u64>) {
+        *self.deterministic.write().unwrap() = seed;
+    }
+
+    /// Get the seed of the deterministic ordering mode, if it is on
+    pub fn deterministic(&self) -> Option<u64> {
+        *self.deterministic.read().unwrap()
+    }
+
+    /// Set what bounded subscriptions do when a consumer's buffer is full
+    ///
+    /// Applies to every subsequent dispatch.
+    pub fn set_backpressure_policy(&self, policy: BackpressurePolicy) {
+        *self.backpressure.write().unwrap() = policy;
+    }
+
+    /// Get the current backpressure policy
+    pub fn backpressure_policy(&self) -> BackpressurePolicy {
+        *self.backpressure.read().unwrap()
+    }
+
+    /// Get the dispatch totals of a tenant
+    ///
+    /// Counts synchronous dispatches whose context carries the tenant's
+    /// [`TenantId`]. Returns `None` if no such dispatch has been made or
+    /// per-event metrics are [off](Self::set_metrics_enabled).
+    pub fn tenant_metrics(&self, tenant: &str) -> Option<TenantMetrics> {
+        self.tenant_metrics.read().unwrap().get(tenant).copied()
+    }
+
+    fn record_tenant(&self, context: &DispatchContext, result: &DispatchResult) {
+        let Some(tenant) = context.get::<TenantId>() else {
+            return;
+        };
+        if !self.metrics_enabled() {
+            return;
+        }
+        self.tenant_metrics
+            .write()
+            .unwrap()
+            .entry(tenant.clone())
+            .or_default()
+            .record(result);
+    }
+
+    /// Turn per-event metrics on or off
+    ///
+    /// While off, dispatches, errors, rate-limited calls and queue waits
+    /// are not added to [`metrics`](Self::metrics); listener counts are
+    /// still kept, and the dispatcher-wide totals behind
+    /// [`health`](Self::health) are unaffected.
+    /// Metrics are on by default.
+    pub fn set_metrics_enabled(&self, enabled: bool) {
+        self.metrics_enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check whether per-event metrics are recorded
+    pub fn metrics_enabled(&self) -> bool {
+        self.metrics_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Apply every setting present in `config`
+    ///
+    /// Settings take effect for dispatches started afterwards; dispatches
+    /// in progress or waiting in the background queue are not dropped.
+    /// Changing the thread count lets the current workers finish the
+    /// queued dispatches before they exit, and leaves the pool alone if
+    /// the count is unchanged. Latency tracking, once enabled, stays on.
+    /// See [`DispatcherConfig`].
+    pub fn reconfigure(&self, config: DispatcherConfig) {
+        if let Some(policy) = config.error_policy {
+            self.set_error_policy(policy);
+        }
+        if let Some(policy) = config.panic_policy {
+            self.set_panic_policy(policy);
+        }
+        if let Some(policy) = config.backpressure {
+            self.set_backpressure_policy(policy);
+        }
+        if let Some(threads) = config.background_threads {
+            let mut workers = self.workers.lock().unwrap();
+            if workers.threads() != threads.max(1) {
+                workers.resize(threads);
+            }
+        }
+        if let Some(fairness) = config.queue_fairness {
+            self.set_queue_fairness(fairness);
+        }
+        if let Some(rate) = config.queue_aging {
+            self.set_queue_aging(rate);
+        }
+        if let Some(Some(window)) = config.latency_window {
+            self.set_latency_window(window);
+        }
+        if let Some(enabled) = config.metrics {
+            self.set_metrics_enabled(enabled);
+        }
+        if let Some(enabled) = config.meta_events {
+            self.set_meta_events(enabled);
+        }
+        if let Some(seed) = config.deterministic {
+            self.set_deterministic(seed);
+        }
+    }
+
+    /// Get the current settings
+    ///
+    /// Every field of the returned [`DispatcherConfig`] is set, so it can
+    /// be stored and passed back to [`reconfigure`](Self::reconfigure).
+    pub fn config(&self) -> DispatcherConfig {
+        let (background_threads, queue_fairness, queue_aging) = {
+            let workers = self.workers.lock().unwrap();
+            (workers.threads(), workers.fairness(), workers.aging())
+        };
+        DispatcherConfig {
+            error_policy: Some(self.error_policy()),
+            panic_policy: Some(self.panic_policy()),
+            backpressure: Some(self.backpressure_policy()),
+            background_threads: Some(background_threads),
+            queue_fairness: Some(queue_fairness),
+            queue_aging: Some(queue_aging),
+            latency_window: Some(
+                self.latency_window
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .map(LatencyWindow::window),
+            ),
+            metrics: Some(self.metrics_enabled()),
+            meta_events: Some(self.meta_events()),
+            deterministic: Some(self.deterministic()),
+        }
+    }
+
+    /// Add middleware that can block events (requires "middleware" feature)
+    ///
+    /// Middleware functions receive events and return `true` to allow
+    /// processing or `false` to block the event. The feature is on by
+    /// default; building without it removes the middleware check, and its
+    /// lock, from every dispatch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{EventDispatcher, Event};
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.add_middleware(|event: &dyn Event| {
+    ///     println!("Processing event: {}", event.event_name());
+    ///     true // Allow all events
+    /// });
+    /// ```
+    #[cfg(feature = "middleware")]
+    pub fn add_middleware<F>(&self, middleware: F)
+    where
+        F: Fn(&dyn Event) -> bool + Send + Sync + 'static,
+    {
+        let mut middleware_manager = self.middleware.write().unwrap();
+        middleware_manager.add(middleware);
+    }
+
+    /// Add a [`Middleware`] implementation that can block events (requires "middleware" feature)
+    ///
+    /// Unlike [`add_middleware`](Self::add_middleware), the middleware sees
+    /// the [`DispatchContext`] and reports why it blocked an event.
+    #[cfg(feature = "middleware")]
+    pub fn add_middleware_layer<M: Middleware + 'static>(&self, middleware: M) {
+        self.middleware.write().unwrap().add_layer(middleware);
+    }
+
+    /// Add an observer notified after every dispatch
+    ///
+    /// Observers see the event, its [`DispatchResult`] and how long the
+    /// dispatch took, including dispatches blocked by middleware.
+    pub fn add_observer<O: DispatchObserver + 'static>(&self, observer: O) {
+        let mut observers = self.observers.write().unwrap();
+        let mut updated = Vec::clone(&observers);
+        updated.push(Arc::new(observer));
+        *observers = Arc::new(updated);
+    }
+
+    /// Add a hook called around every listener invocation
+    ///
+    /// See [`ListenerHook`]. Returns a handle for
+    /// [`remove_listener_hook`](Self::remove_listener_hook).
+    pub fn add_listener_hook<H: ListenerHook + 'static>(&self, hook: H) -> HookId {
+        let id = HookId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let mut hooks = self.listener_hooks.write().unwrap();
+        let mut updated = Vec::clone(&hooks);
+        updated.push((id, Arc::new(hook) as Arc<dyn ListenerHook>));
+        *hooks = Arc::new(updated);
+        id
+    }
+
+    /// Call `callback` whenever a listener call takes longer than `threshold`
+    ///
+    /// A watchdog for listeners that stall dispatch: the callback gets the
+    /// listener, the event name and the elapsed time once the call returns,
+    /// on the dispatching thread. Timing comes from the dispatcher's
+    /// [`Clock`]. Installed as a [`ListenerHook`], so it covers sequential
+    /// sync and async dispatch and is removed with
+    /// [`remove_listener_hook`](Self::remove_listener_hook).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, TestClock};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Render;
+    ///
+    /// impl Event for Render {}
+    ///
+    /// let clock = TestClock::new();
+    /// let dispatcher = EventDispatcher::with_clock(clock.clone());
+    /// let slow = Arc::new(Mutex::new(Vec::new()));
+    /// let log = slow.clone();
+    /// dispatcher.on_slow_listener(Duration::from_millis(16), move |report| {
+    ///     eprintln!("{} took {:?} handling {}", report.listener.id, report.elapsed, report.event);
+    ///     log.lock().unwrap().push((report.listener.id, report.elapsed));
+    /// });
+    ///
+    /// dispatcher.on(|_: &Render| {});
+    /// let step = clock.clone();
+    /// let sluggish = dispatcher.on(move |_: &Render| step.advance(Duration::from_millis(40)));
+    /// dispatcher.emit(Render);
+    ///
+    /// assert_eq!(*slow.lock().unwrap(), [(sluggish.id(), Duration::from_millis(40))]);
+    /// ```
+    pub fn on_slow_listener<F>(&self, threshold: Duration, callback: F) -> HookId
+    where
+        F: Fn(&SlowListener) + Send + Sync + 'static,
+    {
+        self.add_listener_hook(SlowListenerHook {
+            threshold,
+            callback: Box::new(callback),
+        })
+    }
+
+    /// Remove a listener hook
+    ///
+    /// Returns `true` if the hook was found and removed. Dispatches already
+    /// in progress keep calling it until they finish.
+    pub fn remove_listener_hook(&self, hook: HookId) -> bool {
+        let mut hooks = self.listener_hooks.write().unwrap();
+        let Some(pos) = hooks.iter().position(|(id, _)| *id == hook) else {
+            return false;
+        };
+        let mut updated = Vec::clone(&hooks);
+        updated.remove(pos);
+        *hooks = Arc::new(updated);
+        true
+    }
+
+    /// Remove a listener
+    ///
+    /// Returns `true` if the listener was found and removed, `false` otherwise.
+    /// Unsubscribing does not wait for calls already in flight: dispatches
+    /// in progress may still call the listener after this returns, and it
+    /// is dropped once the last of them finishes. Release resources the
+    /// listener uses from its drop or
+    /// [`on_unsubscribe`](EventListener::on_unsubscribe), not after this
+    /// call.
+    pub fn unsubscribe(&self, listener_id: impl Into<ListenerId>) -> bool {
+        let listener_id = listener_id.into();
+        if listener_id.dispatcher != self.id {
+            return false;
+        }
+
+        // Try sync listeners first
+        {
+            let mut listeners = self.listeners.write().unwrap();
+            if let Some(event_listeners) = listeners.get_mut(&listener_id.type_id) {
+                if let Some(pos) = event_listeners.iter().position(|l| l.id == listener_id.id) {
+                    let removed = event_listeners.remove(pos);
+                    // Run the listener's removal callback outside the lock,
+                    // unless a dispatch in progress still holds it
+                    drop(listeners);
+                    drop(removed);
+                    return true;
+                }
+            }
+        }
+
+        // Try async listeners
+        #[cfg(feature = "async")]
+        {
+            let mut async_listeners = self.async_listeners.write().unwrap();
+            if let Some(event_listeners) = async_listeners.get_mut(&listener_id.type_id) {
+                if let Some(pos) = event_listeners.iter().position(|l| l.id == listener_id.id) {
+                    let removed = event_listeners.remove(pos);
+                    drop(async_listeners);
+                    drop(removed);
+                    return true;
+                }
+            }
+        }
+
+        // Try responders, whichever response type they produce
+        let mut responders = self.responders.write().unwrap();
+        for ((type_id, _), event_responders) in responders.iter_mut() {
+            if *type_id != listener_id.type_id {
+                continue;
+            }
+            if let Some(pos) = event_responders.iter().position(|r| r.id == listener_id.id) {
+                event_responders.remove(pos);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Get the number of listeners for an event type
+    pub fn listener_count<T: Event + 'static>(&self) -> usize {
+        self.listener_count_of(TypeId::of::<T>())
+    }
+
+    fn listener_count_of(&self, type_id: TypeId) -> usize {
+        let sync_count = self
+            .listeners
+            .read()
+            .unwrap()
+            .get(&type_id)
+            .map(|v| v.len())
+            .unwrap_or(0);
+
+        #[cfg(feature = "async")]
+        let async_count = self
+            .async_listeners
+            .read()
+            .unwrap()
+            .get(&type_id)
+            .map(|v| v.len())
+            .unwrap_or(0);
+
+        #[cfg(not(feature = "async"))]
+        let async_count = 0;
+
+        let responder_count: usize = self
+            .responders
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|((event_type, _), _)| *event_type == type_id)
+            .map(|(_, responders)| responders.len())
+            .sum();
+
+        sync_count + async_count + responder_count
+    }
+
+    /// Track dispatch and listener durations over a sliding `window`
+    ///
+    /// Enables [`dispatch_percentile`](Self::dispatch_percentile) and
+    /// [`listener_percentile`](Self::listener_percentile). Samples older
+    /// than `window`, as measured by the dispatcher's clock, are
+    /// discarded; at most 8192 recent samples are kept per event type and
+    /// per listener. Calling it again only changes the window length.
+    /// Like observers, listener durations are only measured in sequential
+    /// sync and async dispatch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, TestClock};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Render;
+    ///
+    /// impl Event for Render {}
+    ///
+    /// let clock = TestClock::new();
+    /// let dispatcher = EventDispatcher::with_clock(clock.clone());
+    /// dispatcher.set_latency_window(Duration::from_secs(60));
+    ///
+    /// let step = clock.clone();
+    /// let listener = dispatcher.on(move |_: &Render| step.advance(Duration::from_millis(4)));
+    /// for _ in 0..10 {
+    ///     dispatcher.emit(Render);
+    /// }
+    ///
+    /// assert_eq!(dispatcher.dispatch_percentile::<Render>(99.0), Some(Duration::from_millis(4)));
+    /// assert_eq!(dispatcher.listener_percentile(listener, 50.0), Some(Duration::from_millis(4)));
+    ///
+    /// // Samples age out of the window
+    /// clock.advance(Duration::from_secs(61));
+    /// assert_eq!(dispatcher.dispatch_percentile::<Render>(99.0), None);
+    /// ```
+    pub fn set_latency_window(&self, window: Duration) {
+        let mut latency_window = self.latency_window.lock().unwrap();
+        match &*latency_window {
+            Some(existing) => existing.set_window(window),
+            None => {
+                let tracker = LatencyWindow::new(window, self.clock.clone());
+                self.add_observer(tracker.clone());
+                *latency_window = Some(tracker);
+            }
+        }
+    }
+
+    /// Get the `p`th percentile (0 to 100) of recent dispatch durations of `T`
+    ///
+    /// Uses the nearest-rank method over the samples in the window set by
+    /// [`set_latency_window`](Self::set_latency_window). Returns `None`
+    /// if tracking is off or no dispatch of `T` finished in the window.
+    /// Blocked dispatches are not sampled.
+    pub fn dispatch_percentile<T: Event + 'static>(&self, p: f64) -> Option<Duration> {
+        self.latency_percentile(LatencySeries::Dispatch(TypeId::of::<T>()), p)
+    }
+
+    /// Get the `p`th percentile (0 to 100) of a listener's recent durations
+    ///
+    /// See [`dispatch_percentile`](Self::dispatch_percentile).
+    pub fn listener_percentile(&self, id: impl Into<ListenerId>, p: f64) -> Option<Duration> {
+        self.latency_percentile(LatencySeries::Listener(id.into()), p)
+    }
+
+    fn latency_percentile(&self, series: LatencySeries, p: f64) -> Option<Duration> {
+        let tracker = self.latency_window.lock().unwrap().clone()?;
+        tracker.percentile(series, p)
+    }
+
+    /// Get event metrics
+    pub fn metrics(&self) -> HashMap<TypeId, EventMetadata> {
+        self.metrics.read().unwrap().clone()
+    }
+
+    /// Estimate the memory used by the dispatcher
+    ///
+    /// Useful for long-running processes with many dynamic subscriptions
+    /// that need to watch for listener leaks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    /// use std::sync::{Arc, Barrier};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Tick;
+    ///
+    /// impl Event for Tick {}
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Hold;
+    ///
+    /// impl Event for Hold {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// let before = dispatcher.memory_footprint();
+    ///
+    /// for _ in 0..100 {
+    ///     dispatcher.on(|_: &Tick| {});
+    /// }
+    ///
+    /// assert!(dispatcher.memory_footprint().listeners > before.listeners);
+    ///
+    /// // Keep the only worker busy while dispatches queue up
+    /// dispatcher.set_background_threads(1);
+    /// let release = Arc::new(Barrier::new(2));
+    /// let held = release.clone();
+    /// dispatcher.on(move |_: &Hold| {
+    ///     held.wait();
+    /// });
+    /// let hold = dispatcher.dispatch_background(Hold);
+    /// let idle = dispatcher.memory_footprint().queues;
+    ///
+    /// let handles: Vec<_> = (0..10).map(|_| dispatcher.dispatch_background(Tick)).collect();
+    /// assert!(dispatcher.memory_footprint().queues > idle);
+    ///
+    /// release.wait();
+    /// hold.wait().unwrap();
+    /// for handle in handles {
+    ///     handle.wait().unwrap();
+    /// }
+    /// ```
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        let listeners = table_size(&self.listeners.read().unwrap(), |listener| {
+            std::mem::size_of::<ListenerWrapper>() + std::mem::size_of_val(&*listener.handler)
+        });
+
+        #[cfg(feature = "async")]
+        let async_listeners = table_size(&self.async_listeners.read().unwrap(), |listener| {
+            std::mem::size_of_val(&*listener.handler)
+        });
+
+        #[cfg(not(feature = "async"))]
+        let async_listeners = 0;
+
+        let metrics = self.metrics.read().unwrap().capacity()
+            * std::mem::size_of::<(TypeId, EventMetadata)>();
+
+        #[cfg(feature = "middleware")]
+        let middleware = self.middleware.read().unwrap().heap_size();
+        #[cfg(not(feature = "middleware"))]
+        let middleware = 0;
+
+        let queues = {
+            let queues = self.queues.read().unwrap();
+            queues.capacity() * std::mem::size_of::<(TypeId, Arc<TypeQueue>)>()
+                + queues.len() * std::mem::size_of::<TypeQueue>()
+        } + self.workers.lock().unwrap().footprint();
+
+        MemoryFootprint {
+            listeners,
+            async_listeners,
+            middleware,
+            metrics,
+            queues,
+        }
+    }
+
+    /// Get metrics for every known instantiation of a generic event type
+    ///
+    /// Matches on [`EventMetadata::base_name`], so `"ValueChanged"` finds
+    /// `ValueChanged<u32>`, `ValueChanged<String>`, and so on. An event type
+    /// is known once it has been subscribed to or dispatched. Results are
+    /// sorted by [`EventMetadata::display_name`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct ValueChanged<T> {
+    ///     value: T,
+    /// }
+    ///
+    /// impl<T: std::fmt::Debug + Send + Sync + 'static> Event for ValueChanged<T> {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.on(|e: &ValueChanged<u32>| println!("{}", e.value));
+    /// dispatcher.on(|e: &ValueChanged<String>| println!("{}", e.value));
+    ///
+    /// let names: Vec<String> = dispatcher
+    ///     .instantiations("ValueChanged")
+    ///     .iter()
+    ///     .map(|meta| meta.display_name())
+    ///     .collect();
+    /// assert_eq!(names, ["ValueChanged<String>", "ValueChanged<u32>"]);
+    /// ```
+    pub fn instantiations(&self, base_name: &str) -> Vec<EventMetadata> {
+        let mut found: Vec<(String, EventMetadata)> = self
+            .metrics
+            .read()
+            .unwrap()
+            .values()
+            .filter(|meta| meta.base_name() == base_name)
+            .map(|meta| (meta.display_name(), meta.clone()))
+            .collect();
+        found.sort_by(|a, b| a.0.cmp(&b.0));
+        found.into_iter().map(|(_, meta)| meta).collect()
+    }
+
+    /// Check the dispatcher's health
+    ///
+    /// Reports listener error rates over the interval since the previous
+    /// call, classified with the default [`HealthThresholds`]. Suitable
+    /// for wiring into a `/healthz` endpoint.
+    ///
+    /// # Example
+    ///
     /// ```rust
-    /// use mod_events::{EventDispatcher, Event};
+    /// use mod_events::{Event, EventDispatcher, HealthStatus};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Job;
+    ///
+    /// impl Event for Job {}
     ///
     /// let dispatcher = EventDispatcher::new();
-    /// dispatcher.add_middleware(|event: &dyn Event| {
-    ///     println!("Processing event: {}", event.event_name());
-    ///     true // Allow all events
-    /// });
+    /// dispatcher.subscribe(|_: &Job| Err("worker offline".into()));
+    /// dispatcher.emit(Job);
+    ///
+    /// let report = dispatcher.health();
+    /// assert_eq!(report.status, HealthStatus::Unhealthy);
+    /// assert_eq!(report.errors, 1);
+    ///
+    /// // The next report only covers what happened since this one
+    /// assert_eq!(dispatcher.health().status, HealthStatus::Healthy);
     /// ```
-    pub fn add_middleware<F>(&self, middleware: F)
-    where
-        F: Fn(&dyn Event) -> bool + Send + Sync + 'static,
-    {
-        let mut middleware_manager = self.middleware.write().unwrap();
-        middleware_manager.add(middleware);
+    pub fn health(&self) -> HealthReport {
+        self.health_with(&HealthThresholds::default())
     }
 
-    /// Remove a listener
+    /// Check the dispatcher's health using custom thresholds
+    pub fn health_with(&self, thresholds: &HealthThresholds) -> HealthReport {
+        let current = self.counters.snapshot(self.clock.now());
+        let previous = std::mem::replace(&mut *self.health_baseline.lock().unwrap(), current);
+        HealthReport::between(&previous, &current, self.total_listener_count(), thresholds)
+    }
+
+    /// Get aggregated dispatcher statistics
     ///
-    /// Returns `true` if the listener was found and removed, `false` otherwise.
-    pub fn unsubscribe(&self, listener_id: ListenerId) -> bool {
-        // Try sync listeners first
-        {
-            let mut listeners = self.listeners.write().unwrap();
-            if let Some(event_listeners) = listeners.get_mut(&listener_id.type_id) {
-                if let Some(pos) = event_listeners.iter().position(|l| l.id == listener_id.id) {
-                    event_listeners.remove(pos);
-                    return true;
-                }
+    /// Reports uptime, lifetime totals, recent dispatch rates, the busiest
+    /// event types, and listener totals from counters kept on every
+    /// dispatch, so it is cheap enough to call from a periodic logger or
+    /// an admin endpoint. Unlike [`health`](Self::health), it does not
+    /// reset anything between calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, TestClock};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Click;
+    ///
+    /// impl Event for Click {}
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Scroll;
+    ///
+    /// impl Event for Scroll {}
+    ///
+    /// let clock = TestClock::new();
+    /// let dispatcher = EventDispatcher::with_clock(clock.clone());
+    /// dispatcher.on(|_: &Click| {});
+    /// dispatcher.subscribe(|_: &Scroll| Err("lost".into()));
+    ///
+    /// clock.advance(Duration::from_secs(20));
+    /// for _ in 0..30 {
+    ///     dispatcher.emit(Click);
+    /// }
+    /// dispatcher.emit(Scroll);
+    ///
+    /// let stats = dispatcher.stats();
+    /// assert_eq!(stats.uptime, Duration::from_secs(20));
+    /// assert_eq!(stats.dispatched, 31);
+    /// assert_eq!(stats.errors, 1);
+    /// assert_eq!(stats.listener_count, 2);
+    /// assert_eq!(stats.rate_1m, 31.0 / 20.0);
+    /// assert_eq!(stats.top_events[0].name, "Click");
+    /// assert_eq!(stats.top_events[0].dispatched, 30);
+    /// println!("{stats}");
+    /// ```
+    pub fn stats(&self) -> DispatcherStats {
+        self.stats_top(TOP_EVENT_TYPES)
+    }
+
+    /// Get aggregated dispatcher statistics listing the `n` busiest event types
+    ///
+    /// See [`stats`](Self::stats).
+    pub fn stats_top(&self, n: usize) -> DispatcherStats {
+        let now = self.clock.now();
+        let totals = self.counters.snapshot(now);
+        let (top_events, event_types) = {
+            let metrics = self.metrics.read().unwrap();
+            let mut busiest: Vec<&EventMetadata> = metrics
+                .values()
+                .filter(|meta| meta.dispatch_count > 0)
+                .collect();
+            let cut = n.min(busiest.len());
+            if cut < busiest.len() {
+                busiest.select_nth_unstable_by(cut, |a, b| b.dispatch_count.cmp(&a.dispatch_count));
+                busiest.truncate(cut);
             }
+            let mut top: Vec<EventVolume> = busiest
+                .into_iter()
+                .map(|meta| EventVolume {
+                    name: meta.display_name(),
+                    dispatched: meta.dispatch_count,
+                })
+                .collect();
+            top.sort_by(|a, b| {
+                b.dispatched
+                    .cmp(&a.dispatched)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+            (top, metrics.len())
+        };
+
+        DispatcherStats {
+            uptime: now.saturating_duration_since(self.rate.started()),
+            dispatched: totals.dispatched,
+            blocked: totals.blocked,
+            invocations: totals.invocations,
+            errors: totals.errors,
+            rate_1m: self.rate.per_second(now, Duration::from_secs(60)),
+            rate_5m: self.rate.per_second(now, Duration::from_secs(5 * 60)),
+            rate_15m: self.rate.per_second(now, Duration::from_secs(15 * 60)),
+            top_events,
+            listener_count: self.total_listener_count(),
+            event_types,
         }
+    }
 
-        // Try async listeners
+    /// Render a human-readable summary of the dispatcher state
+    ///
+    /// Lists every known event type with its dispatch count and its
+    /// listeners (priority, id, and name), along with the middleware
+    /// count. Intended as a one-call snapshot for diagnosing problems.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Priority};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct MyEvent;
+    ///
+    /// impl Event for MyEvent {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.subscribe_with_priority(|_: &MyEvent| Ok(()), Priority::High);
+    /// dispatcher.emit(MyEvent);
+    ///
+    /// let dump = dispatcher.dump();
+    /// assert!(dump.contains("MyEvent (dispatched 1 times"));
+    /// assert!(dump.contains("[High] #0"));
+    /// ```
+    pub fn dump(&self) -> String {
+        use std::fmt::Write;
+
+        let now = self.clock.now();
+        let metrics = self.metrics.read().unwrap();
+        let listeners = self.listeners.read().unwrap();
         #[cfg(feature = "async")]
-        {
-            let mut async_listeners = self.async_listeners.write().unwrap();
-            if let Some(event_listeners) = async_listeners.get_mut(&listener_id.type_id) {
-                if let Some(pos) = event_listeners.iter().position(|l| l.id == listener_id.id) {
-                    event_listeners.remove(pos);
-                    return true;
-                }
+        let async_listeners = self.async_listeners.read().unwrap();
+
+        let mut event_types: Vec<(String, &EventMetadata)> = metrics
+            .values()
+            .map(|meta| (meta.display_name(), meta))
+            .collect();
+        event_types.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut out = String::new();
+        let _ = writeln!(out, "EventDispatcher");
+        #[cfg(feature = "middleware")]
+        let _ = writeln!(
+            out,
+            "  middleware: {}",
+            self.middleware.read().unwrap().count()
+        );
+        let _ = writeln!(out, "  event types: {}", event_types.len());
+
+        for (name, meta) in event_types {
+            if meta.dispatch_count == 0 {
+                let _ = writeln!(out, "  {} (never dispatched)", name);
+            } else {
+                let _ = writeln!(
+                    out,
+                    "  {} (dispatched {} times, last {:?} ago)",
+                    name,
+                    meta.dispatch_count,
+                    meta.time_since(now)
+                );
+            }
+            for listener in listeners.get(&meta.type_id).into_iter().flatten() {
+                let _ = writeln!(
+                    out,
+                    "    [{:?}] #{} {}",
+                    listener.priority, listener.id, listener.name
+                );
+            }
+            #[cfg(feature = "async")]
+            for listener in async_listeners.get(&meta.type_id).into_iter().flatten() {
+                let _ = writeln!(
+                    out,
+                    "    [{:?}] #{} {} (async)",
+                    listener.priority, listener.id, listener.name
+                );
             }
         }
 
-        false
+        out
     }
 
-    /// Get the number of listeners for an event type
-    pub fn listener_count<T: Event + 'static>(&self) -> usize {
-        let type_id = TypeId::of::<T>();
-        let sync_count = self
-            .listeners
-            .read()
-            .unwrap()
-            .get(&type_id)
-            .map(|v| v.len())
-            .unwrap_or(0);
-
+    /// Describe the dispatcher's configuration and listener topology
+    ///
+    /// Unlike [`dump`](Self::dump), the result is structured data; see
+    /// [`DispatcherDescriptor`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, ListenerKind, ListenerOptions, Priority};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct OrderPlaced;
+    ///
+    /// impl Event for OrderPlaced {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.on_with_options(
+    ///     |_: &OrderPlaced| {},
+    ///     ListenerOptions::new().name("send-receipt").priority(Priority::High),
+    /// );
+    /// dispatcher.respond(|_: &OrderPlaced| 42u32);
+    ///
+    /// let descriptor = dispatcher.describe();
+    /// let order = &descriptor.event_types[0];
+    /// assert_eq!(order.display_name, "OrderPlaced");
+    /// assert_eq!(order.listeners[0].name, "send-receipt");
+    /// assert_eq!(order.listeners[0].priority, Priority::High);
+    /// assert_eq!(order.listeners[1].kind, ListenerKind::Responder);
+    /// ```
+    pub fn describe(&self) -> DispatcherDescriptor {
+        let metrics = self.metrics.read().unwrap();
+        let listeners = self.listeners.read().unwrap();
         #[cfg(feature = "async")]
-        let async_count = self
-            .async_listeners
-            .read()
-            .unwrap()
-            .get(&type_id)
-            .map(|v| v.len())
-            .unwrap_or(0);
+        let async_listeners = self.async_listeners.read().unwrap();
+        let responders = self.responders.read().unwrap();
 
-        #[cfg(not(feature = "async"))]
-        let async_count = 0;
+        let mut event_types: Vec<EventTypeDescriptor> = metrics
+            .values()
+            .map(|meta| {
+                let mut described: Vec<ListenerDescriptor> = listeners
+                    .get(&meta.type_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|listener| ListenerDescriptor {
+                        id: listener.id,
+                        name: listener.name,
+                        priority: listener.priority,
+                        kind: ListenerKind::Sync,
+                        first_match: listener.group,
+                        balanced: listener.balanced,
+                        namespace: listener.namespace.as_deref().map(str::to_owned),
+                        tenant: listener.tenant.as_ref().map(TenantId::to_string),
+                    })
+                    .collect();
+                #[cfg(feature = "async")]
+                described.extend(
+                    async_listeners
+                        .get(&meta.type_id)
+                        .into_iter()
+                        .flatten()
+                        .map(|listener| ListenerDescriptor {
+                            id: listener.id,
+                            name: listener.name,
+                            priority: listener.priority,
+                            kind: ListenerKind::Async,
+                            first_match: None,
+                            balanced: None,
+                            namespace: None,
+                            tenant: None,
+                        }),
+                );
+                described.extend(
+                    responders
+                        .iter()
+                        .filter(|((type_id, _), _)| *type_id == meta.type_id)
+                        .flat_map(|(_, responders)| responders)
+                        .map(|responder| ListenerDescriptor {
+                            id: responder.id,
+                            name: responder.name,
+                            priority: responder.priority,
+                            kind: ListenerKind::Responder,
+                            first_match: None,
+                            balanced: None,
+                            namespace: None,
+                            tenant: None,
+                        }),
+                );
+                EventTypeDescriptor {
+                    type_name: meta.event_name,
+                    display_name: meta.display_name(),
+                    delivery: self.delivery_of(meta.type_id),
+                    dispatch_count: meta.dispatch_count,
+                    listeners: described,
+                }
+            })
+            .collect();
+        event_types.sort_by(|a, b| a.display_name.cmp(&b.display_name));
 
-        sync_count + async_count
+        DispatcherDescriptor {
+            config: self.config(),
+            #[cfg(feature = "middleware")]
+            middleware: self.middleware.read().unwrap().names(),
+            #[cfg(not(feature = "middleware"))]
+            middleware: Vec::new(),
+            observers: self.observers.read().unwrap().len(),
+            listener_hooks: self.listener_hooks.read().unwrap().len(),
+            event_types,
+        }
     }
 
-    /// Get event metrics
-    pub fn metrics(&self) -> HashMap<TypeId, EventMetadata> {
-        self.metrics.read().unwrap().clone()
+    /// Move every listener of `other` into this dispatcher
+    ///
+    /// Lets a library that builds its own dispatcher join an
+    /// application-wide bus without re-registering its listeners. Sync
+    /// listeners, async listeners and responders keep their priorities
+    /// and options and run after this dispatcher's listeners of the same
+    /// priority. They get new ids; ids issued by `other` no longer refer
+    /// to them. `other` is left without listeners; its middleware,
+    /// observers and metrics are not merged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct CacheMiss;
+    ///
+    /// impl Event for CacheMiss {}
+    ///
+    /// // Built inside a library
+    /// let library = EventDispatcher::new();
+    /// library.on(|_: &CacheMiss| println!("warming cache"));
+    ///
+    /// let app = EventDispatcher::new();
+    /// app.on(|_: &CacheMiss| println!("counting misses"));
+    /// app.merge(&library);
+    ///
+    /// assert_eq!(app.dispatch(CacheMiss).listener_count(), 2);
+    /// assert_eq!(library.listener_count::<CacheMiss>(), 0);
+    /// ```
+    pub fn merge(&self, other: &EventDispatcher) {
+        if std::ptr::eq(self, other) {
+            return;
+        }
+
+        let mut renumbered = HashMap::new();
+        let mut renumber = |id: usize| {
+            *renumbered
+                .entry(id)
+                .or_insert_with(|| self.next_id.fetch_add(1, Ordering::Relaxed))
+        };
+        let mut touched = Vec::new();
+
+        let incoming = std::mem::take(&mut *other.listeners.write().unwrap());
+        {
+            let mut listeners = self.listeners.write().unwrap();
+            for (type_id, merged) in incoming {
+                let event_listeners = listeners.entry(type_id).or_default();
+                event_listeners.extend(
+                    merged
+                        .iter()
+                        .map(|listener| Arc::new(listener.renumbered(&mut renumber))),
+                );
+                event_listeners.sort_by_key(|l| std::cmp::Reverse(l.priority));
+                touched.push(type_id);
+            }
+        }
+
+        #[cfg(feature = "async")]
+        {
+            let incoming = std::mem::take(&mut *other.async_listeners.write().unwrap());
+            let mut async_listeners = self.async_listeners.write().unwrap();
+            for (type_id, mut merged) in incoming {
+                for listener in &mut merged {
+                    listener.id = renumber(listener.id);
+                }
+                let event_listeners = async_listeners.entry(type_id).or_default();
+                event_listeners.extend(merged);
+                event_listeners.sort_by_key(|l| std::cmp::Reverse(l.priority));
+                touched.push(type_id);
+            }
+        }
+
+        let incoming = std::mem::take(&mut *other.responders.write().unwrap());
+        {
+            let mut responders = self.responders.write().unwrap();
+            for (key, mut merged) in incoming {
+                for responder in &mut merged {
+                    responder.id = renumber(responder.id);
+                }
+                let event_responders = responders.entry(key).or_default();
+                event_responders.extend(merged);
+                event_responders.sort_by_key(|r| std::cmp::Reverse(r.priority));
+                touched.push(key.0);
+            }
+        }
+
+        let now = self.clock.now();
+        let other_metrics = other.metrics();
+        for type_id in touched {
+            let count = self.listener_count_of(type_id);
+            self.metrics
+                .write()
+                .unwrap()
+                .entry(type_id)
+                .or_insert_with(|| {
+                    let name = other_metrics
+                        .get(&type_id)
+                        .map_or("<merged>", |meta| meta.event_name);
+                    EventMetadata::new(name, type_id, now)
+                })
+                .update_listener_count(count);
+            if let Some(meta) = other.metrics.write().unwrap().get_mut(&type_id) {
+                meta.update_listener_count(0);
+            }
+        }
     }
 
     /// Clear all listeners
     pub fn clear(&self) {
-        self.listeners.write().unwrap().clear();
+        // Removal callbacks run once the locks are released
+        let _listeners = std::mem::take(&mut *self.listeners.write().unwrap());
+        self.responders.write().unwrap().clear();
 
         #[cfg(feature = "async")]
-        self.async_listeners.write().unwrap().clear();
+        let _async_listeners = std::mem::take(&mut *self.async_listeners.write().unwrap());
     }
 
-    fn update_metrics<T: Event>(&self, _event: &T) {
+    fn update_metrics(&self, event: &dyn Event) {
+        if !self.metrics_enabled() {
+            return;
+        }
+        let now = self.clock.now();
         let mut metrics = self.metrics.write().unwrap();
-        let type_id = TypeId::of::<T>();
+        let type_id = event.as_any().type_id();
 
         match metrics.get_mut(&type_id) {
             Some(meta) => {
-                meta.increment_dispatch();
+                meta.increment_dispatch(now);
             }
             None => {
-                let mut meta = EventMetadata::new::<T>();
-                meta.increment_dispatch();
+                let mut meta = EventMetadata::new(event.event_name(), type_id, now);
+                meta.increment_dispatch(now);
                 metrics.insert(type_id, meta);
             }
         }
     }
 
     fn update_listener_count<T: Event + 'static>(&self) {
+        let now = self.clock.now();
         let mut metrics = self.metrics.write().unwrap();
         let type_id = TypeId::of::<T>();
         let count = self.listener_count::<T>();
@@ -459,16 +4683,341 @@ impl EventDispatcher {
                 meta.update_listener_count(count);
             }
             None => {
-                let mut meta = EventMetadata::new::<T>();
+                let mut meta = EventMetadata::new(std::any::type_name::<T>(), type_id, now);
                 meta.update_listener_count(count);
                 metrics.insert(type_id, meta);
             }
         }
     }
 
-    fn check_middleware(&self, event: &dyn Event) -> bool {
+    /// Snapshot the registered observers, or `None` if there are none
+    fn observers(&self) -> Option<Arc<Vec<Arc<dyn DispatchObserver>>>> {
+        let observers = self.observers.read().unwrap();
+        (!observers.is_empty()).then(|| observers.clone())
+    }
+
+    /// Remove delivery-limited listeners that have used up their deliveries
+    fn remove_spent(&self, spent: Vec<ListenerId>) {
+        for id in spent {
+            self.unsubscribe(id);
+        }
+    }
+
+    /// Select the listeners a dispatch reaches
+    ///
+    /// Drops listeners outside the dispatch's namespace or tenant, then
+    /// the load-balanced group members whose turn it is not. The table is
+    /// released before this returns, so the listeners may subscribe,
+    /// unsubscribe, or dispatch while they run.
+    fn eligible_listeners(
+        &self,
+        type_id: TypeId,
+        event: &dyn Event,
+        context: &DispatchContext,
+    ) -> Vec<Arc<ListenerWrapper>> {
+        let namespace = context.get::<ActiveNamespace>().map(|active| &*active.0);
+        let exported = namespace.is_some_and(|namespace| {
+            self.exports
+                .read()
+                .unwrap()
+                .get(namespace)
+                .is_some_and(|types| types.contains(&type_id))
+        });
+        let tenant = context.get::<TenantId>();
+        let event_name = event.downcast_ref::<DynamicEvent>().map(DynamicEvent::name);
+        let mut eligible: Vec<Arc<ListenerWrapper>> =
+            match self.listeners.read().unwrap().get(&type_id) {
+                Some(listeners) => listeners
+                    .iter()
+                    .filter(|listener| {
+                        !listener.is_spent()
+                            && listener.visible_in(namespace, exported)
+                            && listener
+                                .tenant
+                                .as_ref()
+                                .is_none_or(|own| Some(own) == tenant)
+                            && listener
+                                .event_name
+                                .as_deref()
+                                .is_none_or(|own| Some(own) == event_name)
+                    })
+                    .cloned()
+                    .collect(),
+                None => return Vec::new(),
+            };
+        let passed_over = self.balance_turn(type_id, &eligible);
+        if !passed_over.is_empty() {
+            eligible.retain(|listener| !passed_over.contains(&listener.id));
+        }
+        eligible
+    }
+
+    /// Make events of `T` dispatched in `namespace` visible outside it
+    pub(crate) fn export(&self, namespace: &Arc<str>, type_id: TypeId) {
+        self.exports
+            .write()
+            .unwrap()
+            .entry(namespace.clone())
+            .or_default()
+            .insert(type_id);
+    }
+
+    /// Pick this event's worker in each load-balanced group
+    ///
+    /// Returns the ids of the group members that were passed over.
+    /// Every listener of a unicast event type is in one implicit group.
+    fn balance_turn(&self, type_id: TypeId, listeners: &[Arc<ListenerWrapper>]) -> Vec<usize> {
+        let unicast = self.delivery_of(type_id) == Delivery::Unicast;
+        if !unicast && listeners.iter().all(|listener| listener.balanced.is_none()) {
+            return Vec::new();
+        }
+
+        let mut members: HashMap<&'static str, Vec<&ListenerWrapper>> = HashMap::new();
+        for listener in listeners {
+            let group = if unicast {
+                Some(UNICAST_POOL)
+            } else {
+                listener.balanced
+            };
+            if let Some(group) = group {
+                members.entry(group).or_default().push(listener);
+            }
+        }
+        // Subscription order, so turns do not depend on priority
+        for group in members.values_mut() {
+            group.sort_by_key(|listener| listener.id);
+        }
+
+        let mut balance = self.balance.lock().unwrap();
+        let mut passed_over = Vec::new();
+        for (group, members) in members {
+            let strategy = balance.strategies.get(group).copied().unwrap_or_default();
+            let turn = balance.turns.entry((type_id, group)).or_default();
+            let start = *turn % members.len();
+            let chosen = match strategy {
+                LoadBalance::RoundRobin => start,
+                LoadBalance::LeastBusy => (0..members.len())
+                    .map(|offset| (start + offset) % members.len())
+                    .min_by_key(|&index| members[index].in_flight.load(Ordering::Relaxed))
+                    .unwrap_or(start),
+            };
+            *turn = chosen + 1;
+            passed_over.extend(
+                members
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| *index != chosen)
+                    .map(|(_, listener)| listener.id),
+            );
+        }
+        passed_over
+    }
+
+    fn delivery_of(&self, type_id: TypeId) -> Delivery {
+        self.delivery
+            .read()
+            .unwrap()
+            .get(&type_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Snapshot the registered listener hooks, or `None` if there are none
+    fn listener_hooks(&self) -> Option<ListenerHooks> {
+        let hooks = self.listener_hooks.read().unwrap();
+        (!hooks.is_empty()).then(|| hooks.clone())
+    }
+
+    /// Run the `before` hooks and start timing if anything will report the listener
+    fn start_listener(
+        &self,
+        observers: Option<&Arc<Vec<Arc<dyn DispatchObserver>>>>,
+        hooks: Option<&ListenerHooks>,
+        event: &dyn Event,
+        listener: &ListenerInfo,
+    ) -> Option<Instant> {
+        for (_, hook) in hooks.into_iter().flat_map(|hooks| hooks.iter()) {
+            hook.before(event, listener);
+        }
+        (observers.is_some() || hooks.is_some()).then(|| self.clock.now())
+    }
+
+    /// Report a finished listener to the observers and hooks, if any
+    fn observe_listener(
+        &self,
+        observers: Option<&Arc<Vec<Arc<dyn DispatchObserver>>>>,
+        hooks: Option<&ListenerHooks>,
+        started: Option<Instant>,
+        event: &dyn Event,
+        listener: &ListenerInfo,
+        result: &Result<(), ListenerError>,
+    ) {
+        let Some(started) = started else {
+            return;
+        };
+        let elapsed = self.clock.now().saturating_duration_since(started);
+        for observer in observers.into_iter().flat_map(|observers| observers.iter()) {
+            observer.on_listener(event, listener.id, listener.name, result, elapsed);
+        }
+        for (_, hook) in hooks.into_iter().flat_map(|hooks| hooks.iter()) {
+            hook.after(event, listener, result, elapsed);
+        }
+    }
+
+    fn record_outcome(
+        &self,
+        type_id: TypeId,
+        event: &dyn Event,
+        started: Instant,
+        result: DispatchResult,
+    ) -> DispatchResult {
+        let undelivered = !result.is_blocked()
+            && result.listener_count() == 0
+            && self.delivery_of(type_id) == Delivery::Unicast;
+        let result = result
+            .with_cancelled(event.is_cancelled())
+            .with_undelivered(undelivered);
+        self.counters.dispatched.fetch_add(1, Ordering::Relaxed);
+        self.rate.record(started);
+
+        if let Some(observers) = self.observers() {
+            let elapsed = self.clock.now().saturating_duration_since(started);
+            for observer in observers.iter() {
+                observer.on_dispatch(event, &result, elapsed);
+            }
+        }
+
+        if let Some(reason) = result.block_reason() {
+            self.counters.blocked.fetch_add(1, Ordering::Relaxed);
+            if !is_meta_event(type_id) {
+                self.emit_meta(|| EventBlocked {
+                    event: event.event_name(),
+                    reason: reason.clone(),
+                });
+            }
+            return result;
+        }
+
+        let errors = result.error_count();
+        self.counters
+            .invocations
+            .fetch_add(result.listener_count(), Ordering::Relaxed);
+        if errors > 0 {
+            self.counters.errors.fetch_add(errors, Ordering::Relaxed);
+            if !self.metrics_enabled() {
+                return result;
+            }
+            if let Some(meta) = self.metrics.write().unwrap().get_mut(&type_id) {
+                meta.add_errors(errors);
+            }
+        }
+
+        result
+    }
+
+    fn total_listener_count(&self) -> usize {
+        let sync_count: usize = self.listeners.read().unwrap().values().map(Vec::len).sum();
+
+        #[cfg(feature = "async")]
+        let async_count: usize = self
+            .async_listeners
+            .read()
+            .unwrap()
+            .values()
+            .map(Vec::len)
+            .sum();
+
+        #[cfg(not(feature = "async"))]
+        let async_count = 0;
+
+        let responder_count: usize = self.responders.read().unwrap().values().map(Vec::len).sum();
+
+        sync_count + async_count + responder_count
+    }
+
+    pub(crate) fn listener_id(&self, id: usize, type_id: TypeId) -> ListenerId {
+        ListenerId::new(self.id, id, type_id)
+    }
+
+    #[cfg(feature = "middleware")]
+    fn check_middleware(
+        &self,
+        event: &dyn Event,
+        context: &DispatchContext,
+    ) -> Result<(), BlockReason> {
         let middleware = self.middleware.read().unwrap();
-        middleware.process(event)
+        middleware.process_with_context(event, context)
+    }
+
+    /// Without the "middleware" feature nothing can block an event
+    #[cfg(not(feature = "middleware"))]
+    #[inline(always)]
+    fn check_middleware(
+        &self,
+        _event: &dyn Event,
+        _context: &DispatchContext,
+    ) -> Result<(), BlockReason> {
+        Ok(())
+    }
+}
+
+/// Estimate the heap bytes of a listener table, including each entry's closure
+fn table_size<W>(table: &HashMap<TypeId, Vec<W>>, handler_size: impl Fn(&W) -> usize) -> usize {
+    let buckets = table.capacity() * std::mem::size_of::<(TypeId, Vec<W>)>();
+    let entries: usize = table
+        .values()
+        .map(|listeners| {
+            listeners.capacity() * std::mem::size_of::<W>()
+                + listeners.iter().map(&handler_size).sum::<usize>()
+        })
+        .sum();
+    buckets + entries
+}
+
+/// Owned event queued for background dispatch
+struct Owned<T>(T);
+
+impl<T: Event> EventRef for Owned<T> {
+    type Event = T;
+
+    fn event(&self) -> &T {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for EventDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let metrics = self.metrics.read().unwrap();
+        let name_of = |type_id: &TypeId| metrics.get(type_id).map_or("<unknown>", |m| m.event_name);
+
+        let listeners = self.listeners.read().unwrap();
+        let listeners: std::collections::BTreeMap<_, _> =
+            listeners.iter().map(|(k, v)| (name_of(k), v)).collect();
+
+        let mut debug = f.debug_struct("EventDispatcher");
+        debug.field("listeners", &listeners);
+
+        #[cfg(feature = "async")]
+        {
+            let async_listeners = self.async_listeners.read().unwrap();
+            let async_listeners: std::collections::BTreeMap<_, _> = async_listeners
+                .iter()
+                .map(|(k, v)| (name_of(k), v))
+                .collect();
+            debug.field("async_listeners", &async_listeners);
+        }
+
+        #[cfg(feature = "middleware")]
+        debug.field("middleware", &*self.middleware.read().unwrap());
+        debug
+            .field("metrics", &metrics.values().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Dispatch for EventDispatcher {
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        self.dispatch_event(&event)
     }
 }
 
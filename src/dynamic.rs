@@ -0,0 +1,55 @@
+//! Name-addressed events for dynamic producers and consumers
+
+use crate::Event;
+
+/// An event identified by name at runtime rather than by Rust type
+///
+/// Dynamic events carry an opaque byte payload and are routed by their
+/// name. They are the bridge between the typed dispatcher and producers
+/// that cannot share Rust types with the host, such as C plugins.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{DynamicEvent, EventDispatcher};
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.subscribe_named("user.registered", |event: &DynamicEvent| {
+///     println!("Payload: {:?}", event.payload());
+///     Ok(())
+/// });
+///
+/// dispatcher.emit(DynamicEvent::new("user.registered", b"alice".to_vec()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicEvent {
+    name: String,
+    payload: Vec<u8>,
+}
+
+impl DynamicEvent {
+    /// Create a new dynamic event
+    pub fn new(name: impl Into<String>, payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            payload: payload.into(),
+        }
+    }
+
+    /// Get the event name used for routing
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the raw payload bytes
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Consume the event and return its payload
+    pub fn into_payload(self) -> Vec<u8> {
+        self.payload
+    }
+}
+
+impl Event for DynamicEvent {}
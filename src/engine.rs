@@ -0,0 +1,161 @@
+//! Bevy integration (requires "bevy" feature)
+//!
+//! [`EventBusPlugin`] inserts an [`EventBus`] resource and pumps its queued
+//! events once per frame, in the [`Last`] schedule. Engine events can be
+//! forwarded onto the bus with [`EventBusPlugin::forward`].
+
+use crate::sync::Mutex;
+use crate::{Event, EventDispatcher};
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::event::EventReader;
+use bevy_ecs::prelude::{IntoScheduleConfigs, Res, Resource};
+use std::sync::Arc;
+
+/// Dispatcher resource with a per-frame event queue
+///
+/// Systems queue events with [`queue`](Self::queue) instead of
+/// dispatching inline, so listeners run at a predictable point in the
+/// frame. Listeners may queue further events; those are delivered on the
+/// next frame.
+#[derive(Debug, Clone, Resource)]
+pub struct EventBus {
+    dispatcher: Arc<EventDispatcher>,
+    queue: Arc<Mutex<Vec<Box<dyn Event>>>>,
+}
+
+impl EventBus {
+    /// Create a bus over a shared dispatcher
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self {
+            dispatcher,
+            queue: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Get the underlying dispatcher
+    pub fn dispatcher(&self) -> &Arc<EventDispatcher> {
+        &self.dispatcher
+    }
+
+    /// Queue an event for the next pump
+    pub fn queue<T: Event>(&self, event: T) {
+        self.queue.lock().unwrap().push(Box::new(event));
+    }
+
+    /// Get the number of queued events
+    pub fn pending(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    /// Dispatch every queued event, returning how many were dispatched
+    pub fn pump(&self) -> usize {
+        let events = std::mem::take(&mut *self.queue.lock().unwrap());
+        let count = events.len();
+        for event in events {
+            self.dispatcher.dispatch_boxed(event);
+        }
+        count
+    }
+}
+
+/// System that pumps the [`EventBus`] queue
+pub fn pump_events(bus: Res<EventBus>) {
+    bus.pump();
+}
+
+/// System that queues every engine event `E` onto the [`EventBus`]
+pub fn forward_engine_events<E>(mut reader: EventReader<E>, bus: Res<EventBus>)
+where
+    E: bevy_ecs::event::Event + Event + Clone,
+{
+    for event in reader.read() {
+        bus.queue(event.clone());
+    }
+}
+
+/// Plugin installing an [`EventBus`] and its per-frame pump
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_app::App;
+/// use bevy_ecs::event::Events;
+/// use mod_events::engine::{EventBus, EventBusPlugin};
+/// use mod_events::{Event, EventDispatcher};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone, bevy_ecs::event::Event)]
+/// struct Collision {
+///     damage: u32,
+/// }
+///
+/// impl Event for Collision {}
+///
+/// #[derive(Debug, Clone)]
+/// struct ScoreChanged;
+///
+/// impl Event for ScoreChanged {}
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// let damage = Arc::new(AtomicUsize::new(0));
+/// let total = damage.clone();
+/// dispatcher.on(move |hit: &Collision| {
+///     total.fetch_add(hit.damage as usize, Ordering::SeqCst);
+/// });
+///
+/// let mut app = App::new();
+/// app.add_plugins(EventBusPlugin::new(dispatcher).forward::<Collision>());
+///
+/// app.world_mut().resource::<EventBus>().queue(ScoreChanged);
+/// app.world_mut().resource_mut::<Events<Collision>>().send(Collision { damage: 3 });
+/// app.update();
+///
+/// assert_eq!(damage.load(Ordering::SeqCst), 3);
+/// assert_eq!(app.world().resource::<EventBus>().pending(), 0);
+/// ```
+pub struct EventBusPlugin {
+    dispatcher: Arc<EventDispatcher>,
+    forwarders: Vec<fn(&mut App)>,
+}
+
+impl EventBusPlugin {
+    /// Create a plugin for a shared dispatcher
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self {
+            dispatcher,
+            forwarders: Vec::new(),
+        }
+    }
+
+    /// Forward engine events of type `E` onto the bus each frame
+    pub fn forward<E>(mut self) -> Self
+    where
+        E: bevy_ecs::event::Event + Event + Clone,
+    {
+        self.forwarders.push(|app| {
+            app.add_event::<E>()
+                .add_systems(Last, forward_engine_events::<E>.before(pump_events));
+        });
+        self
+    }
+}
+
+impl std::fmt::Debug for EventBusPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBusPlugin")
+            .field("dispatcher", &self.dispatcher)
+            .field("forwarded_count", &self.forwarders.len())
+            .finish()
+    }
+}
+
+impl Plugin for EventBusPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(EventBus::new(self.dispatcher.clone()))
+            .add_systems(Last, pump_events);
+        for forward in &self.forwarders {
+            forward(app);
+        }
+    }
+}
@@ -0,0 +1,679 @@
+//! C ABI for native plugins (requires "ffi" feature)
+//!
+//! Exposes an opaque dispatcher handle so code written in C, C++, or any
+//! language with a C FFI can publish and consume events. Events crossing
+//! the boundary are [`DynamicEvent`]s: a UTF-8 name plus a byte payload.
+//!
+//! The equivalent C declarations are:
+//!
+//! ```c
+//! typedef struct ModEventsDispatcher ModEventsDispatcher;
+//! typedef int (*ModEventsCallback)(const char *name, const uint8_t *payload,
+//!                                  size_t len, void *user_data);
+//! typedef void (*ModEventsRelease)(void *user_data);
+//!
+//! ModEventsDispatcher *mod_events_dispatcher_new(void);
+//! void mod_events_dispatcher_free(ModEventsDispatcher *handle);
+//! int mod_events_subscribe(ModEventsDispatcher *handle, const char *name,
+//!                          ModEventsCallback callback, void *user_data,
+//!                          uint64_t *out_id);
+//! int mod_events_subscribe_with_release(ModEventsDispatcher *handle,
+//!                                       const char *name,
+//!                                       ModEventsCallback callback,
+//!                                       void *user_data,
+//!                                       ModEventsRelease release,
+//!                                       uint64_t *out_id);
+//! int mod_events_unsubscribe(ModEventsDispatcher *handle, uint64_t id);
+//! int mod_events_emit(ModEventsDispatcher *handle, const char *name,
+//!                     const uint8_t *payload, size_t len, size_t *out_failed);
+//! ```
+//!
+//! Rust hosts usually create the handle from their own dispatcher with
+//! [`ModEventsDispatcher::from_dispatcher`] and pass the raw pointer to
+//! their plugins, so native and Rust listeners share one bus. Rust plugins
+//! loaded as separate dynamic libraries should go through the versioned
+//! [`ModEventsPluginApi`] table and [`PluginContext`] instead of linking
+//! against the host's types.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mod_events::ffi::*;
+//! use std::ffi::{c_char, c_int, c_void};
+//!
+//! extern "C" fn on_event(
+//!     _name: *const c_char,
+//!     _payload: *const u8,
+//!     len: usize,
+//!     _user_data: *mut c_void,
+//! ) -> c_int {
+//!     assert_eq!(len, 3);
+//!     0
+//! }
+//!
+//! unsafe {
+//!     let handle = mod_events_dispatcher_new();
+//!     let mut id = 0;
+//!     let status = mod_events_subscribe(
+//!         handle,
+//!         c"tick".as_ptr(),
+//!         on_event,
+//!         std::ptr::null_mut(),
+//!         &mut id,
+//!     );
+//!     assert_eq!(status, MOD_EVENTS_OK);
+//!
+//!     let mut failed = 0;
+//!     mod_events_emit(handle, c"tick".as_ptr(), [1, 2, 3].as_ptr(), 3, &mut failed);
+//!     assert_eq!(failed, 0);
+//!
+//!     mod_events_unsubscribe(handle, id);
+//!     mod_events_dispatcher_free(handle);
+//! }
+//! ```
+
+use crate::{DynamicEvent, EventDispatcher};
+use std::any::TypeId;
+use std::ffi::{c_char, c_int, c_void, CStr};
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+/// The call completed successfully
+pub const MOD_EVENTS_OK: c_int = 0;
+/// A required pointer argument was null
+pub const MOD_EVENTS_ERR_NULL: c_int = -1;
+/// The event name was not valid UTF-8
+pub const MOD_EVENTS_ERR_UTF8: c_int = -2;
+/// No listener with the given id was found
+pub const MOD_EVENTS_ERR_NOT_FOUND: c_int = -3;
+/// A Rust listener or callback panicked; the panic was stopped at the boundary
+pub const MOD_EVENTS_ERR_PANIC: c_int = -4;
+
+/// Callback invoked for each matching event
+///
+/// Receives the event name, the payload pointer and length, and the
+/// `user_data` pointer given at subscription. Return `0` on success;
+/// any other value is recorded as a listener error.
+pub type ModEventsCallback = extern "C" fn(
+    name: *const c_char,
+    payload: *const u8,
+    len: usize,
+    user_data: *mut c_void,
+) -> c_int;
+
+/// Callback invoked with a listener's `user_data` once it is never called again
+///
+/// Runs after the listener is unsubscribed, or its dispatcher freed, and
+/// every call already in flight has returned; the pointee can be freed
+/// there.
+pub type ModEventsRelease = extern "C" fn(user_data: *mut c_void);
+
+/// Opaque dispatcher handle shared with native code
+#[derive(Debug)]
+pub struct ModEventsDispatcher {
+    dispatcher: Arc<EventDispatcher>,
+}
+
+impl ModEventsDispatcher {
+    /// Wrap an existing dispatcher in a handle for native code
+    pub fn from_dispatcher(dispatcher: Arc<EventDispatcher>) -> Box<Self> {
+        Box::new(Self { dispatcher })
+    }
+
+    /// Get the dispatcher behind this handle
+    pub fn dispatcher(&self) -> &Arc<EventDispatcher> {
+        &self.dispatcher
+    }
+}
+
+/// Error recorded when a native callback returns a non-zero status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallbackError {
+    /// The status code returned by the callback
+    pub code: c_int,
+}
+
+impl fmt::Display for CallbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "native callback failed with status {}", self.code)
+    }
+}
+
+impl std::error::Error for CallbackError {}
+
+/// The `user_data` pointer handed back to native callbacks
+///
+/// The subscriber guarantees the pointee may be used from any thread.
+/// Owned by the listener, so the release callback runs once the listener
+/// and every call still using it are gone.
+struct UserData(*mut c_void, Option<ModEventsRelease>);
+
+impl UserData {
+    fn get(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+impl Drop for UserData {
+    fn drop(&mut self) {
+        if let Some(release) = self.1 {
+            release(self.0);
+        }
+    }
+}
+
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+/// Create a new dispatcher handle
+///
+/// The handle must be released with [`mod_events_dispatcher_free`].
+#[no_mangle]
+pub extern "C" fn mod_events_dispatcher_new() -> *mut ModEventsDispatcher {
+    Box::into_raw(ModEventsDispatcher::from_dispatcher(Arc::new(
+        EventDispatcher::new(),
+    )))
+}
+
+/// Release a dispatcher handle
+///
+/// # Safety
+///
+/// `handle` must be null or a pointer obtained from this module (or from
+/// `Box::into_raw` on a [`ModEventsDispatcher`]) that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn mod_events_dispatcher_free(handle: *mut ModEventsDispatcher) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Subscribe a native callback to events with the given name
+///
+/// On success the listener id is written to `out_id` (if non-null).
+///
+/// # Safety
+///
+/// `handle` must be a live dispatcher handle and `name` a valid
+/// NUL-terminated string. `user_data` is passed back verbatim and must
+/// be safe to use from any thread. It must outlive every call of the
+/// listener, and a dispatch already in progress may still make one after
+/// [`mod_events_unsubscribe`] returns; hosts that free `user_data` should
+/// use [`mod_events_subscribe_with_release`] instead.
+#[no_mangle]
+pub unsafe extern "C" fn mod_events_subscribe(
+    handle: *mut ModEventsDispatcher,
+    name: *const c_char,
+    callback: ModEventsCallback,
+    user_data: *mut c_void,
+    out_id: *mut u64,
+) -> c_int {
+    mod_events_subscribe_with_release(handle, name, callback, user_data, None, out_id)
+}
+
+/// Subscribe a native callback, freeing `user_data` through `release`
+///
+/// Like [`mod_events_subscribe`], but `release` (if non-null) is called
+/// with `user_data` once the listener is removed and no call is still
+/// running, so the host can free it there rather than after
+/// [`mod_events_unsubscribe`]. It is not called if subscribing fails.
+///
+/// # Safety
+///
+/// `handle` must be a live dispatcher handle and `name` a valid
+/// NUL-terminated string. `user_data` is passed back verbatim and must
+/// remain valid, and safe to use from any thread, until `release` is
+/// called with it.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::ffi::*;
+/// use std::ffi::{c_char, c_int, c_void};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// static RELEASED: AtomicBool = AtomicBool::new(false);
+///
+/// extern "C" fn count(
+///     _name: *const c_char,
+///     _payload: *const u8,
+///     _len: usize,
+///     user_data: *mut c_void,
+/// ) -> c_int {
+///     unsafe { *(user_data as *mut u32) += 1 };
+///     0
+/// }
+///
+/// extern "C" fn release(user_data: *mut c_void) {
+///     drop(unsafe { Box::from_raw(user_data as *mut u32) });
+///     RELEASED.store(true, Ordering::SeqCst);
+/// }
+///
+/// unsafe {
+///     let handle = mod_events_dispatcher_new();
+///     let calls = Box::into_raw(Box::new(0u32));
+///     let mut id = 0;
+///     let status = mod_events_subscribe_with_release(
+///         handle,
+///         c"tick".as_ptr(),
+///         count,
+///         calls.cast(),
+///         Some(release),
+///         &mut id,
+///     );
+///     assert_eq!(status, MOD_EVENTS_OK);
+///
+///     mod_events_emit(handle, c"tick".as_ptr(), std::ptr::null(), 0, std::ptr::null_mut());
+///     assert_eq!(*calls, 1);
+///
+///     // Frees the counter; no dispatch is running
+///     mod_events_unsubscribe(handle, id);
+///     assert!(RELEASED.load(Ordering::SeqCst));
+///     mod_events_dispatcher_free(handle);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn mod_events_subscribe_with_release(
+    handle: *mut ModEventsDispatcher,
+    name: *const c_char,
+    callback: ModEventsCallback,
+    user_data: *mut c_void,
+    release: Option<ModEventsRelease>,
+    out_id: *mut u64,
+) -> c_int {
+    if handle.is_null() || name.is_null() {
+        return MOD_EVENTS_ERR_NULL;
+    }
+    let c_name = CStr::from_ptr(name).to_owned();
+    let name = match c_name.to_str() {
+        Ok(name) => name.to_owned(),
+        Err(_) => return MOD_EVENTS_ERR_UTF8,
+    };
+
+    let user_data = UserData(user_data, release);
+    let listener_id = (*handle)
+        .dispatcher
+        .subscribe_named(name, move |event: &DynamicEvent| {
+            let payload = event.payload();
+            let code = callback(
+                c_name.as_ptr(),
+                payload.as_ptr(),
+                payload.len(),
+                user_data.get(),
+            );
+            if code == 0 {
+                Ok(())
+            } else {
+                Err(Box::new(CallbackError { code }))
+            }
+        });
+
+    if !out_id.is_null() {
+        *out_id = listener_id.id().id as u64;
+    }
+    MOD_EVENTS_OK
+}
+
+/// Remove a listener registered with [`mod_events_subscribe`]
+///
+/// Does not wait for calls already in flight, which may still use the
+/// listener's `user_data`; see [`mod_events_subscribe_with_release`].
+///
+/// # Safety
+///
+/// `handle` must be a live dispatcher handle.
+#[no_mangle]
+pub unsafe extern "C" fn mod_events_unsubscribe(
+    handle: *mut ModEventsDispatcher,
+    id: u64,
+) -> c_int {
+    if handle.is_null() {
+        return MOD_EVENTS_ERR_NULL;
+    }
+    let dispatcher = &(*handle).dispatcher;
+    let listener_id = dispatcher.listener_id(id as usize, TypeId::of::<DynamicEvent>());
+    // Removing a listener drops it, which may run Rust removal callbacks
+    match catch_unwind(AssertUnwindSafe(|| dispatcher.unsubscribe(listener_id))) {
+        Ok(true) => MOD_EVENTS_OK,
+        Ok(false) => MOD_EVENTS_ERR_NOT_FOUND,
+        Err(_) => MOD_EVENTS_ERR_PANIC,
+    }
+}
+
+/// Emit a named event with a byte payload
+///
+/// The number of listeners that returned an error is written to
+/// `out_failed` (if non-null). Returns [`MOD_EVENTS_ERR_PANIC`] instead
+/// of unwinding into the caller if a Rust listener panics.
+///
+/// # Safety
+///
+/// `handle` must be a live dispatcher handle and `name` a valid
+/// NUL-terminated string. `payload` must point to `len` readable bytes,
+/// or may be null when `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn mod_events_emit(
+    handle: *mut ModEventsDispatcher,
+    name: *const c_char,
+    payload: *const u8,
+    len: usize,
+    out_failed: *mut usize,
+) -> c_int {
+    if handle.is_null() || name.is_null() || (payload.is_null() && len > 0) {
+        return MOD_EVENTS_ERR_NULL;
+    }
+    let name = match CStr::from_ptr(name).to_str() {
+        Ok(name) => name,
+        Err(_) => return MOD_EVENTS_ERR_UTF8,
+    };
+    let payload = if len == 0 {
+        Vec::new()
+    } else {
+        std::slice::from_raw_parts(payload, len).to_vec()
+    };
+
+    let dispatcher = &(*handle).dispatcher;
+    let event = DynamicEvent::new(name, payload);
+    let result = match catch_unwind(AssertUnwindSafe(|| dispatcher.dispatch(event))) {
+        Ok(result) => result,
+        Err(_) => return MOD_EVENTS_ERR_PANIC,
+    };
+
+    if !out_failed.is_null() {
+        *out_failed = result.error_count();
+    }
+    MOD_EVENTS_OK
+}
+
+/// Version of the [`ModEventsPluginApi`] layout
+///
+/// Bumped whenever the table changes incompatibly. Plugins must refuse
+/// tables with a version they were not built against.
+pub const MOD_EVENTS_PLUGIN_ABI_VERSION: u32 = 2;
+
+/// Stable function table handed from a host to dynamically loaded plugins
+///
+/// Plugins built as separate `dylib`/`cdylib`s cannot safely share Rust
+/// types, trait objects, or `TypeId`s with the host: each binary may be
+/// compiled with a different compiler or carry its own copy of this crate.
+/// Instead, the host passes this `#[repr(C)]` table to the plugin's entry
+/// point and all traffic goes through the host's function pointers, using
+/// event names rather than types to route.
+///
+/// Plugins should wrap the table in a [`PluginContext`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ModEventsPluginApi {
+    /// Layout version, always [`MOD_EVENTS_PLUGIN_ABI_VERSION`] for tables built here
+    pub abi_version: u32,
+    /// The host's dispatcher handle
+    pub handle: *mut ModEventsDispatcher,
+    /// See [`mod_events_subscribe`]
+    pub subscribe: unsafe extern "C" fn(
+        *mut ModEventsDispatcher,
+        *const c_char,
+        ModEventsCallback,
+        *mut c_void,
+        *mut u64,
+    ) -> c_int,
+    /// See [`mod_events_subscribe_with_release`]
+    pub subscribe_with_release: unsafe extern "C" fn(
+        *mut ModEventsDispatcher,
+        *const c_char,
+        ModEventsCallback,
+        *mut c_void,
+        Option<ModEventsRelease>,
+        *mut u64,
+    ) -> c_int,
+    /// See [`mod_events_unsubscribe`]
+    pub unsubscribe: unsafe extern "C" fn(*mut ModEventsDispatcher, u64) -> c_int,
+    /// See [`mod_events_emit`]
+    pub emit: unsafe extern "C" fn(
+        *mut ModEventsDispatcher,
+        *const c_char,
+        *const u8,
+        usize,
+        *mut usize,
+    ) -> c_int,
+}
+
+impl ModEventsPluginApi {
+    /// Build the function table for a host dispatcher handle
+    ///
+    /// The handle must outlive every plugin the table is given to.
+    pub fn new(handle: *mut ModEventsDispatcher) -> Self {
+        Self {
+            abi_version: MOD_EVENTS_PLUGIN_ABI_VERSION,
+            handle,
+            subscribe: mod_events_subscribe,
+            subscribe_with_release: mod_events_subscribe_with_release,
+            unsubscribe: mod_events_unsubscribe,
+            emit: mod_events_emit,
+        }
+    }
+}
+
+/// Signature of the entry point a plugin exports to receive the table
+pub type ModEventsPluginInit = unsafe extern "C" fn(api: *const ModEventsPluginApi) -> c_int;
+
+/// Errors raised by [`PluginContext`]
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    /// The table pointer was null
+    #[error("plugin api table is null")]
+    NullApi,
+    /// The host was built against a different table layout
+    #[error("plugin abi version mismatch: expected {expected}, found {found}")]
+    AbiMismatch {
+        /// Version this plugin was built against
+        expected: u32,
+        /// Version the host provided
+        found: u32,
+    },
+    /// The event name contained an interior NUL byte
+    #[error("event name contains a NUL byte")]
+    InvalidName,
+    /// The host returned a non-zero status code
+    #[error("host call failed with status {0}")]
+    Status(c_int),
+}
+
+type PluginCallback =
+    dyn Fn(&[u8]) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync;
+
+/// Plugin-side view of a host's [`ModEventsPluginApi`]
+///
+/// Lets Rust plugins subscribe closures and emit events through the host
+/// without touching raw pointers. Listeners registered through a context
+/// are unsubscribed when it is dropped, so a plugin should drop its
+/// context before being unloaded. The host keeps each closure until any
+/// dispatch still calling it returns, so no host dispatch may be running
+/// when the plugin's code is unloaded.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::ffi::{ModEventsDispatcher, ModEventsPluginApi, PluginContext};
+/// use mod_events::{DynamicEvent, EventDispatcher};
+/// use std::sync::Arc;
+///
+/// // Host side
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// let handle = Box::into_raw(ModEventsDispatcher::from_dispatcher(dispatcher.clone()));
+/// let api = ModEventsPluginApi::new(handle);
+///
+/// // Plugin side, usually inside the exported init function
+/// let plugin = unsafe { PluginContext::from_raw(&api) }.unwrap();
+/// plugin
+///     .subscribe("host.started", |payload: &[u8]| {
+///         println!("Host started with {} bytes of config", payload.len());
+///         Ok(())
+///     })
+///     .unwrap();
+///
+/// dispatcher.emit(DynamicEvent::new("host.started", b"{}".to_vec()));
+/// plugin.emit("plugin.ready", &[]).unwrap();
+///
+/// drop(plugin);
+/// unsafe { mod_events::ffi::mod_events_dispatcher_free(handle) };
+/// ```
+pub struct PluginContext {
+    api: ModEventsPluginApi,
+    subscriptions: std::sync::Mutex<std::collections::HashSet<u64>>,
+}
+
+impl fmt::Debug for PluginContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginContext")
+            .field("abi_version", &self.api.abi_version)
+            .field(
+                "subscriptions",
+                &self.subscriptions.lock().map(|ids| ids.len()).unwrap_or(0),
+            )
+            .finish()
+    }
+}
+
+// The host dispatcher behind the table is thread-safe.
+unsafe impl Send for PluginContext {}
+unsafe impl Sync for PluginContext {}
+
+impl PluginContext {
+    /// Wrap a table received from the host
+    ///
+    /// # Safety
+    ///
+    /// `api` must be null or point to a table whose function pointers and
+    /// handle stay valid for the lifetime of the returned context.
+    pub unsafe fn from_raw(api: *const ModEventsPluginApi) -> Result<Self, PluginError> {
+        if api.is_null() {
+            return Err(PluginError::NullApi);
+        }
+        let api = *api;
+        if api.abi_version != MOD_EVENTS_PLUGIN_ABI_VERSION {
+            return Err(PluginError::AbiMismatch {
+                expected: MOD_EVENTS_PLUGIN_ABI_VERSION,
+                found: api.abi_version,
+            });
+        }
+        Ok(Self {
+            api,
+            subscriptions: std::sync::Mutex::new(std::collections::HashSet::new()),
+        })
+    }
+
+    /// Subscribe a closure to host events with the given name
+    ///
+    /// The host owns the closure and drops it once the listener is
+    /// unsubscribed and no call is still running.
+    pub fn subscribe<F>(&self, name: &str, listener: F) -> Result<u64, PluginError>
+    where
+        F: Fn(&[u8]) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let name = std::ffi::CString::new(name).map_err(|_| PluginError::InvalidName)?;
+        let callback: Arc<Box<PluginCallback>> = Arc::new(Box::new(listener));
+        let user_data = Arc::into_raw(callback) as *mut c_void;
+
+        let mut id = 0;
+        let status = unsafe {
+            (self.api.subscribe_with_release)(
+                self.api.handle,
+                name.as_ptr(),
+                plugin_trampoline,
+                user_data,
+                Some(plugin_release),
+                &mut id,
+            )
+        };
+        if status != MOD_EVENTS_OK {
+            // The host does not release `user_data` when subscribing fails
+            plugin_release(user_data);
+            return Err(PluginError::Status(status));
+        }
+
+        self.subscriptions.lock().unwrap().insert(id);
+        Ok(id)
+    }
+
+    /// Remove a listener registered through this context
+    ///
+    /// Calls already running on the host finish before the closure is
+    /// dropped.
+    pub fn unsubscribe(&self, id: u64) -> bool {
+        let status = unsafe { (self.api.unsubscribe)(self.api.handle, id) };
+        self.subscriptions.lock().unwrap().remove(&id);
+        status == MOD_EVENTS_OK
+    }
+
+    /// Emit a named event on the host bus
+    ///
+    /// Returns the number of host listeners that failed.
+    pub fn emit(&self, name: &str, payload: &[u8]) -> Result<usize, PluginError> {
+        let name = std::ffi::CString::new(name).map_err(|_| PluginError::InvalidName)?;
+        let mut failed = 0;
+        let status = unsafe {
+            (self.api.emit)(
+                self.api.handle,
+                name.as_ptr(),
+                payload.as_ptr(),
+                payload.len(),
+                &mut failed,
+            )
+        };
+        if status == MOD_EVENTS_OK {
+            Ok(failed)
+        } else {
+            Err(PluginError::Status(status))
+        }
+    }
+}
+
+impl Drop for PluginContext {
+    fn drop(&mut self) {
+        let ids: Vec<u64> = match self.subscriptions.lock() {
+            Ok(ids) => ids.iter().copied().collect(),
+            Err(_) => return,
+        };
+        for id in ids {
+            self.unsubscribe(id);
+        }
+    }
+}
+
+extern "C" fn plugin_trampoline(
+    _name: *const c_char,
+    payload: *const u8,
+    len: usize,
+    user_data: *mut c_void,
+) -> c_int {
+    // SAFETY: `user_data` is the strong reference `PluginContext::subscribe`
+    // made with `Arc::into_raw`. The host only hands it to `plugin_release`
+    // once the listener is removed and every call, this one included, has
+    // returned, so the callback is alive for the whole call.
+    let callback = unsafe { &*(user_data as *const Box<PluginCallback>) };
+    let payload = if len == 0 {
+        &[][..]
+    } else {
+        // SAFETY: the host passes the payload of the event being
+        // dispatched, `len` readable bytes that outlive this call.
+        unsafe { std::slice::from_raw_parts(payload, len) }
+    };
+    // A panic must not unwind across the host's C frames
+    match catch_unwind(AssertUnwindSafe(|| callback(payload))) {
+        Ok(Ok(())) => 0,
+        Ok(Err(_)) => 1,
+        Err(_) => 2,
+    }
+}
+
+extern "C" fn plugin_release(user_data: *mut c_void) {
+    // SAFETY: `user_data` is the strong reference made by
+    // `PluginContext::subscribe`, released exactly once.
+    let callback = unsafe { Arc::from_raw(user_data as *const Box<PluginCallback>) };
+    // Dropping the closure must not unwind into the host either
+    let _ = catch_unwind(AssertUnwindSafe(|| drop(callback)));
+}
@@ -0,0 +1,241 @@
+//! Entry points for fuzz targets (requires "fuzzing" feature)
+//!
+//! Each function drives one layer of the crate with arbitrary bytes.
+//! Malformed input is rejected quietly; a panic means an invariant broke,
+//! which is what the fuzzer reports. A `cargo fuzz` target is one line:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| mod_events::fuzz::dispatch(data));
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use mod_events::fuzz;
+//!
+//! fuzz::dispatch(&[0, 1, 3, 1, 4, b'h', b'i', 0, 0]);
+//! fuzz::decode(br#"{"name":"fuzz.record","version":1,"payload":{"name":"a","count":2}}"#);
+//! fuzz::decode(b"\xff not json");
+//! ```
+
+use crate::codec::{EncryptedEvent, Encryptor, EventCodec, KeyRing, SerializedEvent};
+use crate::persist::LogEnvelope;
+use crate::{DynamicEvent, ErrorPolicy, Event, EventDispatcher, EventSchema, TypedListenerId};
+use serde::{Deserialize, Serialize};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Names events are routed by, kept few so operations collide often
+const NAMES: [&str; 4] = ["alpha", "beta", "gamma", "alpha.beta"];
+
+/// Cursor turning fuzzer bytes into choices
+///
+/// Reads past the end return zeros, so every input is a valid program.
+#[derive(Debug, Clone)]
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    /// Read choices from `data`
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Check whether every byte has been read
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Read one byte
+    pub fn byte(&mut self) -> u8 {
+        match self.data.split_first() {
+            Some((&byte, rest)) => {
+                self.data = rest;
+                byte
+            }
+            None => 0,
+        }
+    }
+
+    /// Read a run of bytes whose length is given by the next byte
+    pub fn bytes(&mut self) -> &'a [u8] {
+        let len = usize::from(self.byte()).min(self.data.len());
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        bytes
+    }
+
+    /// Pick one of `choices`
+    pub fn choose<'c, T>(&mut self, choices: &'c [T]) -> &'c T {
+        &choices[usize::from(self.byte()) % choices.len()]
+    }
+}
+
+/// Drive a dispatcher through subscriptions, middleware and dispatches
+///
+/// Every byte selects an operation on a fresh dispatcher, reading its
+/// arguments from the bytes that follow.
+pub fn dispatch(data: &[u8]) {
+    let mut input = ByteReader::new(data);
+    let dispatcher = EventDispatcher::new();
+    let mut subscribed: Vec<TypedListenerId<DynamicEvent>> = Vec::new();
+
+    while !input.is_empty() {
+        match input.byte() % 8 {
+            0 => {
+                let id = dispatcher.subscribe_named(*input.choose(&NAMES), |event| {
+                    if event.payload().first() == Some(&0xff) {
+                        return Err("payload rejected".into());
+                    }
+                    Ok(())
+                });
+                subscribed.push(id);
+            }
+            1 if !subscribed.is_empty() => {
+                let index = usize::from(input.byte()) % subscribed.len();
+                let id = subscribed.swap_remove(index);
+                assert!(dispatcher.unsubscribe(id), "subscribed listener not found");
+            }
+            2 => {
+                let blocked = *input.choose(&NAMES);
+                dispatcher.add_middleware(move |event: &dyn Event| {
+                    event
+                        .downcast_ref::<DynamicEvent>()
+                        .is_none_or(|event| event.name() != blocked)
+                });
+            }
+            3 => {
+                let event = dynamic_event(&mut input);
+                check(&dispatcher, &dispatcher.dispatch(event));
+            }
+            4 => {
+                let event = dynamic_event(&mut input);
+                let threads = usize::from(input.byte() % 4) + 1;
+                check(&dispatcher, &dispatcher.dispatch_parallel(event, threads));
+            }
+            5 => dispatcher.set_error_policy(
+                *input.choose(&[ErrorPolicy::Continue, ErrorPolicy::StopOnError]),
+            ),
+            6 => {
+                let event = dynamic_event(&mut input);
+                let namespace = dispatcher.namespace(*input.choose(&NAMES));
+                check(&dispatcher, &namespace.dispatch(event));
+            }
+            _ => dispatcher.set_deterministic(match input.byte() {
+                0 => None,
+                seed => Some(u64::from(seed)),
+            }),
+        }
+    }
+    assert_eq!(
+        dispatcher.listener_count::<DynamicEvent>(),
+        subscribed.len(),
+        "listener count drifted from subscriptions"
+    );
+}
+
+fn dynamic_event(input: &mut ByteReader<'_>) -> DynamicEvent {
+    let name = *input.choose(&NAMES);
+    DynamicEvent::new(name, input.bytes())
+}
+
+fn check(dispatcher: &EventDispatcher, result: &crate::DispatchResult) {
+    assert!(result.listener_count() <= dispatcher.listener_count::<DynamicEvent>());
+    if result.is_blocked() {
+        assert_eq!(
+            result.listener_count(),
+            0,
+            "blocked dispatch reached listeners"
+        );
+    }
+}
+
+/// Event type the codec entry points decode into
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FuzzRecord {
+    name: String,
+    count: u64,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    flag: Option<bool>,
+}
+
+impl Event for FuzzRecord {}
+
+impl EventSchema for FuzzRecord {
+    const NAME: &'static str = "fuzz.record";
+}
+
+/// Key that flips every bit, enough to exercise sealing
+struct InvertKey;
+
+impl Encryptor for InvertKey {
+    fn key_id(&self) -> &str {
+        "invert"
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, BoxError> {
+        Ok(plaintext.iter().map(|byte| !byte).collect())
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, BoxError> {
+        Ok(ciphertext.iter().map(|byte| !byte).collect())
+    }
+}
+
+/// Feed bytes to the wire formats: codec records, sealed records and log lines
+///
+/// Whatever decodes successfully must survive an encode/decode round trip
+/// unchanged, and sealing then opening it must give the record back.
+pub fn decode(data: &[u8]) {
+    let mut codec = EventCodec::new();
+    codec
+        .register::<FuzzRecord>()
+        .expect("fuzz schema registers");
+    codec.set_keys(KeyRing::new(InvertKey));
+
+    if let Ok(record) = serde_json::from_slice::<SerializedEvent>(data) {
+        round_trip(&codec, record);
+    }
+    if let Ok(record) = serde_json::from_slice::<EncryptedEvent>(data) {
+        if let Ok(record) = codec.open(record) {
+            round_trip(&codec, record);
+        }
+    }
+    if let Ok(envelope) = serde_json::from_slice::<LogEnvelope>(data) {
+        let _ = envelope.record.decode(&codec);
+    }
+    if let Ok(payload) = serde_json::from_slice(data) {
+        round_trip(
+            &codec,
+            SerializedEvent {
+                name: FuzzRecord::NAME.to_string(),
+                version: FuzzRecord::VERSION,
+                payload,
+            },
+        );
+    }
+}
+
+fn round_trip(codec: &EventCodec, record: SerializedEvent) {
+    let Ok(event) = codec.decode(record) else {
+        return;
+    };
+    let encoded = codec
+        .encode_dyn(event.as_ref())
+        .expect("decoded event encodes");
+    let sealed = codec.seal(encoded.clone()).expect("encoded event seals");
+    assert_eq!(codec.open(sealed).expect("sealed event opens"), encoded);
+    let decoded = codec
+        .decode(encoded.clone())
+        .expect("encoded event decodes");
+    assert_eq!(
+        codec
+            .encode_dyn(decoded.as_ref())
+            .expect("decoded event encodes"),
+        encoded,
+        "round trip changed the record"
+    );
+}
@@ -0,0 +1,21 @@
+//! Subscribing one handler to several event types
+
+use crate::{EventDispatcher, ListenerId, Priority};
+use std::sync::Arc;
+
+/// Handler shared by every member of an [`EventGroup`]
+pub type GroupHandler<G> =
+    Arc<dyn Fn(G) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync>;
+
+/// A set of event types delivered to one handler through an enum
+///
+/// Implemented by enums generated with [`event_group!`](crate::event_group);
+/// see [`EventDispatcher::on_each`].
+pub trait EventGroup: Sized + 'static {
+    /// Subscribe `handler` to every member type, returning one id per type
+    fn subscribe_group(
+        dispatcher: &EventDispatcher,
+        handler: GroupHandler<Self>,
+        priority: Priority,
+    ) -> Vec<ListenerId>;
+}
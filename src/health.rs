@@ -0,0 +1,133 @@
+//! Dispatcher health reporting
+
+use crate::sync::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Overall health classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthStatus {
+    /// Everything is operating normally
+    Healthy,
+    /// Listener failures exceed the degraded threshold
+    Degraded,
+    /// Listener failures exceed the unhealthy threshold
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// Check whether the status is `Healthy`
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, HealthStatus::Healthy)
+    }
+}
+
+/// Error-rate thresholds used to classify health
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthThresholds {
+    /// Error rate at or above which the dispatcher is `Degraded`
+    pub degraded_error_rate: f64,
+    /// Error rate at or above which the dispatcher is `Unhealthy`
+    pub unhealthy_error_rate: f64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            degraded_error_rate: 0.05,
+            unhealthy_error_rate: 0.5,
+        }
+    }
+}
+
+/// Structured health report returned by
+/// [`EventDispatcher::health`](crate::EventDispatcher::health)
+///
+/// Rates cover the interval since the previous health check (or since the
+/// dispatcher was created), so polling from a `/healthz` endpoint yields
+/// recent behavior rather than lifetime averages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// Overall classification
+    pub status: HealthStatus,
+    /// Length of the interval the rates cover
+    pub interval: Duration,
+    /// Events dispatched during the interval
+    pub dispatched: usize,
+    /// Events blocked by middleware during the interval
+    pub blocked: usize,
+    /// Listener invocations during the interval
+    pub invocations: usize,
+    /// Listener invocations that returned an error during the interval
+    pub errors: usize,
+    /// Fraction of listener invocations that failed, from 0.0 to 1.0
+    pub error_rate: f64,
+    /// Listeners currently subscribed across all event types
+    pub listener_count: usize,
+}
+
+/// Lifetime dispatch counters shared by health and statistics reporting
+#[derive(Debug, Default)]
+pub(crate) struct DispatchCounters {
+    pub(crate) dispatched: AtomicUsize,
+    pub(crate) blocked: AtomicUsize,
+    pub(crate) invocations: AtomicUsize,
+    pub(crate) errors: AtomicUsize,
+}
+
+/// Counter values captured at the previous health check
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CounterSnapshot {
+    pub(crate) at: Instant,
+    pub(crate) dispatched: usize,
+    pub(crate) blocked: usize,
+    pub(crate) invocations: usize,
+    pub(crate) errors: usize,
+}
+
+impl DispatchCounters {
+    pub(crate) fn snapshot(&self, at: Instant) -> CounterSnapshot {
+        CounterSnapshot {
+            at,
+            dispatched: self.dispatched.load(Ordering::Relaxed),
+            blocked: self.blocked.load(Ordering::Relaxed),
+            invocations: self.invocations.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl HealthReport {
+    pub(crate) fn between(
+        previous: &CounterSnapshot,
+        current: &CounterSnapshot,
+        listener_count: usize,
+        thresholds: &HealthThresholds,
+    ) -> Self {
+        let invocations = current.invocations - previous.invocations;
+        let errors = current.errors - previous.errors;
+        let error_rate = if invocations == 0 {
+            0.0
+        } else {
+            errors as f64 / invocations as f64
+        };
+
+        let status = if invocations > 0 && error_rate >= thresholds.unhealthy_error_rate {
+            HealthStatus::Unhealthy
+        } else if invocations > 0 && error_rate >= thresholds.degraded_error_rate {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+
+        Self {
+            status,
+            interval: current.at.saturating_duration_since(previous.at),
+            dispatched: current.dispatched - previous.dispatched,
+            blocked: current.blocked - previous.blocked,
+            invocations,
+            errors,
+            error_rate,
+            listener_count,
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Queryable history of dispatched events
+
+use crate::sync::Mutex;
+use crate::{Clock, DispatchObserver, DispatchResult, Event, SystemClock};
+use std::any::TypeId;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Bounded, queryable record of dispatched events
+///
+/// Register a clone with
+/// [`EventDispatcher::add_observer`](crate::EventDispatcher::add_observer)
+/// and every finished dispatch is recorded as a [`RecordedEvent`]. Use
+/// [`query`](Self::query) to page through the records of one event type,
+/// time range or correlation id instead of replaying everything. Once
+/// `capacity` records are held, the oldest are dropped. Records are
+/// timestamped on a [`Clock`]; create the history
+/// [`with_clock`](Self::with_clock) to share the dispatcher's.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, EventHistory, HistoryQuery};
+///
+/// #[derive(Debug, Clone)]
+/// struct RequestHandled {
+///     request_id: String,
+///     step: u32,
+/// }
+///
+/// impl Event for RequestHandled {
+///     fn correlation_id(&self) -> Option<&str> {
+///         Some(&self.request_id)
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// let history = EventHistory::new(1_000);
+/// dispatcher.add_observer(history.clone());
+///
+/// for step in 0..5 {
+///     for request in ["req-1", "req-2"] {
+///         dispatcher.emit(RequestHandled { request_id: request.into(), step });
+///     }
+/// }
+///
+/// // Page through one request's events, two at a time
+/// let query = HistoryQuery::new().correlation_id("req-2").limit(2);
+/// let first = history.query(&query);
+/// assert_eq!(first.len(), 2);
+///
+/// let next = history.query(&query.clone().after(first[1].sequence));
+/// assert_eq!(next.len(), 2);
+/// assert!(next[0].debug.contains("step: 2"));
+///
+/// let total = history.query(&HistoryQuery::new().of_type::<RequestHandled>()).len();
+/// assert_eq!(total, 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventHistory {
+    inner: Arc<HistoryState>,
+}
+
+struct HistoryState {
+    capacity: usize,
+    clock: Arc<dyn Clock>,
+    records: Mutex<HistoryRecords>,
+}
+
+impl fmt::Debug for HistoryState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistoryState")
+            .field("capacity", &self.capacity)
+            .field("records", &self.records)
+            .finish()
+    }
+}
+
+#[derive(Debug, Default)]
+struct HistoryRecords {
+    next_sequence: u64,
+    entries: VecDeque<RecordedEvent>,
+}
+
+/// One dispatch recorded by [`EventHistory`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedEvent {
+    /// Position in the history; increases by one per recorded dispatch
+    pub sequence: u64,
+    /// Type id of the event
+    pub type_id: TypeId,
+    /// Name of the event type
+    pub name: &'static str,
+    /// The event's correlation id, if it has one
+    pub correlation_id: Option<String>,
+    /// When the dispatch finished, on the history's clock
+    pub recorded_at: Instant,
+    /// How long the dispatch took
+    pub elapsed: Duration,
+    /// Number of listeners that ran
+    pub listener_count: usize,
+    /// Number of listeners that failed
+    pub error_count: usize,
+    /// Whether middleware blocked the event
+    pub blocked: bool,
+    /// The event rendered with `{:?}`
+    pub debug: String,
+}
+
+/// Filter and page selecting records from an [`EventHistory`]
+///
+/// Every filter is optional; an empty query selects every record.
+/// Records are returned oldest first. To fetch the next page, pass the
+/// last returned sequence to [`after`](Self::after).
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    type_id: Option<TypeId>,
+    correlation_id: Option<String>,
+    since: Option<Instant>,
+    until: Option<Instant>,
+    after: Option<u64>,
+    limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    /// Create a query selecting every record
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select only events of type `T`
+    pub fn of_type<T: Event>(mut self) -> Self {
+        self.type_id = Some(TypeId::of::<T>());
+        self
+    }
+
+    /// Select only events with this correlation id
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Select only events recorded at or after `time`
+    pub fn since(mut self, time: Instant) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Select only events recorded before `time`
+    pub fn until(mut self, time: Instant) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Start after the record with this sequence number
+    pub fn after(mut self, sequence: u64) -> Self {
+        self.after = Some(sequence);
+        self
+    }
+
+    /// Return at most `limit` records
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Check whether a record passes every filter
+    pub fn matches(&self, record: &RecordedEvent) -> bool {
+        self.type_id.is_none_or(|type_id| record.type_id == type_id)
+            && self
+                .correlation_id
+                .as_deref()
+                .is_none_or(|id| record.correlation_id.as_deref() == Some(id))
+            && self.since.is_none_or(|since| record.recorded_at >= since)
+            && self.until.is_none_or(|until| record.recorded_at < until)
+            && self.after.is_none_or(|after| record.sequence > after)
+    }
+}
+
+impl EventHistory {
+    /// Create a history holding at most `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        Self::with_clock(capacity, Arc::new(SystemClock))
+    }
+
+    /// Create a history that timestamps records with `clock`
+    ///
+    /// Pass the dispatcher's [`clock`](crate::EventDispatcher::clock) so
+    /// time-range queries follow a [`TestClock`](crate::TestClock).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, EventHistory, HistoryQuery, TestClock};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Heartbeat;
+    ///
+    /// impl Event for Heartbeat {}
+    ///
+    /// let clock = TestClock::new();
+    /// let dispatcher = EventDispatcher::with_clock(clock.clone());
+    /// let history = EventHistory::with_clock(100, dispatcher.clock().clone());
+    /// dispatcher.add_observer(history.clone());
+    ///
+    /// let start = dispatcher.clock().now();
+    /// for _ in 0..3 {
+    ///     dispatcher.emit(Heartbeat);
+    ///     clock.advance(Duration::from_secs(10));
+    /// }
+    ///
+    /// let window = HistoryQuery::new()
+    ///     .since(start + Duration::from_secs(10))
+    ///     .until(start + Duration::from_secs(30));
+    /// assert_eq!(history.query(&window).len(), 2);
+    /// ```
+    pub fn with_clock(capacity: usize, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            inner: Arc::new(HistoryState {
+                capacity,
+                clock,
+                records: Mutex::new(HistoryRecords::default()),
+            }),
+        }
+    }
+
+    /// Get the maximum number of records held
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Get the number of records held
+    pub fn len(&self) -> usize {
+        self.inner.records.lock().unwrap().entries.len()
+    }
+
+    /// Check whether no records are held
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discard every record; sequence numbers keep increasing
+    pub fn clear(&self) {
+        self.inner.records.lock().unwrap().entries.clear();
+    }
+
+    /// Get the records selected by `query`, oldest first
+    pub fn query(&self, query: &HistoryQuery) -> Vec<RecordedEvent> {
+        let records = self.inner.records.lock().unwrap();
+        // Sequences are contiguous, so a cursor maps straight to an index
+        let skip = match (query.after, records.entries.front()) {
+            (Some(after), Some(oldest)) => {
+                usize::try_from((after + 1).saturating_sub(oldest.sequence)).unwrap_or(usize::MAX)
+            }
+            _ => 0,
+        };
+        records
+            .entries
+            .iter()
+            .skip(skip)
+            .filter(|record| query.matches(record))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+impl DispatchObserver for EventHistory {
+    fn on_dispatch(&self, event: &dyn Event, result: &DispatchResult, elapsed: Duration) {
+        if self.inner.capacity == 0 {
+            return;
+        }
+        let mut record = RecordedEvent {
+            sequence: 0,
+            type_id: event.as_any().type_id(),
+            name: event.event_name(),
+            correlation_id: event.correlation_id().map(str::to_owned),
+            recorded_at: self.inner.clock.now(),
+            elapsed,
+            listener_count: result.listener_count(),
+            error_count: result.error_count(),
+            blocked: result.is_blocked(),
+            debug: format!("{event:?}"),
+        };
+        let mut records = self.inner.records.lock().unwrap();
+        record.sequence = records.next_sequence;
+        records.next_sequence += 1;
+        if records.entries.len() == self.inner.capacity {
+            records.entries.pop_front();
+        }
+        records.entries.push_back(record);
+    }
+}
@@ -0,0 +1,135 @@
+//! Hooks around individual listener calls
+
+use crate::{Event, ListenerError, ListenerId, Priority};
+use std::fmt;
+use std::time::Duration;
+
+/// The listener a [`ListenerHook`] is called for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListenerInfo {
+    /// Id of the listener
+    pub id: ListenerId,
+    /// Name of the listener
+    pub name: &'static str,
+    /// Priority the listener was subscribed with
+    pub priority: Priority,
+}
+
+/// Hook wrapped around each listener call
+///
+/// Unlike middleware, which sees each event once, a hook runs immediately
+/// before and after every listener invocation. Register one with
+/// [`EventDispatcher::add_listener_hook`](crate::EventDispatcher::add_listener_hook)
+/// to instrument listeners without touching their code, and remove it
+/// again with the returned [`HookId`]. Hooks run for sequential sync and
+/// async dispatch; the parallel dispatch modes do not call them.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, ListenerError, ListenerHook, ListenerInfo};
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone)]
+/// struct Saved;
+///
+/// impl Event for Saved {}
+///
+/// struct CallLog(Arc<Mutex<Vec<String>>>);
+///
+/// impl ListenerHook for CallLog {
+///     fn before(&self, _event: &dyn Event, listener: &ListenerInfo) {
+///         self.0.lock().unwrap().push(format!("enter {}", listener.id));
+///     }
+///
+///     fn after(
+///         &self,
+///         _event: &dyn Event,
+///         listener: &ListenerInfo,
+///         result: &Result<(), ListenerError>,
+///         _elapsed: Duration,
+///     ) {
+///         self.0.lock().unwrap().push(format!("exit {} ok={}", listener.id, result.is_ok()));
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// let id = dispatcher.on(|_: &Saved| {});
+///
+/// let log = Arc::new(Mutex::new(Vec::new()));
+/// let hook = dispatcher.add_listener_hook(CallLog(log.clone()));
+/// dispatcher.emit(Saved);
+/// assert!(dispatcher.remove_listener_hook(hook));
+/// dispatcher.emit(Saved);
+///
+/// assert_eq!(*log.lock().unwrap(), [format!("enter {id}"), format!("exit {id} ok=true")]);
+/// ```
+pub trait ListenerHook: Send + Sync {
+    /// Called just before the listener runs
+    fn before(&self, event: &dyn Event, listener: &ListenerInfo) {
+        let _ = (event, listener);
+    }
+
+    /// Called after the listener returns, with its outcome and duration
+    fn after(
+        &self,
+        event: &dyn Event,
+        listener: &ListenerInfo,
+        result: &Result<(), ListenerError>,
+        elapsed: Duration,
+    ) {
+        let _ = (event, listener, result, elapsed);
+    }
+}
+
+/// Handle for removing a [`ListenerHook`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookId(pub(crate) usize);
+
+impl fmt::Display for HookId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hook#{}", self.0)
+    }
+}
+
+/// A listener call that took longer than its watchdog threshold
+///
+/// Passed to the callback registered with
+/// [`EventDispatcher::on_slow_listener`](crate::EventDispatcher::on_slow_listener).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowListener {
+    /// The slow listener
+    pub listener: ListenerInfo,
+    /// Name of the event it was handling
+    pub event: &'static str,
+    /// How long the call took
+    pub elapsed: Duration,
+    /// The threshold it exceeded
+    pub threshold: Duration,
+}
+
+/// Hook reporting listener calls slower than a threshold
+pub(crate) struct SlowListenerHook {
+    pub(crate) threshold: Duration,
+    pub(crate) callback: Box<dyn Fn(&SlowListener) + Send + Sync>,
+}
+
+impl ListenerHook for SlowListenerHook {
+    fn after(
+        &self,
+        event: &dyn Event,
+        listener: &ListenerInfo,
+        _result: &Result<(), ListenerError>,
+        elapsed: Duration,
+    ) {
+        if elapsed > self.threshold {
+            (self.callback)(&SlowListener {
+                listener: *listener,
+                event: event.event_name(),
+                elapsed,
+                threshold: self.threshold,
+            });
+        }
+    }
+}
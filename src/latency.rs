@@ -0,0 +1,161 @@
+//! Latency histograms (requires "hdrhistogram" feature)
+
+use crate::sync::Mutex;
+use crate::{DispatchObserver, DispatchResult, Event, ListenerError, ListenerId};
+use hdrhistogram::Histogram;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Percentiles of a recorded latency distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Number of samples recorded
+    pub count: u64,
+    /// Median latency
+    pub p50: Duration,
+    /// 95th percentile latency
+    pub p95: Duration,
+    /// 99th percentile latency
+    pub p99: Duration,
+    /// 99.9th percentile latency
+    pub p999: Duration,
+    /// Highest latency recorded
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    fn of(histogram: &Histogram<u64>) -> Self {
+        let at = |quantile| Duration::from_nanos(histogram.value_at_quantile(quantile));
+        Self {
+            count: histogram.len(),
+            p50: at(0.5),
+            p95: at(0.95),
+            p99: at(0.99),
+            p999: at(0.999),
+            max: Duration::from_nanos(histogram.max()),
+        }
+    }
+}
+
+/// Observer recording dispatch and listener latencies in HDR histograms
+///
+/// Register a clone with
+/// [`EventDispatcher::add_observer`](crate::EventDispatcher::add_observer)
+/// and query percentiles per event type or per listener. Values are
+/// recorded in nanoseconds with three significant digits.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, LatencyRecorder};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone)]
+/// struct Query;
+///
+/// impl Event for Query {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// let latency = LatencyRecorder::new();
+/// dispatcher.add_observer(latency.clone());
+/// let slow = dispatcher.on(|_: &Query| std::thread::sleep(Duration::from_millis(2)));
+///
+/// for _ in 0..10 {
+///     dispatcher.emit(Query);
+/// }
+///
+/// let summary = latency.dispatch_latency::<Query>().unwrap();
+/// assert_eq!(summary.count, 10);
+/// assert!(summary.p50 >= Duration::from_millis(2));
+/// assert!(latency.listener_latency(slow).unwrap().p999 <= summary.max);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LatencyRecorder {
+    inner: Arc<Mutex<Histograms>>,
+}
+
+#[derive(Debug, Default)]
+struct Histograms {
+    dispatches: HashMap<TypeId, Histogram<u64>>,
+    listeners: HashMap<ListenerId, Histogram<u64>>,
+}
+
+impl LatencyRecorder {
+    /// Create an empty recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the dispatch latency distribution of events of type `T`
+    pub fn dispatch_latency<T: Event>(&self) -> Option<LatencySummary> {
+        let histograms = self.inner.lock().unwrap();
+        histograms
+            .dispatches
+            .get(&TypeId::of::<T>())
+            .map(LatencySummary::of)
+    }
+
+    /// Get the latency distribution of one listener
+    pub fn listener_latency(&self, listener: impl Into<ListenerId>) -> Option<LatencySummary> {
+        let histograms = self.inner.lock().unwrap();
+        histograms
+            .listeners
+            .get(&listener.into())
+            .map(LatencySummary::of)
+    }
+
+    /// Get the dispatch latency of `T` at `quantile`, from 0.0 to 1.0
+    pub fn dispatch_quantile<T: Event>(&self, quantile: f64) -> Option<Duration> {
+        let histograms = self.inner.lock().unwrap();
+        histograms
+            .dispatches
+            .get(&TypeId::of::<T>())
+            .map(|histogram| Duration::from_nanos(histogram.value_at_quantile(quantile)))
+    }
+
+    /// Discard every recorded sample
+    pub fn reset(&self) {
+        let mut histograms = self.inner.lock().unwrap();
+        histograms.dispatches.clear();
+        histograms.listeners.clear();
+    }
+}
+
+fn record(histogram: &mut Histogram<u64>, elapsed: Duration) {
+    let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    // Auto-resizing histograms only reject zero-width ranges, never values
+    let _ = histogram.record(nanos.max(1));
+}
+
+fn new_histogram() -> Histogram<u64> {
+    Histogram::new(3).expect("three significant digits is a valid precision")
+}
+
+impl DispatchObserver for LatencyRecorder {
+    fn on_dispatch(&self, event: &dyn Event, _result: &DispatchResult, elapsed: Duration) {
+        let mut histograms = self.inner.lock().unwrap();
+        let histogram = histograms
+            .dispatches
+            .entry(event.as_any().type_id())
+            .or_insert_with(new_histogram);
+        record(histogram, elapsed);
+    }
+
+    fn on_listener(
+        &self,
+        _event: &dyn Event,
+        listener: ListenerId,
+        _name: &'static str,
+        _result: &Result<(), ListenerError>,
+        elapsed: Duration,
+    ) {
+        let mut histograms = self.inner.lock().unwrap();
+        let histogram = histograms
+            .listeners
+            .entry(listener)
+            .or_insert_with(new_histogram);
+        record(histogram, elapsed);
+    }
+}
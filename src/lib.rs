@@ -23,11 +23,7 @@
 //!     email: String,
 //! }
 //!
-//! impl Event for UserRegistered {
-//!     fn as_any(&self) -> &dyn std::any::Any {
-//!         self
-//!     }
-//! }
+//! impl Event for UserRegistered {}
 //!
 //! // Create dispatcher and subscribe
 //! let dispatcher = EventDispatcher::new();
@@ -41,31 +37,151 @@
 //!     email: "alice@example.com".to_string(),
 //! });
 //! ```
+#[cfg(feature = "middleware")]
+mod authz;
+mod background;
+mod cancel;
+mod clock;
+mod coalesce;
+mod compose;
+mod config;
+mod context;
 mod core;
+mod decorator;
+mod describe;
 mod dispatcher;
+mod dynamic;
+mod group;
+mod health;
+mod history;
+mod hook;
 mod listener;
+mod macros;
+mod meta;
 mod metrics;
 mod middleware;
+mod namespace;
+mod observer;
+mod options;
+mod policy;
+mod pool;
 mod priority;
+mod response;
 mod result;
+mod schedule;
+mod schema;
+mod stats;
+mod subscription;
+mod sync;
+mod tenant;
+mod trace;
+#[cfg(feature = "middleware")]
+mod validation;
 
 #[cfg(feature = "async")]
 mod async_support;
 
+#[cfg(feature = "hdrhistogram")]
+mod latency;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "rhai")]
+pub mod scripting;
+
+#[cfg(feature = "test-utils")]
+pub mod testing;
+
+#[cfg(feature = "proptest")]
+pub mod property;
+
+#[cfg(feature = "serde")]
+pub mod codec;
+
+#[cfg(feature = "serde")]
+pub mod persist;
+
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+
+#[cfg(feature = "axum")]
+pub mod web;
+
+#[cfg(feature = "actix")]
+pub mod actor;
+
+#[cfg(feature = "bevy")]
+pub mod engine;
+
+#[cfg(feature = "tower")]
+pub mod service;
+
+#[cfg(feature = "log")]
+pub mod logging;
+
+#[cfg(feature = "statsd")]
+pub mod statsd;
+
+#[cfg(feature = "metrics-server")]
+pub mod metrics_server;
+
+#[cfg(feature = "middleware")]
+pub use authz::*;
+pub use background::*;
+pub use cancel::*;
+pub use clock::*;
+pub use coalesce::*;
+pub use compose::*;
+pub use config::*;
+pub use context::*;
 pub use core::*;
+pub use decorator::*;
+pub use describe::*;
 pub use dispatcher::*;
+pub use dynamic::*;
+pub use group::*;
+pub use health::*;
+pub use history::*;
+pub use hook::*;
 pub use listener::*;
+pub use macros::*;
+pub use meta::*;
 pub use metrics::*;
 pub use middleware::*;
+pub use namespace::*;
+pub use observer::*;
+pub use options::*;
+pub use policy::*;
+pub use pool::*;
 pub use priority::*;
+pub use response::*;
 pub use result::*;
+pub use schedule::*;
+pub use schema::*;
+pub use stats::*;
+pub use subscription::*;
+pub use tenant::*;
+pub use trace::*;
+#[cfg(feature = "middleware")]
+pub use validation::*;
 
 #[cfg(feature = "async")]
 pub use async_support::*;
 
+#[cfg(feature = "hdrhistogram")]
+pub use latency::*;
+
 /// Convenience re-exports
+///
+/// Covers the types typical code needs, so a single
+/// `use mod_events::prelude::*;` is enough to define events, subscribe,
+/// dispatch, and inspect results.
 pub mod prelude {
-    pub use crate::{Event, EventDispatcher, Priority};
+    pub use crate::{
+        DispatchResult, Event, EventDispatcher, EventListener, EventMetadata, ListenerId,
+        MiddlewareFunction, MiddlewareManager, Priority,
+    };
 
     #[cfg(feature = "async")]
     pub use crate::AsyncEventListener;
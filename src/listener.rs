@@ -1,6 +1,11 @@
 //! Event listener traits and implementations
 
-use crate::{Event, Priority};
+use crate::sync::{AtomicBool, AtomicUsize, Mutex, Ordering};
+use crate::{Event, ListenerId, Priority, RateLimiter, TenantId};
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::thread::ThreadId;
 
 /// Trait for synchronous event listeners
 ///
@@ -19,11 +24,7 @@ use crate::{Event, Priority};
 ///     email: String,
 /// }
 ///
-/// impl Event for UserRegistered {
-///     fn as_any(&self) -> &dyn std::any::Any {
-///         self
-///     }
-/// }
+/// impl Event for UserRegistered {}
 ///
 /// struct EmailNotifier;
 ///
@@ -39,6 +40,58 @@ use crate::{Event, Priority};
 ///     }
 /// }
 /// ```
+///
+/// # Lifecycle
+///
+/// Listeners subscribed with
+/// [`subscribe_listener`](crate::EventDispatcher::subscribe_listener) are
+/// told when they are registered, when they first see an event and when
+/// they are removed, so they can acquire resources lazily and release them
+/// deterministically:
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, EventListener, ListenerId};
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Debug, Clone)]
+/// struct Query;
+///
+/// impl Event for Query {}
+///
+/// struct Database(Arc<Mutex<Vec<&'static str>>>);
+///
+/// impl EventListener<Query> for Database {
+///     fn handle(&self, _event: &Query) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///         self.0.lock().unwrap().push("query");
+///         Ok(())
+///     }
+///
+///     fn on_subscribe(&self, _id: ListenerId) {
+///         self.0.lock().unwrap().push("subscribed");
+///     }
+///
+///     fn on_first_event(&self, _event: &Query) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///         self.0.lock().unwrap().push("connect");
+///         Ok(())
+///     }
+///
+///     fn on_unsubscribe(&self) {
+///         self.0.lock().unwrap().push("disconnect");
+///     }
+/// }
+///
+/// let calls = Arc::new(Mutex::new(Vec::new()));
+/// let dispatcher = EventDispatcher::new();
+/// let id = dispatcher.subscribe_listener(Database(calls.clone()));
+/// dispatcher.emit(Query);
+/// dispatcher.emit(Query);
+/// dispatcher.unsubscribe(id);
+///
+/// assert_eq!(
+///     *calls.lock().unwrap(),
+///     ["subscribed", "connect", "query", "query", "disconnect"]
+/// );
+/// ```
 pub trait EventListener<T: Event>: Send + Sync {
     /// Handle the event
     ///
@@ -53,16 +106,336 @@ pub trait EventListener<T: Event>: Send + Sync {
     fn priority(&self) -> Priority {
         Priority::Normal
     }
+
+    /// Called once the listener is registered, with its id
+    fn on_subscribe(&self, id: ListenerId) {
+        let _ = id;
+    }
+
+    /// Called before the first event is handled
+    ///
+    /// Returning an error fails that call without handling the event; the
+    /// hook runs again for the next event until it succeeds. Events of `T`
+    /// the hook dispatches itself reach [`handle`](Self::handle) without
+    /// running the hook again; other threads wait for it to finish.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, EventListener};
+    /// use std::sync::{Arc, Mutex, Weak};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Lookup(&'static str);
+    ///
+    /// impl Event for Lookup {}
+    ///
+    /// struct Cache {
+    ///     dispatcher: Weak<EventDispatcher>,
+    ///     seen: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    ///
+    /// impl EventListener<Lookup> for Cache {
+    ///     fn handle(&self, event: &Lookup) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///         self.seen.lock().unwrap().push(event.0);
+    ///         Ok(())
+    ///     }
+    ///
+    ///     // Warm up with a lookup of its own
+    ///     fn on_first_event(&self, _event: &Lookup) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///         if let Some(dispatcher) = self.dispatcher.upgrade() {
+    ///             dispatcher.emit(Lookup("warm-up"));
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// dispatcher.subscribe_listener(Cache {
+    ///     dispatcher: Arc::downgrade(&dispatcher),
+    ///     seen: seen.clone(),
+    /// });
+    ///
+    /// dispatcher.emit(Lookup("home"));
+    /// assert_eq!(*seen.lock().unwrap(), ["warm-up", "home"]);
+    /// ```
+    fn on_first_event(&self, event: &T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let _ = event;
+        Ok(())
+    }
+
+    /// Called once the listener has been removed
+    ///
+    /// Runs when the listener is unsubscribed, when the dispatcher is
+    /// cleared or when it is dropped, once no dispatch in progress is
+    /// still calling it.
+    fn on_unsubscribe(&self) {}
+}
+
+/// [`EventListener`] driven through its lifecycle callbacks
+///
+/// Owned by the listener's handler, so dropping the handler reports the
+/// removal.
+pub(crate) struct ManagedListener<T, L: EventListener<T>>
+where
+    T: Event,
+{
+    listener: L,
+    started: AtomicBool,
+    first: Mutex<()>,
+    /// Thread running `on_first_event`, so a dispatch it makes can skip the hook
+    warming: Mutex<Option<ThreadId>>,
+    _event: PhantomData<fn(&T)>,
+}
+
+impl<T: Event, L: EventListener<T>> ManagedListener<T, L> {
+    pub(crate) fn new(listener: L) -> Self {
+        Self {
+            listener,
+            started: AtomicBool::new(false),
+            first: Mutex::new(()),
+            warming: Mutex::new(None),
+            _event: PhantomData,
+        }
+    }
+
+    pub(crate) fn listener(&self) -> &L {
+        &self.listener
+    }
+
+    pub(crate) fn handle(&self, event: &T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !self.started.load(Ordering::Acquire) {
+            let current = std::thread::current().id();
+            // Re-entered from our own hook, which holds `first`
+            if *self.warming.lock().unwrap() == Some(current) {
+                return self.listener.handle(event);
+            }
+            let _first = self.first.lock().unwrap();
+            if !self.started.load(Ordering::Acquire) {
+                let _warming = Warming::enter(&self.warming, current);
+                self.listener.on_first_event(event)?;
+                self.started.store(true, Ordering::Release);
+            }
+        }
+        self.listener.handle(event)
+    }
+}
+
+/// Marks a thread as running `on_first_event` until dropped, so a
+/// panicking hook does not leave the mark behind
+struct Warming<'a>(&'a Mutex<Option<ThreadId>>);
+
+impl<'a> Warming<'a> {
+    fn enter(warming: &'a Mutex<Option<ThreadId>>, thread: ThreadId) -> Self {
+        *warming.lock().unwrap() = Some(thread);
+        Self(warming)
+    }
+}
+
+impl Drop for Warming<'_> {
+    fn drop(&mut self) {
+        *self.0.lock().unwrap() = None;
+    }
+}
+
+impl<T: Event, L: EventListener<T>> Drop for ManagedListener<T, L> {
+    fn drop(&mut self) {
+        self.listener.on_unsubscribe();
+    }
+}
+
+/// Shared [`EventListener`] trait object, forwarded to unchanged
+pub(crate) struct SharedListener<T: Event>(pub(crate) Arc<dyn EventListener<T>>);
+
+impl<T: Event> EventListener<T> for SharedListener<T> {
+    fn handle(&self, event: &T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.0.handle(event)
+    }
+
+    fn priority(&self) -> Priority {
+        self.0.priority()
+    }
+
+    fn on_subscribe(&self, id: ListenerId) {
+        self.0.on_subscribe(id);
+    }
+
+    fn on_first_event(&self, event: &T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.0.on_first_event(event)
+    }
+
+    fn on_unsubscribe(&self) {
+        self.0.on_unsubscribe();
+    }
+}
+
+/// Whether an event continues to lower-priority listeners
+///
+/// Return `Handled::Stop` from a listener closure to consume the event:
+/// the remaining lower-priority listeners are skipped and reported by
+/// [`DispatchResult::skipped`](crate::DispatchResult::skipped), and the
+/// consuming listener by [`DispatchResult::stopped_by`](crate::DispatchResult::stopped_by).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, Handled, Priority};
+///
+/// #[derive(Debug, Clone)]
+/// struct KeyPressed {
+///     key: char,
+/// }
+///
+/// impl Event for KeyPressed {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// let dialog = dispatcher.on_with_priority(
+///     |event: &KeyPressed| {
+///         if event.key == '\u{1b}' {
+///             Handled::Stop
+///         } else {
+///             Handled::Continue
+///         }
+///     },
+///     Priority::High,
+/// );
+/// let editor = dispatcher.on(|_: &KeyPressed| println!("typing"));
+///
+/// let result = dispatcher.dispatch(KeyPressed { key: '\u{1b}' });
+/// assert_eq!(result.stopped_by(), Some(dialog.id()));
+/// assert_eq!(result.skipped(), &[editor]);
+///
+/// let result = dispatcher.dispatch(KeyPressed { key: 'a' });
+/// assert_eq!(result.listener_count(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Handled {
+    /// Let lower-priority listeners see the event (default)
+    #[default]
+    Continue,
+    /// Consume the event; lower-priority listeners are skipped
+    Stop,
+    /// Decline the event
+    ///
+    /// In a first-match group (see
+    /// [`ListenerOptions::first_match`](crate::ListenerOptions::first_match))
+    /// the next member gets the event; elsewhere this is the same as
+    /// `Continue`.
+    Pass,
+}
+
+/// Return types accepted from listener closures
+///
+/// Implemented for `()`, [`Handled`], and for `Result<(), E>` and
+/// `Result<Handled, E>` where `E` converts into the boxed error, so [`EventDispatcher::on`](crate::EventDispatcher::on)
+/// accepts both infallible and fallible closures.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct Saved {
+///     bytes: usize,
+/// }
+///
+/// impl Event for Saved {}
+///
+/// let dispatcher = EventDispatcher::new();
+///
+/// dispatcher.on(|event: &Saved| println!("saved {} bytes", event.bytes));
+/// dispatcher.on(|event: &Saved| {
+///     if event.bytes == 0 {
+///         return Err("nothing was written");
+///     }
+///     Ok(())
+/// });
+///
+/// assert_eq!(dispatcher.dispatch(Saved { bytes: 0 }).error_count(), 1);
+/// ```
+pub trait IntoListenerResult {
+    /// Convert into the result recorded for the listener
+    fn into_listener_result(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Convert into the result and whether propagation continues
+    fn into_handled(self) -> Result<Handled, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        self.into_listener_result().map(|()| Handled::Continue)
+    }
+}
+
+impl IntoListenerResult for Handled {
+    fn into_listener_result(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+
+    fn into_handled(self) -> Result<Handled, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self)
+    }
+}
+
+impl<E> IntoListenerResult for Result<Handled, E>
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    fn into_listener_result(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.map(drop).map_err(Into::into)
+    }
+
+    fn into_handled(self) -> Result<Handled, Box<dyn std::error::Error + Send + Sync>> {
+        self.map_err(Into::into)
+    }
+}
+
+impl IntoListenerResult for () {
+    fn into_listener_result(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+impl<E> IntoListenerResult for Result<(), E>
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    fn into_listener_result(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.map_err(Into::into)
+    }
 }
 
 /// Internal listener wrapper for type erasure
 type ListenerHandler =
-    dyn Fn(&dyn Event) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync;
+    dyn Fn(&dyn Event) -> Result<Handled, Box<dyn std::error::Error + Send + Sync>> + Send + Sync;
 
 pub(crate) struct ListenerWrapper {
-    pub(crate) handler: Box<ListenerHandler>,
+    pub(crate) handler: Arc<ListenerHandler>,
     pub(crate) priority: Priority,
     pub(crate) id: usize,
+    pub(crate) name: &'static str,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    /// Ids of listeners that must finish first in the parallel dispatch modes
+    pub(crate) depends_on: Vec<usize>,
+    /// Ids of listeners that must wait for this one in the parallel dispatch modes
+    pub(crate) precedes: Vec<usize>,
+    /// First-match group the listener belongs to
+    pub(crate) group: Option<&'static str>,
+    /// Load-balanced group the listener belongs to
+    pub(crate) balanced: Option<&'static str>,
+    /// Calls currently running, tracked for load-balanced listeners only
+    pub(crate) in_flight: AtomicUsize,
+    /// Namespace the listener was registered in, if any
+    pub(crate) namespace: Option<Arc<str>>,
+    /// Tenant whose dispatches the listener is limited to, if any
+    pub(crate) tenant: Option<TenantId>,
+    /// Deliveries left before the listener is removed, if limited
+    pub(crate) deliveries_left: Option<AtomicUsize>,
+    /// Set once the handler returns [`ListenerClosed`]
+    pub(crate) closed: AtomicBool,
+    /// Name of the dynamic events the listener is limited to, if any
+    pub(crate) event_name: Option<String>,
 }
 
 impl std::fmt::Debug for ListenerWrapper {
@@ -70,27 +443,189 @@ impl std::fmt::Debug for ListenerWrapper {
         f.debug_struct("ListenerWrapper")
             .field("priority", &self.priority)
             .field("id", &self.id)
-            .field("handler", &"<function>")
+            .field("name", &self.name)
             .finish()
     }
 }
 
 impl ListenerWrapper {
-    pub(crate) fn new<T, F>(listener: F, priority: Priority, id: usize) -> Self
+    pub(crate) fn new<T, F, R>(
+        listener: F,
+        priority: Priority,
+        id: usize,
+        name: &'static str,
+    ) -> Self
     where
         T: Event + 'static,
-        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
     {
         Self {
-            handler: Box::new(move |event: &dyn Event| {
-                if let Some(concrete_event) = event.as_any().downcast_ref::<T>() {
-                    listener(concrete_event)
+            handler: Arc::new(move |event: &dyn Event| {
+                if let Some(concrete_event) = event.downcast_ref::<T>() {
+                    listener(concrete_event).into_handled()
                 } else {
-                    Ok(())
+                    Ok(Handled::Continue)
                 }
             }),
             priority,
             id,
+            name,
+            rate_limiter: None,
+            depends_on: Vec::new(),
+            precedes: Vec::new(),
+            group: None,
+            balanced: None,
+            in_flight: AtomicUsize::new(0),
+            namespace: None,
+            tenant: None,
+            deliveries_left: None,
+            closed: AtomicBool::new(false),
+            event_name: None,
+        }
+    }
+
+    /// Copy the listener under new ids, sharing its handler
+    ///
+    /// Used when moving listeners between dispatchers, where a dispatch
+    /// still running on the old one may hold the original.
+    pub(crate) fn renumbered(&self, renumber: &mut impl FnMut(usize) -> usize) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            priority: self.priority,
+            id: renumber(self.id),
+            name: self.name,
+            rate_limiter: self.rate_limiter.as_ref().map(RateLimiter::duplicate),
+            depends_on: self.depends_on.iter().map(|&id| renumber(id)).collect(),
+            precedes: self.precedes.iter().map(|&id| renumber(id)).collect(),
+            group: self.group,
+            balanced: self.balanced,
+            in_flight: AtomicUsize::new(0),
+            namespace: self.namespace.clone(),
+            tenant: self.tenant.clone(),
+            deliveries_left: self
+                .deliveries_left
+                .as_ref()
+                .map(|left| AtomicUsize::new(left.load(Ordering::Acquire))),
+            closed: AtomicBool::new(self.closed.load(Ordering::Acquire)),
+            event_name: self.event_name.clone(),
+        }
+    }
+
+    /// Check whether an event dispatched in `namespace` reaches this listener
+    ///
+    /// `exported` is whether the event type is exported from that namespace.
+    pub(crate) fn visible_in(&self, namespace: Option<&str>, exported: bool) -> bool {
+        match self.namespace.as_deref() {
+            Some(own) => namespace == Some(own),
+            None => namespace.is_none() || exported,
+        }
+    }
+
+    /// Check whether the listener has used up its deliveries or closed
+    ///
+    /// Spent listeners are skipped and removed by the dispatch that
+    /// spent them once it is done calling listeners.
+    pub(crate) fn is_spent(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+            || self
+                .deliveries_left
+                .as_ref()
+                .is_some_and(|left| left.load(Ordering::Acquire) == 0)
+    }
+
+    /// Take one of a delivery-limited listener's deliveries
+    ///
+    /// Returns `false` if none are left, in which case the listener must
+    /// not be invoked; concurrent dispatches cannot overrun the limit.
+    /// Listeners without a limit always succeed.
+    pub(crate) fn claim_delivery(&self) -> bool {
+        self.deliveries_left.as_ref().is_none_or(|left| {
+            left.fetch_update(Ordering::AcqRel, Ordering::Acquire, |left| {
+                left.checked_sub(1)
+            })
+            .is_ok()
+        })
+    }
+
+    /// Invoke the listener, tracking in-flight calls if it is load balanced
+    ///
+    /// Delivery-limited listeners must have claimed a delivery first.
+    /// With `catch`, a panic is returned as a [`ListenerPanicked`] error.
+    /// A handler returning [`ListenerClosed`] succeeds and marks the
+    /// listener spent.
+    pub(crate) fn call(
+        &self,
+        event: &dyn Event,
+        catch: bool,
+    ) -> Result<Handled, Box<dyn std::error::Error + Send + Sync>> {
+        let invoke = || {
+            if !catch {
+                return (self.handler)(event);
+            }
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| (self.handler)(event)))
+                .unwrap_or_else(|payload| Err(ListenerPanicked::from_payload(payload).into()))
+        };
+        let result = if self.balanced.is_none() {
+            invoke()
+        } else {
+            let _in_flight = InFlight::enter(&self.in_flight);
+            invoke()
+        };
+        match result {
+            Err(error) if error.is::<ListenerClosed>() => {
+                self.closed.store(true, Ordering::Release);
+                Ok(Handled::Continue)
+            }
+            result => result,
         }
     }
 }
+
+/// Counts a call as in flight until dropped, so a panicking handler
+/// still leaves the count balanced
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl<'a> InFlight<'a> {
+    fn enter(count: &'a AtomicUsize) -> Self {
+        count.fetch_add(1, Ordering::Relaxed);
+        Self(count)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Returned by a listener that should be removed, such as a channel
+/// forwarder whose receiver is gone
+///
+/// The call counts as handled and the listener is removed like a spent
+/// delivery-limited one.
+#[derive(Debug, thiserror::Error)]
+#[error("listener closed")]
+pub(crate) struct ListenerClosed;
+
+/// A caught listener panic, carried through the listener's error path
+///
+/// Turned into [`ListenerError::Panic`](crate::ListenerError::Panic) when
+/// the result is recorded.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub(crate) struct ListenerPanicked(pub(crate) String);
+
+impl ListenerPanicked {
+    /// Take the message out of a panic payload, if it was a string
+    pub(crate) fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => (*message).to_string(),
+                Err(_) => "Box<dyn Any>".to_string(),
+            },
+        };
+        Self(message)
+    }
+}
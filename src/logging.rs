@@ -0,0 +1,117 @@
+//! Dispatch logging through the `log` facade (requires "log" feature)
+
+use crate::{display_type_name, DispatchObserver, DispatchResult, Event};
+use log::Level;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Observer logging every dispatch with its outcome and duration
+///
+/// Successful dispatches are logged at the level configured for their
+/// event type. Dispatches with failing listeners or blocked by middleware
+/// are logged at `Warn` when that is more severe.
+///
+/// # Example
+///
+/// ```rust
+/// use log::Level;
+/// use mod_events::logging::LogObserver;
+/// use mod_events::{Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct MouseMoved;
+///
+/// impl Event for MouseMoved {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.add_observer(
+///     LogObserver::new()
+///         .level(Level::Info)
+///         .level_for::<MouseMoved>(Level::Trace),
+/// );
+/// dispatcher.emit(MouseMoved);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogObserver {
+    target: &'static str,
+    default_level: Level,
+    levels: HashMap<TypeId, Level>,
+}
+
+impl Default for LogObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogObserver {
+    /// Create an observer logging at `Debug` to the `mod_events` target
+    pub fn new() -> Self {
+        Self {
+            target: "mod_events",
+            default_level: Level::Debug,
+            levels: HashMap::new(),
+        }
+    }
+
+    /// Set the log target
+    pub fn target(mut self, target: &'static str) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Set the level for event types without their own level
+    pub fn level(mut self, level: Level) -> Self {
+        self.default_level = level;
+        self
+    }
+
+    /// Set the level for events of type `T`
+    pub fn level_for<T: Event>(mut self, level: Level) -> Self {
+        self.levels.insert(TypeId::of::<T>(), level);
+        self
+    }
+
+    fn level_of(&self, event: &dyn Event, result: &DispatchResult) -> Level {
+        let level = self
+            .levels
+            .get(&event.as_any().type_id())
+            .copied()
+            .unwrap_or(self.default_level);
+        if result.is_blocked() || result.has_errors() {
+            level.min(Level::Warn)
+        } else {
+            level
+        }
+    }
+}
+
+impl DispatchObserver for LogObserver {
+    fn on_dispatch(&self, event: &dyn Event, result: &DispatchResult, elapsed: Duration) {
+        let level = self.level_of(event, result);
+        if !log::log_enabled!(target: self.target, level) {
+            return;
+        }
+
+        let name = display_type_name(event.event_name());
+        if let Some(reason) = result.block_reason() {
+            log::log!(target: self.target, level, "{} {} after {:?}", name, reason, elapsed);
+            return;
+        }
+
+        log::log!(
+            target: self.target,
+            level,
+            "{} dispatched to {} listeners in {:?}: {} succeeded, {} failed",
+            name,
+            result.listener_count(),
+            elapsed,
+            result.success_count(),
+            result.error_count(),
+        );
+        for error in result.listener_errors() {
+            log::log!(target: self.target, level, "{}", error);
+        }
+    }
+}
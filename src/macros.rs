@@ -0,0 +1,192 @@
+//! Convenience macros for subscribing and emitting
+
+use crate::Event;
+
+/// Compile-time check that a type implements [`Event`]
+#[doc(hidden)]
+pub fn __assert_event<T: Event>() {}
+
+/// Compile-time check that a value's type implements [`Event`]
+#[doc(hidden)]
+pub fn __assert_event_value<T: Event>(_: &T) {}
+
+/// Subscribe a closure to an event type
+///
+/// Takes the dispatcher, the event type, an optional priority (`lowest`,
+/// `low`, `normal`, `high`, `highest`, or `critical`), and a closure
+/// returning `Result<(), Box<dyn Error + Send + Sync>>`. The event
+/// parameter's type is filled in from the event type, and the type is
+/// checked to implement [`Event`](crate::Event). Expands to
+/// [`subscribe_with_priority`](crate::EventDispatcher::subscribe_with_priority)
+/// and returns its [`ListenerId`](crate::ListenerId).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{subscribe, Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct UserRegistered {
+///     email: String,
+/// }
+///
+/// impl Event for UserRegistered {}
+///
+/// let dispatcher = EventDispatcher::new();
+///
+/// subscribe!(dispatcher, UserRegistered, high, |e| {
+///     println!("Welcome {}", e.email);
+///     Ok(())
+/// });
+/// subscribe!(dispatcher, UserRegistered, |_e| Ok(()));
+///
+/// assert_eq!(dispatcher.listener_count::<UserRegistered>(), 2);
+/// ```
+#[macro_export]
+macro_rules! subscribe {
+    ($dispatcher:expr, $event:ty, $priority:ident, move |$arg:pat_param| $body:expr) => {{
+        $crate::__assert_event::<$event>();
+        $dispatcher
+            .subscribe_with_priority(move |$arg: &$event| $body, $crate::__priority!($priority))
+    }};
+    ($dispatcher:expr, $event:ty, $priority:ident, |$arg:pat_param| $body:expr) => {{
+        $crate::__assert_event::<$event>();
+        $dispatcher.subscribe_with_priority(|$arg: &$event| $body, $crate::__priority!($priority))
+    }};
+    ($dispatcher:expr, $event:ty, move |$arg:pat_param| $body:expr) => {
+        $crate::subscribe!($dispatcher, $event, normal, move |$arg| $body)
+    };
+    ($dispatcher:expr, $event:ty, |$arg:pat_param| $body:expr) => {
+        $crate::subscribe!($dispatcher, $event, normal, |$arg| $body)
+    };
+}
+
+/// Emit an event, checking at compile time that it implements [`Event`]
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{emit, Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct UserRegistered {
+///     email: String,
+/// }
+///
+/// impl Event for UserRegistered {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// emit!(dispatcher, UserRegistered {
+///     email: "alice@example.com".to_string(),
+/// });
+/// ```
+#[macro_export]
+macro_rules! emit {
+    ($dispatcher:expr, $event:expr $(,)?) => {{
+        let event = $event;
+        $crate::__assert_event_value(&event);
+        $dispatcher.emit(event)
+    }};
+}
+
+/// Define an enum grouping several event types for one handler
+///
+/// Each variant wraps one event type, which must implement `Clone`. The
+/// generated enum implements [`EventGroup`](crate::EventGroup), so a single
+/// closure can be registered for every member with
+/// [`EventDispatcher::on_each`](crate::EventDispatcher::on_each).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{event_group, Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct UserRegistered(u64);
+/// impl Event for UserRegistered {}
+///
+/// #[derive(Debug, Clone)]
+/// struct UserDeleted(u64);
+/// impl Event for UserDeleted {}
+///
+/// event_group! {
+///     /// Everything that changes a user's lifecycle
+///     pub enum UserLifecycle {
+///         Registered(UserRegistered),
+///         Deleted(UserDeleted),
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// let ids = dispatcher.on_each(|event: UserLifecycle| match event {
+///     UserLifecycle::Registered(e) => println!("user {} registered", e.0),
+///     UserLifecycle::Deleted(e) => println!("user {} deleted", e.0),
+/// });
+///
+/// assert_eq!(ids.len(), 2);
+/// dispatcher.emit(UserDeleted(7));
+/// ```
+#[macro_export]
+macro_rules! event_group {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident($event:ty)),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis enum $name {
+            $(
+                #[allow(missing_docs)]
+                $variant($event),
+            )+
+        }
+
+        impl $crate::EventGroup for $name {
+            fn subscribe_group(
+                dispatcher: &$crate::EventDispatcher,
+                handler: $crate::GroupHandler<Self>,
+                priority: $crate::Priority,
+            ) -> ::std::vec::Vec<$crate::ListenerId> {
+                ::std::vec![$({
+                    let handler = handler.clone();
+                    $crate::ListenerId::from(dispatcher.subscribe_with_priority(
+                        move |event: &$event| handler($name::$variant(::std::clone::Clone::clone(event))),
+                        priority,
+                    ))
+                }),+]
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __priority {
+    (lowest) => {
+        $crate::Priority::Lowest
+    };
+    (low) => {
+        $crate::Priority::Low
+    };
+    (normal) => {
+        $crate::Priority::Normal
+    };
+    (high) => {
+        $crate::Priority::High
+    };
+    (highest) => {
+        $crate::Priority::Highest
+    };
+    (critical) => {
+        $crate::Priority::Critical
+    };
+    ($other:ident) => {
+        compile_error!(concat!(
+            "unknown priority `",
+            stringify!($other),
+            "`; expected lowest, low, normal, high, highest, or critical"
+        ))
+    };
+}
@@ -0,0 +1,93 @@
+//! Events the dispatcher emits about itself
+
+use crate::{BlockReason, Event, ListenerId};
+use std::any::TypeId;
+
+/// A listener was subscribed
+///
+/// Emitted when [meta-events](crate::EventDispatcher::set_meta_events)
+/// are enabled, for sync and async listeners and responders alike.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListenerAdded {
+    /// Id of the new listener
+    pub listener: ListenerId,
+    /// Name of the event type it listens to
+    pub event: &'static str,
+    /// Name of the listener
+    pub name: &'static str,
+}
+
+impl Event for ListenerAdded {}
+
+/// Middleware blocked an event
+///
+/// Emitted when [meta-events](crate::EventDispatcher::set_meta_events)
+/// are enabled. Blocked meta-events are not reported, so middleware that
+/// blocks this event cannot cause a feedback loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventBlocked {
+    /// Name of the blocked event type
+    pub event: &'static str,
+    /// Why it was blocked
+    pub reason: BlockReason,
+}
+
+impl Event for EventBlocked {}
+
+/// A full background queue discarded a dispatch
+///
+/// Emitted when [meta-events](crate::EventDispatcher::set_meta_events)
+/// are enabled; see
+/// [`set_queue_capacity`](crate::EventDispatcher::set_queue_capacity).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventDropped {
+    /// Name of the discarded event type
+    pub event: &'static str,
+    /// Number of dispatches of the type discarded so far
+    pub dropped: usize,
+}
+
+impl Event for EventDropped {}
+
+/// A capped background queue became full
+///
+/// Emitted when [meta-events](crate::EventDispatcher::set_meta_events)
+/// are enabled, each time the queue of an event type fills up to its
+/// [capacity](crate::EventDispatcher::set_queue_capacity).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueSaturated {
+    /// Name of the event type whose queue is full
+    pub event: &'static str,
+    /// The queue's capacity
+    pub capacity: usize,
+}
+
+impl Event for QueueSaturated {}
+
+/// A synchronous dispatch left out async listeners
+///
+/// Emitted when [meta-events](crate::EventDispatcher::set_meta_events)
+/// are enabled and the dispatcher's async bridge is
+/// [`AsyncBridge::Warn`](crate::AsyncBridge::Warn) (requires "async"
+/// feature).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsyncListenersSkipped {
+    /// Name of the dispatched event type
+    pub event: &'static str,
+    /// Number of async listeners left out
+    pub skipped: usize,
+}
+
+impl Event for AsyncListenersSkipped {}
+
+/// Check whether a type is one of the dispatcher's meta-events
+pub(crate) fn is_meta_event(type_id: TypeId) -> bool {
+    [
+        TypeId::of::<ListenerAdded>(),
+        TypeId::of::<EventBlocked>(),
+        TypeId::of::<EventDropped>(),
+        TypeId::of::<QueueSaturated>(),
+        TypeId::of::<AsyncListenersSkipped>(),
+    ]
+    .contains(&type_id)
+}
@@ -1,8 +1,11 @@
 //! Event dispatch metrics and monitoring
 
-use crate::Event;
+use crate::sync::Mutex;
+use crate::{Clock, DispatchObserver, DispatchResult, Event, ListenerError, ListenerId};
 use std::any::TypeId;
-use std::time::Instant;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Event metadata for debugging and monitoring
 ///
@@ -19,30 +22,267 @@ pub struct EventMetadata {
     pub dispatch_count: usize,
     /// Number of listeners currently subscribed to this event
     pub listener_count: usize,
+    /// Total number of listener errors raised while handling this event
+    pub error_count: usize,
+    /// Total number of invocations dropped by listener rate limits
+    pub rate_limited_count: usize,
+    /// Number of dispatches that waited in the background queue
+    pub queued_count: usize,
+    /// Total time dispatches spent waiting in the background queue
+    pub total_queue_wait: Duration,
+    /// Longest time a dispatch spent waiting in the background queue
+    pub max_queue_wait: Duration,
 }
 
 impl EventMetadata {
-    pub(crate) fn new<T: Event>() -> Self {
+    pub(crate) fn new(event_name: &'static str, type_id: TypeId, now: Instant) -> Self {
         Self {
-            event_name: std::any::type_name::<T>(),
-            type_id: TypeId::of::<T>(),
-            last_dispatch: Instant::now(),
+            event_name,
+            type_id,
+            last_dispatch: now,
             dispatch_count: 0,
             listener_count: 0,
+            error_count: 0,
+            rate_limited_count: 0,
+            queued_count: 0,
+            total_queue_wait: Duration::ZERO,
+            max_queue_wait: Duration::ZERO,
         }
     }
 
-    pub(crate) fn increment_dispatch(&mut self) {
+    pub(crate) fn increment_dispatch(&mut self, now: Instant) {
         self.dispatch_count += 1;
-        self.last_dispatch = Instant::now();
+        self.last_dispatch = now;
     }
 
     pub(crate) fn update_listener_count(&mut self, count: usize) {
         self.listener_count = count;
     }
 
+    pub(crate) fn add_errors(&mut self, count: usize) {
+        self.error_count += count;
+    }
+
+    pub(crate) fn add_rate_limited(&mut self, count: usize) {
+        self.rate_limited_count += count;
+    }
+
+    pub(crate) fn add_queue_wait(&mut self, waited: Duration) {
+        self.queued_count += 1;
+        self.total_queue_wait += waited;
+        self.max_queue_wait = self.max_queue_wait.max(waited);
+    }
+
+    /// Get the mean time dispatches spent waiting in the background queue
+    pub fn average_queue_wait(&self) -> Duration {
+        match u32::try_from(self.queued_count) {
+            Ok(0) => Duration::ZERO,
+            Ok(count) => self.total_queue_wait / count,
+            Err(_) => self.total_queue_wait.div_f64(self.queued_count as f64),
+        }
+    }
+
+    /// Get the event type name without module paths
+    ///
+    /// Generic instantiations keep their parameters, e.g.
+    /// `ValueChanged<String>` rather than
+    /// `my_app::events::ValueChanged<alloc::string::String>`.
+    pub fn display_name(&self) -> String {
+        display_type_name(self.event_name)
+    }
+
+    /// Get the event type name without module paths or generic parameters
+    ///
+    /// Every instantiation of a generic event shares the same base name,
+    /// e.g. `ValueChanged` for both `ValueChanged<u32>` and
+    /// `ValueChanged<String>`.
+    pub fn base_name(&self) -> &'static str {
+        let path = match self.event_name.find('<') {
+            Some(end) => &self.event_name[..end],
+            None => self.event_name,
+        };
+        path.rsplit("::").next().unwrap_or(path)
+    }
+
     /// Get the time since the last dispatch
+    ///
+    /// Measured against the system clock. Dispatchers using a custom
+    /// [`Clock`](crate::Clock) should use [`time_since`](Self::time_since).
     pub fn time_since_last_dispatch(&self) -> std::time::Duration {
         self.last_dispatch.elapsed()
     }
+
+    /// Get the time elapsed between the last dispatch and `now`
+    pub fn time_since(&self, now: Instant) -> std::time::Duration {
+        now.saturating_duration_since(self.last_dispatch)
+    }
+}
+
+/// Strip module paths from a type name
+///
+/// Turns names produced by [`std::any::type_name`] into a readable form
+/// that does not change when types move between modules. Generic
+/// parameters are kept and stripped the same way.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::display_type_name;
+///
+/// assert_eq!(
+///     display_type_name("app::events::ValueChanged<alloc::vec::Vec<core::option::Option<u8>>>"),
+///     "ValueChanged<Vec<Option<u8>>>"
+/// );
+/// assert_eq!(display_type_name(std::any::type_name::<(u8, String)>()), "(u8, String)");
+/// ```
+pub fn display_type_name(type_name: &str) -> String {
+    let mut out = String::with_capacity(type_name.len());
+    let mut segment_start = 0;
+    let mut chars = type_name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                out.truncate(segment_start);
+            }
+            c if c.is_alphanumeric() || c == '_' => out.push(c),
+            c => {
+                out.push(c);
+                segment_start = out.len();
+            }
+        }
+    }
+
+    out
+}
+
+/// Estimated heap usage of a dispatcher
+///
+/// Returned by [`EventDispatcher::memory_footprint`](crate::EventDispatcher::memory_footprint).
+/// Sizes are estimates in bytes: they count table capacity and the
+/// closures stored in them, but not heap data owned by captured values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// Bytes used by the synchronous listener table
+    pub listeners: usize,
+    /// Bytes used by the async listener table
+    pub async_listeners: usize,
+    /// Bytes used by the middleware chain
+    pub middleware: usize,
+    /// Bytes used by per-event metrics
+    pub metrics: usize,
+    /// Bytes used by background dispatches waiting for a worker and the
+    /// per-type queues tracking them
+    pub queues: usize,
+}
+
+impl MemoryFootprint {
+    /// Get the total estimated bytes
+    pub fn total(&self) -> usize {
+        self.listeners + self.async_listeners + self.middleware + self.metrics + self.queues
+    }
+}
+
+/// Most samples a latency window keeps per event type or listener
+const MAX_WINDOW_SAMPLES: usize = 8192;
+
+/// Sliding window of recent dispatch and listener durations
+///
+/// Installed by [`EventDispatcher::set_latency_window`](crate::EventDispatcher::set_latency_window)
+/// as an observer. Clones share the same samples.
+#[derive(Clone)]
+pub(crate) struct LatencyWindow {
+    inner: Arc<Mutex<WindowState>>,
+    clock: Arc<dyn Clock>,
+}
+
+struct WindowState {
+    window: Duration,
+    series: HashMap<LatencySeries, VecDeque<(Instant, Duration)>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum LatencySeries {
+    Dispatch(TypeId),
+    Listener(ListenerId),
+}
+
+impl LatencyWindow {
+    pub(crate) fn new(window: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(WindowState {
+                window,
+                series: HashMap::new(),
+            })),
+            clock,
+        }
+    }
+
+    pub(crate) fn window(&self) -> Duration {
+        self.inner.lock().unwrap().window
+    }
+
+    pub(crate) fn set_window(&self, window: Duration) {
+        self.inner.lock().unwrap().window = window;
+    }
+
+    fn record(&self, series: LatencySeries, elapsed: Duration) {
+        let now = self.clock.now();
+        let mut state = self.inner.lock().unwrap();
+        let window = state.window;
+        let samples = state.series.entry(series).or_default();
+        evict(samples, now, window);
+        if samples.len() == MAX_WINDOW_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back((now, elapsed));
+    }
+
+    /// Get the `p`th percentile (0 to 100) of the samples still in the window
+    ///
+    /// Uses the nearest-rank method, so the result is always a recorded
+    /// duration. Returns `None` if the window holds no samples.
+    pub(crate) fn percentile(&self, series: LatencySeries, p: f64) -> Option<Duration> {
+        let now = self.clock.now();
+        let mut state = self.inner.lock().unwrap();
+        let window = state.window;
+        let samples = state.series.get_mut(&series)?;
+        evict(samples, now, window);
+        if samples.is_empty() {
+            return None;
+        }
+        let mut durations: Vec<Duration> = samples.iter().map(|&(_, elapsed)| elapsed).collect();
+        durations.sort_unstable();
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * durations.len() as f64).ceil() as usize;
+        Some(durations[rank.saturating_sub(1)])
+    }
+}
+
+fn evict(samples: &mut VecDeque<(Instant, Duration)>, now: Instant, window: Duration) {
+    while samples
+        .front()
+        .is_some_and(|&(at, _)| now.saturating_duration_since(at) > window)
+    {
+        samples.pop_front();
+    }
+}
+
+impl DispatchObserver for LatencyWindow {
+    fn on_dispatch(&self, event: &dyn Event, result: &DispatchResult, elapsed: Duration) {
+        if !result.is_blocked() {
+            self.record(LatencySeries::Dispatch(event.as_any().type_id()), elapsed);
+        }
+    }
+
+    fn on_listener(
+        &self,
+        _event: &dyn Event,
+        listener: ListenerId,
+        _name: &'static str,
+        _result: &Result<(), ListenerError>,
+        elapsed: Duration,
+    ) {
+        self.record(LatencySeries::Listener(listener), elapsed);
+    }
 }
@@ -0,0 +1,234 @@
+//! HTTP endpoint serving dispatcher metrics (requires "metrics-server" feature)
+//!
+//! A minimal blocking HTTP server on its own thread, answering
+//!
+//! - `GET /metrics` with the Prometheus text format
+//! - `GET /metrics.json` with a JSON array, one object per event type
+//!
+//! It handles one connection at a time, which is plenty for a scraper.
+
+use crate::trace::push_json_string;
+use crate::{EventDispatcher, EventMetadata};
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Running metrics endpoint
+///
+/// Dropping the server stops it.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::metrics_server::MetricsServer;
+/// use mod_events::{Event, EventDispatcher};
+/// use std::io::{Read, Write};
+/// use std::net::TcpStream;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone)]
+/// struct Login;
+///
+/// impl Event for Login {}
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// let server = MetricsServer::bind("127.0.0.1:0", dispatcher.clone()).unwrap();
+/// dispatcher.emit(Login);
+///
+/// let mut client = TcpStream::connect(server.local_addr()).unwrap();
+/// client.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+/// let mut response = String::new();
+/// client.read_to_string(&mut response).unwrap();
+///
+/// assert!(response.starts_with("HTTP/1.1 200 OK"));
+/// assert!(response.contains("mod_events_dispatch_total{event=\"Login\"} 1"));
+/// ```
+#[derive(Debug)]
+pub struct MetricsServer {
+    address: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Serve `dispatcher`'s metrics on `address`
+    pub fn bind(address: impl ToSocketAddrs, dispatcher: Arc<EventDispatcher>) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let stop = shutdown.clone();
+        let thread = std::thread::Builder::new()
+            .name("mod-events-metrics".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Acquire) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let _ = respond(stream, &dispatcher);
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            address,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Get the address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Release);
+        // Wake the accept loop so it sees the shutdown flag
+        let _ = TcpStream::connect(self.address);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn respond(mut stream: TcpStream, dispatcher: &EventDispatcher) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < 8192 {
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            render_prometheus(dispatcher),
+        ),
+        (Some("GET"), Some("/metrics.json")) => {
+            ("200 OK", "application/json", render_json(dispatcher))
+        }
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn sorted_metrics(dispatcher: &EventDispatcher) -> Vec<(String, EventMetadata)> {
+    let mut metrics: Vec<_> = dispatcher
+        .metrics()
+        .into_values()
+        .map(|meta| (meta.display_name(), meta))
+        .collect();
+    metrics.sort_by(|a, b| a.0.cmp(&b.0));
+    metrics
+}
+
+/// Prometheus metric name, type, help text and value
+type Family = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&EventMetadata) -> usize,
+);
+
+const FAMILIES: [Family; 4] = [
+    (
+        "mod_events_dispatch_total",
+        "counter",
+        "Events dispatched",
+        |meta| meta.dispatch_count,
+    ),
+    (
+        "mod_events_listeners",
+        "gauge",
+        "Listeners currently subscribed",
+        |meta| meta.listener_count,
+    ),
+    (
+        "mod_events_listener_errors_total",
+        "counter",
+        "Listener invocations that returned an error",
+        |meta| meta.error_count,
+    ),
+    (
+        "mod_events_rate_limited_total",
+        "counter",
+        "Listener invocations dropped by rate limits",
+        |meta| meta.rate_limited_count,
+    ),
+];
+
+/// Render the dispatcher's metrics in the Prometheus text format
+pub fn render_prometheus(dispatcher: &EventDispatcher) -> String {
+    let metrics = sorted_metrics(dispatcher);
+
+    let mut text = String::new();
+    for (name, kind, help, value) in FAMILIES {
+        let _ = writeln!(text, "# HELP {} {}", name, help);
+        let _ = writeln!(text, "# TYPE {} {}", name, kind);
+        for (event, meta) in &metrics {
+            let _ = writeln!(
+                text,
+                "{}{{event=\"{}\"}} {}",
+                name,
+                escape_label(event),
+                value(meta)
+            );
+        }
+    }
+    text
+}
+
+/// Render the dispatcher's metrics as a JSON array
+pub fn render_json(dispatcher: &EventDispatcher) -> String {
+    let mut json = String::from("[");
+    for (index, (event, meta)) in sorted_metrics(dispatcher).iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str("{\"event\":");
+        push_json_string(&mut json, event);
+        let _ = write!(
+            json,
+            ",\"dispatch_count\":{},\"listener_count\":{},\"error_count\":{},\"rate_limited_count\":{},\"seconds_since_last_dispatch\":{:.3}}}",
+            meta.dispatch_count,
+            meta.listener_count,
+            meta.error_count,
+            meta.rate_limited_count,
+            meta.time_since_last_dispatch().as_secs_f64(),
+        );
+    }
+    json.push(']');
+    json
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,6 +1,6 @@
 //! Middleware system for event processing
 
-use crate::Event;
+use crate::{DispatchContext, Event};
 
 /// Middleware function type
 ///
@@ -8,12 +8,58 @@ use crate::Event;
 /// the event to continue processing, or `false` to block it.
 pub type MiddlewareFunction = Box<dyn Fn(&dyn Event) -> bool + Send + Sync>;
 
+/// Middleware that can inspect the dispatch context and explain blocks
+///
+/// Return `Err(reason)` to block the event; the reason is reported
+/// through [`DispatchResult::block_reason`](crate::DispatchResult::block_reason).
+pub trait Middleware: Send + Sync {
+    /// Check an event before it reaches listeners
+    fn process(&self, event: &dyn Event, context: &DispatchContext) -> Result<(), String>;
+
+    /// Name reported when this middleware blocks an event
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Why middleware blocked an event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockReason {
+    /// Name of the middleware that blocked the event
+    pub middleware: &'static str,
+    /// Explanation given by the middleware
+    pub reason: String,
+}
+
+impl std::fmt::Display for BlockReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "blocked by {}: {}", self.middleware, self.reason)
+    }
+}
+
+/// Adapts a [`MiddlewareFunction`] to the [`Middleware`] trait
+struct FunctionMiddleware(MiddlewareFunction);
+
+impl Middleware for FunctionMiddleware {
+    fn process(&self, event: &dyn Event, _context: &DispatchContext) -> Result<(), String> {
+        if (self.0)(event) {
+            Ok(())
+        } else {
+            Err("middleware returned false".to_string())
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "middleware function"
+    }
+}
+
 /// Middleware manager for event processing
 ///
 /// Middleware allows you to intercept events before they reach listeners.
 /// This is useful for logging, filtering, or transforming events.
 pub struct MiddlewareManager {
-    middleware: Vec<MiddlewareFunction>,
+    middleware: Vec<Box<dyn Middleware>>,
 }
 
 impl std::fmt::Debug for MiddlewareManager {
@@ -46,6 +92,12 @@ impl MiddlewareManager {
     where
         F: Fn(&dyn Event) -> bool + Send + Sync + 'static,
     {
+        self.middleware
+            .push(Box::new(FunctionMiddleware(Box::new(middleware))));
+    }
+
+    /// Add a [`Middleware`] implementation to the chain
+    pub fn add_layer<M: Middleware + 'static>(&mut self, middleware: M) {
         self.middleware.push(Box::new(middleware));
     }
 
@@ -53,7 +105,27 @@ impl MiddlewareManager {
     ///
     /// Returns `true` if the event should continue, `false` if blocked.
     pub fn process(&self, event: &dyn Event) -> bool {
-        self.middleware.iter().all(|m| m(event))
+        self.process_with_context(event, &DispatchContext::default())
+            .is_ok()
+    }
+
+    /// Process an event through all middleware with a dispatch context
+    ///
+    /// Stops at the first middleware that blocks and returns its reason.
+    pub fn process_with_context(
+        &self,
+        event: &dyn Event,
+        context: &DispatchContext,
+    ) -> Result<(), BlockReason> {
+        for middleware in &self.middleware {
+            middleware
+                .process(event, context)
+                .map_err(|reason| BlockReason {
+                    middleware: middleware.name(),
+                    reason,
+                })?;
+        }
+        Ok(())
     }
 
     /// Get the number of middleware functions
@@ -61,6 +133,24 @@ impl MiddlewareManager {
         self.middleware.len()
     }
 
+    /// Get the names of the middleware, in the order they run
+    pub fn names(&self) -> Vec<&'static str> {
+        self.middleware
+            .iter()
+            .map(|middleware| middleware.name())
+            .collect()
+    }
+
+    #[cfg(feature = "middleware")]
+    pub(crate) fn heap_size(&self) -> usize {
+        self.middleware.capacity() * std::mem::size_of::<Box<dyn Middleware>>()
+            + self
+                .middleware
+                .iter()
+                .map(|m| std::mem::size_of_val(&**m))
+                .sum::<usize>()
+    }
+
     /// Clear all middleware
     pub fn clear(&mut self) {
         self.middleware.clear();
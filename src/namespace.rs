@@ -0,0 +1,130 @@
+//! Isolated listener namespaces within one dispatcher
+
+use crate::{
+    Dispatch, DispatchContext, DispatchResult, Event, EventDispatcher, IntoListenerResult,
+    ListenerOptions, TypedListenerId,
+};
+use std::any::TypeId;
+use std::sync::Arc;
+
+/// Namespace a dispatch runs in, carried in its [`DispatchContext`]
+pub(crate) struct ActiveNamespace(pub(crate) Arc<str>);
+
+/// View of a dispatcher scoped to one namespace
+///
+/// Created by [`EventDispatcher::namespace`]. Listeners registered through
+/// the view only see events dispatched through a view of the same
+/// namespace, and events dispatched through it reach only those
+/// listeners. Listeners registered on the dispatcher itself sit outside
+/// every namespace; they see a namespaced event only once its type is
+/// [`export`](Self::export)ed. Middleware, metrics and error policy stay
+/// shared across namespaces.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct InvoicePaid;
+///
+/// impl Event for InvoicePaid {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// let billing = dispatcher.namespace("billing");
+/// let shipping = dispatcher.namespace("shipping");
+///
+/// billing.on(|_: &InvoicePaid| println!("billing: mark invoice paid"));
+/// shipping.on(|_: &InvoicePaid| println!("shipping: never called"));
+/// dispatcher.on(|_: &InvoicePaid| println!("audit: invoice paid"));
+///
+/// // Only the billing listener sees billing's events
+/// assert_eq!(billing.dispatch(InvoicePaid).listener_count(), 1);
+///
+/// // Exported types also reach listeners outside any namespace
+/// billing.export::<InvoicePaid>();
+/// assert_eq!(billing.dispatch(InvoicePaid).listener_count(), 2);
+///
+/// // Events dispatched on the dispatcher itself skip namespaced listeners
+/// assert_eq!(dispatcher.dispatch(InvoicePaid).listener_count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Namespace<'a> {
+    pub(crate) dispatcher: &'a EventDispatcher,
+    pub(crate) name: Arc<str>,
+}
+
+impl Namespace<'_> {
+    /// Get the namespace name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the underlying dispatcher
+    pub fn dispatcher(&self) -> &EventDispatcher {
+        self.dispatcher
+    }
+
+    /// Subscribe a closure in this namespace, like [`EventDispatcher::on`]
+    pub fn on<T, F, R>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        self.on_with_options(listener, ListenerOptions::new())
+    }
+
+    /// Subscribe a closure in this namespace with per-listener options
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options' ordering constraints cannot be met, like
+    /// [`EventDispatcher::on_with_options`].
+    pub fn on_with_options<T, F, R>(
+        &self,
+        listener: F,
+        mut options: ListenerOptions,
+    ) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        options.namespace = Some(self.name.clone());
+        self.dispatcher.on_with_options(listener, options)
+    }
+
+    /// Subscribe a fallible listener in this namespace, like [`EventDispatcher::subscribe`]
+    pub fn subscribe<T, F>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        self.on(listener)
+    }
+
+    /// Dispatch an event to the listeners of this namespace
+    pub fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        let context = DispatchContext::new().with(ActiveNamespace(self.name.clone()));
+        self.dispatcher.dispatch_with_context(event, &context)
+    }
+
+    /// Dispatch an event in this namespace, ignoring the result
+    pub fn emit<T: Event>(&self, event: T) {
+        let _ = self.dispatch(event);
+    }
+
+    /// Also deliver events of type `T` dispatched here to listeners outside any namespace
+    ///
+    /// Listeners of other namespaces never see them.
+    pub fn export<T: Event + 'static>(&self) {
+        self.dispatcher.export(&self.name, TypeId::of::<T>());
+    }
+}
+
+impl Dispatch for Namespace<'_> {
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        Namespace::dispatch(self, event)
+    }
+}
@@ -0,0 +1,56 @@
+//! Post-dispatch observers
+
+use crate::{DispatchResult, Event, ListenerError, ListenerId};
+use std::time::Duration;
+
+/// Hook called after every dispatch
+///
+/// Register with
+/// [`EventDispatcher::add_observer`](crate::EventDispatcher::add_observer)
+/// to log, trace or export metrics for each dispatch. Observers run on the
+/// dispatching thread, so they should be cheap.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{DispatchObserver, DispatchResult, Event, EventDispatcher};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone)]
+/// struct Saved;
+///
+/// impl Event for Saved {}
+///
+/// struct SlowDispatchWarning;
+///
+/// impl DispatchObserver for SlowDispatchWarning {
+///     fn on_dispatch(&self, event: &dyn Event, _result: &DispatchResult, elapsed: Duration) {
+///         if elapsed > Duration::from_millis(16) {
+///             eprintln!("{} took {:?}", event.event_name(), elapsed);
+///         }
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.add_observer(SlowDispatchWarning);
+/// dispatcher.emit(Saved);
+/// ```
+pub trait DispatchObserver: Send + Sync {
+    /// Called once a dispatch has finished or been blocked
+    fn on_dispatch(&self, event: &dyn Event, result: &DispatchResult, elapsed: Duration);
+
+    /// Called after each listener returns
+    ///
+    /// Reported for sequential sync and async dispatch; the parallel
+    /// dispatch modes only report the dispatch as a whole.
+    fn on_listener(
+        &self,
+        event: &dyn Event,
+        listener: ListenerId,
+        name: &'static str,
+        result: &Result<(), ListenerError>,
+        elapsed: Duration,
+    ) {
+        let _ = (event, listener, name, result, elapsed);
+    }
+}
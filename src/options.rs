@@ -0,0 +1,458 @@
+//! Per-subscription listener options
+
+use crate::sync::Mutex;
+use crate::{ListenerId, Priority, TenantId};
+use std::any::TypeId;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cap on how often a single listener is invoked
+///
+/// Invocations beyond `max` within any `per` window are dropped: the
+/// listener is reported in [`DispatchResult::skipped`](crate::DispatchResult::skipped)
+/// and counted in [`EventMetadata::rate_limited_count`](crate::EventMetadata::rate_limited_count).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Maximum invocations per window
+    pub max: u32,
+    /// Length of the sliding window
+    pub per: Duration,
+}
+
+impl RateLimit {
+    /// Allow at most `max` invocations in any window of length `per`
+    pub fn new(max: u32, per: Duration) -> Self {
+        Self { max, per }
+    }
+
+    /// Allow at most `max` invocations per second
+    pub fn per_second(max: u32) -> Self {
+        Self::new(max, Duration::from_secs(1))
+    }
+
+    /// Allow at most `max` invocations per minute
+    pub fn per_minute(max: u32) -> Self {
+        Self::new(max, Duration::from_secs(60))
+    }
+}
+
+/// Options for a single subscription
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, ListenerOptions, Priority, RateLimit};
+///
+/// #[derive(Debug, Clone)]
+/// struct ErrorLogged;
+///
+/// impl Event for ErrorLogged {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.subscribe_with_options(
+///     |_: &ErrorLogged| {
+///         println!("paging the on-call engineer");
+///         Ok(())
+///     },
+///     ListenerOptions::new()
+///         .name("slack-alert")
+///         .priority(Priority::Low)
+///         .rate_limit(RateLimit::per_minute(1)),
+/// );
+///
+/// assert_eq!(dispatcher.dispatch(ErrorLogged).listener_count(), 1);
+/// assert_eq!(dispatcher.dispatch(ErrorLogged).skipped().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ListenerOptions {
+    pub(crate) priority: Priority,
+    pub(crate) name: Option<&'static str>,
+    pub(crate) rate_limit: Option<RateLimit>,
+    #[cfg(feature = "async")]
+    pub(crate) max_concurrency: Option<usize>,
+    pub(crate) after: Vec<OrderTarget>,
+    pub(crate) before: Vec<OrderTarget>,
+    pub(crate) first_match: Option<&'static str>,
+    pub(crate) balanced: Option<&'static str>,
+    pub(crate) namespace: Option<Arc<str>>,
+    pub(crate) tenant: Option<TenantId>,
+    /// Events delivered before the listener removes itself
+    pub(crate) deliveries: Option<usize>,
+    /// Name of the dynamic events the listener is limited to
+    pub(crate) event_name: Option<String>,
+}
+
+/// Listener referenced by an ordering constraint
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum OrderTarget {
+    Id(ListenerId),
+    Name(&'static str),
+}
+
+/// Where a new listener goes and the dependency edges it adds
+#[derive(Debug, Default)]
+pub(crate) struct Placement {
+    /// Index to insert the listener at
+    pub(crate) position: usize,
+    /// Ids of the listeners it runs after
+    pub(crate) after: Vec<usize>,
+    /// Ids of the listeners it runs before
+    pub(crate) before: Vec<usize>,
+}
+
+/// Why a listener's options could not be applied
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubscribeError {
+    /// The referenced id is not subscribed to this event type
+    #[error("listener {0} is not subscribed to this event")]
+    UnknownListener(ListenerId),
+    /// No listener of this event type has the referenced name
+    #[error("no listener named `{0}` is subscribed to this event")]
+    UnknownName(&'static str),
+    /// The constraints cannot hold together with the listener's priority
+    #[error("ordering relative to listener {0} conflicts with the listener's priority or other constraints")]
+    Conflict(ListenerId),
+    /// The event type is delivered to a single listener, so listener
+    /// groups cannot be used with it
+    #[error("`{0}` uses unicast delivery, which cannot be combined with listener groups")]
+    Unicast(&'static str),
+}
+
+impl ListenerOptions {
+    /// Create options with normal priority and no limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the listener priority
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Name the listener for debugging output and dispatch results
+    ///
+    /// Defaults to the closure's type name.
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Limit how often the listener is invoked
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Run after the given listener
+    ///
+    /// Orders listeners that share a priority. The listener must already
+    /// be subscribed to the same event type; this is checked when
+    /// subscribing. A listener of lower priority already runs later.
+    ///
+    /// Ordering constraints form a dependency graph: the parallel dispatch
+    /// modes run a listener only once everything it depends on has
+    /// finished, and run independent listeners concurrently.
+    pub fn after(mut self, listener: impl Into<ListenerId>) -> Self {
+        self.after.push(OrderTarget::Id(listener.into()));
+        self
+    }
+
+    /// Run after every listener with the given name
+    pub fn after_named(mut self, name: &'static str) -> Self {
+        self.after.push(OrderTarget::Name(name));
+        self
+    }
+
+    /// Run before the given listener
+    ///
+    /// The counterpart of [`after`](Self::after), for a listener that must
+    /// see the event before one subscribed earlier.
+    pub fn before(mut self, listener: impl Into<ListenerId>) -> Self {
+        self.before.push(OrderTarget::Id(listener.into()));
+        self
+    }
+
+    /// Run before every listener with the given name
+    pub fn before_named(mut self, name: &'static str) -> Self {
+        self.before.push(OrderTarget::Name(name));
+        self
+    }
+
+    /// Find where a new listener goes among `existing` (id, name, priority)
+    /// entries, which are sorted by priority, highest first
+    pub(crate) fn placement(
+        &self,
+        dispatcher: usize,
+        type_id: TypeId,
+        existing: &[(usize, &'static str, Priority)],
+    ) -> Result<Placement, SubscribeError> {
+        let group_start = existing
+            .iter()
+            .take_while(|(_, _, priority)| *priority > self.priority)
+            .count();
+        let group_end = group_start
+            + existing[group_start..]
+                .iter()
+                .take_while(|(_, _, priority)| *priority == self.priority)
+                .count();
+
+        let resolve = |target: &OrderTarget| -> Result<Vec<usize>, SubscribeError> {
+            let positions: Vec<usize> = existing
+                .iter()
+                .enumerate()
+                .filter(|(_, (id, name, _))| match target {
+                    OrderTarget::Id(target) => {
+                        target.dispatcher == dispatcher
+                            && target.type_id == type_id
+                            && target.id == *id
+                    }
+                    OrderTarget::Name(target) => target == name,
+                })
+                .map(|(position, _)| position)
+                .collect();
+            match (positions.is_empty(), target) {
+                (true, OrderTarget::Id(id)) => Err(SubscribeError::UnknownListener(*id)),
+                (true, OrderTarget::Name(name)) => Err(SubscribeError::UnknownName(name)),
+                (false, _) => Ok(positions),
+            }
+        };
+
+        let mut placement = Placement::default();
+        let (mut lo, mut lo_by) = (group_start, None);
+        for target in &self.after {
+            for position in resolve(target)? {
+                placement.after.push(existing[position].0);
+                if position + 1 > lo {
+                    (lo, lo_by) = (position + 1, Some(position));
+                }
+            }
+        }
+        let (mut hi, mut hi_by) = (group_end, None);
+        for target in &self.before {
+            for position in resolve(target)? {
+                placement.before.push(existing[position].0);
+                if position < hi {
+                    (hi, hi_by) = (position, Some(position));
+                }
+            }
+        }
+
+        if lo > hi {
+            let culprit = if hi < group_start { hi_by } else { lo_by };
+            let (id, _, _) = existing[culprit.expect("a constraint narrowed the range")];
+            return Err(SubscribeError::Conflict(ListenerId::new(
+                dispatcher, id, type_id,
+            )));
+        }
+        placement.position = hi;
+        Ok(placement)
+    }
+
+    /// Make the listener a member of a first-match group
+    ///
+    /// Of the listeners in `group`, only the first to handle an event, in
+    /// priority order, runs; the others are reported as skipped. A member
+    /// that returns an error or [`Handled::Pass`](crate::Handled::Pass)
+    /// leaves the event to the next member, which makes fallback chains.
+    /// Listeners outside the group are unaffected. Groups apply to
+    /// sequential dispatch; the parallel dispatch modes run every member.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, Handled, ListenerOptions, Priority};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Render {
+    ///     format: &'static str,
+    /// }
+    ///
+    /// impl Event for Render {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// // Primary renderer, which may fail
+    /// dispatcher.subscribe_with_options(
+    ///     |_: &Render| Err("no GPU available".into()),
+    ///     ListenerOptions::new().priority(Priority::High).first_match("renderer"),
+    /// );
+    /// // Only takes SVG, declines everything else
+    /// dispatcher.on_with_options(
+    ///     |event: &Render| if event.format == "svg" { Handled::Continue } else { Handled::Pass },
+    ///     ListenerOptions::new().first_match("renderer"),
+    /// );
+    /// // Software fallback
+    /// let software = dispatcher.subscribe_with_options(
+    ///     |_: &Render| Ok(()),
+    ///     ListenerOptions::new().priority(Priority::Low).first_match("renderer"),
+    /// );
+    /// dispatcher.on(|_: &Render| println!("frame counted"));
+    ///
+    /// let result = dispatcher.dispatch(Render { format: "svg" });
+    /// assert_eq!(result.error_count(), 1);
+    /// assert_eq!(result.skipped(), &[software]);
+    ///
+    /// let result = dispatcher.dispatch(Render { format: "png" });
+    /// assert!(result.skipped().is_empty());
+    /// assert_eq!(result.listener_count(), 4);
+    /// ```
+    pub fn first_match(mut self, group: &'static str) -> Self {
+        self.first_match = Some(group);
+        self
+    }
+
+    /// Make the listener a worker in a load-balanced group
+    ///
+    /// Each event is delivered to exactly one member of `group`, chosen by
+    /// the group's [`LoadBalance`](crate::LoadBalance) strategy, instead
+    /// of being broadcast to all of them. Listeners outside the group
+    /// still see every event. Members that were not chosen are not
+    /// reported in the dispatch result.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, ListenerOptions};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Thumbnail {
+    ///     image: u32,
+    /// }
+    ///
+    /// impl Event for Thumbnail {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// let handled: Vec<Arc<AtomicUsize>> = (0..3).map(|_| Arc::new(AtomicUsize::new(0))).collect();
+    /// for count in &handled {
+    ///     let count = count.clone();
+    ///     dispatcher.on_with_options(
+    ///         move |_: &Thumbnail| {
+    ///             count.fetch_add(1, Ordering::SeqCst);
+    ///         },
+    ///         ListenerOptions::new().balanced("thumbnailers"),
+    ///     );
+    /// }
+    ///
+    /// for image in 0..6 {
+    ///     assert_eq!(dispatcher.dispatch(Thumbnail { image }).listener_count(), 1);
+    /// }
+    /// assert!(handled.iter().all(|count| count.load(Ordering::SeqCst) == 2));
+    /// ```
+    pub fn balanced(mut self, group: &'static str) -> Self {
+        self.balanced = Some(group);
+        self
+    }
+
+    /// Only receive dispatches made for `tenant`
+    ///
+    /// The tenant is read from the [`TenantId`] in the dispatch context;
+    /// dispatches without one skip the listener.
+    pub fn tenant(mut self, tenant: impl Into<TenantId>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Deliver at most `n` events, then remove the listener
+    ///
+    /// The limit holds across concurrent dispatches. Events the listener
+    /// is skipped for, by a rate limit or an earlier listener stopping
+    /// propagation, do not count. A dispatch that reaches the listener
+    /// after another one took its last delivery reports it as skipped.
+    /// Applies to sync listeners only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher, ListenerOptions, Priority};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Job;
+    ///
+    /// impl Event for Job {}
+    ///
+    /// let dispatcher = Arc::new(EventDispatcher::new());
+    /// let handle = dispatcher.clone();
+    /// let nested = AtomicBool::new(false);
+    /// // Dispatches the job again before the worker below is reached
+    /// dispatcher.on_with_options(
+    ///     move |_: &Job| {
+    ///         if !nested.swap(true, Ordering::SeqCst) {
+    ///             handle.dispatch(Job);
+    ///         }
+    ///     },
+    ///     ListenerOptions::new().priority(Priority::High),
+    /// );
+    /// let worker = dispatcher.on_with_options(|_: &Job| {}, ListenerOptions::new().take(1));
+    ///
+    /// let result = dispatcher.dispatch(Job);
+    /// assert_eq!(result.skipped(), [worker.id()]);
+    /// assert_eq!(result.listener_count(), 1);
+    /// ```
+    pub fn take(mut self, n: usize) -> Self {
+        assert!(n > 0, "a listener must take at least one event");
+        self.deliveries = Some(n);
+        self
+    }
+
+    /// Limit how many invocations of an async listener may run at once
+    ///
+    /// Applies across concurrent `dispatch_async` calls. Defaults to 1, so
+    /// an async listener never runs concurrently with itself and sees
+    /// events in dispatch order. Values below one are treated as one.
+    #[cfg(feature = "async")]
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+}
+
+/// Sliding-window limiter enforcing a [`RateLimit`]
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            recent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Copy the limiter, including the invocations it remembers
+    pub(crate) fn duplicate(&self) -> Self {
+        Self {
+            limit: self.limit,
+            recent: Mutex::new(self.recent.lock().unwrap().clone()),
+        }
+    }
+
+    /// Record an invocation at `now` if the limit allows it
+    pub(crate) fn try_acquire(&self, now: Instant) -> bool {
+        let mut recent = self.recent.lock().unwrap();
+        while recent
+            .front()
+            .is_some_and(|at| now.saturating_duration_since(*at) >= self.limit.per)
+        {
+            recent.pop_front();
+        }
+
+        if recent.len() < self.limit.max as usize {
+            recent.push_back(now);
+            true
+        } else {
+            false
+        }
+    }
+}
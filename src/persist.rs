@@ -0,0 +1,982 @@
+//! Append-only event log on disk (requires "serde" feature)
+//!
+//! [`EventLog`] appends events encoded by an [`EventCodec`] to a file, one
+//! JSON [`LogEnvelope`] per line. When the codec has
+//! [encryption keys](EventCodec::set_keys), payloads are sealed before
+//! they are written. Writes are buffered and made durable according to
+//! the log's [`FlushPolicy`]. A [`ProjectionManager`] keeps read models
+//! built from the log up to date, and a [`LogReader`] inspects a log file
+//! offline.
+
+use crate::codec::{CodecError, EncryptedEvent, EventCodec, SerializedEvent};
+use crate::sync::Mutex;
+use crate::{Clock, DispatchObserver, DispatchResult, Event, SystemClock};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// One line of an event log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEnvelope {
+    /// Position in the log, starting at zero
+    pub sequence: u64,
+    /// Milliseconds since the Unix epoch when the event was appended
+    pub recorded_at: u64,
+    /// The encoded event
+    pub record: LogRecord,
+}
+
+/// Encoded event stored in a [`LogEnvelope`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogRecord {
+    /// Payload stored as plain JSON
+    Plain(SerializedEvent),
+    /// Payload encrypted by the codec's key ring
+    Sealed(EncryptedEvent),
+}
+
+impl LogRecord {
+    /// Decode the event, opening sealed payloads with the codec's keys
+    pub fn decode(self, codec: &EventCodec) -> Result<Box<dyn Event>, CodecError> {
+        let record = match self {
+            LogRecord::Plain(record) => record,
+            LogRecord::Sealed(record) => codec.open(record)?,
+        };
+        codec.decode(record)
+    }
+}
+
+/// Errors raised by the event log
+#[derive(Debug, thiserror::Error)]
+pub enum LogError {
+    /// Reading or writing the log file failed
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Encoding or decoding an event failed
+    #[error(transparent)]
+    Codec(#[from] CodecError),
+    /// A line of the log is not a valid envelope
+    #[error("corrupt log entry on line {line}: {source}")]
+    Corrupt {
+        /// One-based line number
+        line: usize,
+        /// The parse error
+        source: serde_json::Error,
+    },
+}
+
+/// When appended events are made durable
+///
+/// A flush writes buffered envelopes to the file and syncs it to disk.
+/// Flushing less often trades the events that can be lost in a crash
+/// for throughput. Whatever the policy, [`EventLog::flush`] flushes on
+/// demand and dropping the log flushes what is left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// Flush after every event (default)
+    #[default]
+    EveryEvent,
+    /// Flush once this many events are buffered
+    EveryN(usize),
+    /// Flush on the first append after this much time has passed since
+    /// the last flush
+    Interval(Duration),
+    /// Only flush when asked to
+    Manual,
+}
+
+/// Flush activity of an [`EventLog`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushStats {
+    /// Number of flushes
+    pub flushes: u64,
+    /// Number of events made durable
+    pub events_flushed: u64,
+    /// Events appended but not yet flushed
+    pub pending: usize,
+    /// Duration of the most recent flush
+    pub last_latency: Duration,
+    /// Longest flush
+    pub max_latency: Duration,
+    /// Time spent flushing in total
+    pub total_latency: Duration,
+}
+
+impl FlushStats {
+    /// Get the mean flush duration
+    pub fn average_latency(&self) -> Duration {
+        match u32::try_from(self.flushes) {
+            Ok(0) => Duration::ZERO,
+            Ok(count) => self.total_latency / count,
+            Err(_) => self.total_latency.div_f64(self.flushes as f64),
+        }
+    }
+}
+
+/// Append-only, file-backed log of events
+///
+/// Append events directly with [`append`](Self::append), or register a
+/// clone with
+/// [`EventDispatcher::add_observer`](crate::EventDispatcher::add_observer)
+/// to log every dispatched event whose type is registered with the codec.
+/// Clones share the same file. Observer appends cannot report errors;
+/// they are counted by [`failed_appends`](Self::failed_appends).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::codec::EventCodec;
+/// use mod_events::persist::{EventLog, FlushPolicy};
+/// use mod_events::{Event, EventDispatcher, EventSchema};
+/// use serde::{Deserialize, Serialize};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct OrderPlaced {
+///     order_id: u64,
+/// }
+///
+/// impl Event for OrderPlaced {}
+///
+/// impl EventSchema for OrderPlaced {
+///     const NAME: &'static str = "order.placed";
+///     const VERSION: u32 = 1;
+/// }
+///
+/// let mut codec = EventCodec::new();
+/// codec.register::<OrderPlaced>().unwrap();
+///
+/// # let path = std::env::temp_dir().join(format!("mod-events-doc-{}.log", std::process::id()));
+/// # let _ = std::fs::remove_file(&path);
+/// let log = EventLog::open(&path, Arc::new(codec))
+///     .unwrap()
+///     .with_flush_policy(FlushPolicy::EveryN(100));
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.add_observer(log.clone());
+/// for order_id in 0..250 {
+///     dispatcher.emit(OrderPlaced { order_id });
+/// }
+///
+/// let stats = log.flush_stats();
+/// assert_eq!((stats.flushes, stats.pending), (2, 50));
+///
+/// log.flush().unwrap();
+/// assert_eq!(log.flush_stats().events_flushed, 250);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventLog {
+    inner: Arc<LogShared>,
+}
+
+#[derive(Debug)]
+struct LogShared {
+    path: PathBuf,
+    codec: Arc<EventCodec>,
+    state: Mutex<LogState>,
+}
+
+struct LogState {
+    writer: BufWriter<File>,
+    policy: FlushPolicy,
+    /// Time source for flush intervals and latencies
+    clock: Arc<dyn Clock>,
+    next_sequence: u64,
+    last_flush: Instant,
+    failed_appends: u64,
+    stats: FlushStats,
+}
+
+impl fmt::Debug for LogState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogState")
+            .field("policy", &self.policy)
+            .field("next_sequence", &self.next_sequence)
+            .field("last_flush", &self.last_flush)
+            .field("failed_appends", &self.failed_appends)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl EventLog {
+    /// Open the log at `path`, creating it if needed
+    ///
+    /// Existing entries are kept; new events are appended after them.
+    pub fn open(path: impl AsRef<Path>, codec: Arc<EventCodec>) -> Result<Self, LogError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+        let mut next_sequence = 0;
+        for line in BufReader::new(&file).lines() {
+            if !line?.trim().is_empty() {
+                next_sequence += 1;
+            }
+        }
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+        Ok(Self {
+            inner: Arc::new(LogShared {
+                path,
+                codec,
+                state: Mutex::new(LogState {
+                    writer: BufWriter::new(file),
+                    policy: FlushPolicy::default(),
+                    last_flush: clock.now(),
+                    clock,
+                    next_sequence,
+                    failed_appends: 0,
+                    stats: FlushStats::default(),
+                }),
+            }),
+        })
+    }
+
+    /// Set when appended events are made durable
+    pub fn with_flush_policy(self, policy: FlushPolicy) -> Self {
+        self.set_flush_policy(policy);
+        self
+    }
+
+    /// Change when appended events are made durable
+    pub fn set_flush_policy(&self, policy: FlushPolicy) {
+        self.inner.state.lock().unwrap().policy = policy;
+    }
+
+    /// Time flush intervals and latencies with `clock`
+    ///
+    /// Pass the dispatcher's [`clock`](crate::EventDispatcher::clock) so
+    /// a [`TestClock`](crate::TestClock) drives [`FlushPolicy::Interval`].
+    /// The interval restarts from the clock's current time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::codec::EventCodec;
+    /// use mod_events::persist::{EventLog, FlushPolicy};
+    /// use mod_events::{Event, EventDispatcher, EventSchema, TestClock};
+    /// use serde::{Deserialize, Serialize};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone, Serialize, Deserialize)]
+    /// struct Tick;
+    ///
+    /// impl Event for Tick {}
+    ///
+    /// impl EventSchema for Tick {
+    ///     const NAME: &'static str = "tick";
+    /// }
+    ///
+    /// let mut codec = EventCodec::new();
+    /// codec.register::<Tick>().unwrap();
+    ///
+    /// # let path = std::env::temp_dir().join(format!("mod-events-clock-{}.log", std::process::id()));
+    /// # let _ = std::fs::remove_file(&path);
+    /// let clock = TestClock::new();
+    /// let dispatcher = EventDispatcher::with_clock(clock.clone());
+    /// let log = EventLog::open(&path, Arc::new(codec))
+    ///     .unwrap()
+    ///     .with_clock(dispatcher.clock().clone())
+    ///     .with_flush_policy(FlushPolicy::Interval(Duration::from_secs(5)));
+    ///
+    /// log.append(&Tick).unwrap();
+    /// assert_eq!(log.flush_stats().flushes, 0);
+    ///
+    /// clock.advance(Duration::from_secs(5));
+    /// log.append(&Tick).unwrap();
+    /// assert_eq!(log.flush_stats().flushes, 1);
+    /// # drop(log);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            state.last_flush = clock.now();
+            state.clock = clock;
+        }
+        self
+    }
+
+    /// Get the path of the log file
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Get the codec events are encoded with
+    pub fn codec(&self) -> &EventCodec {
+        &self.inner.codec
+    }
+
+    /// Get the number of events in the log, flushed or not
+    pub fn len(&self) -> u64 {
+        self.inner.state.lock().unwrap().next_sequence
+    }
+
+    /// Check whether the log holds no events
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append an event, returning its sequence number
+    ///
+    /// The event's type must be registered with the codec. Whether the
+    /// event is durable when this returns depends on the flush policy.
+    pub fn append(&self, event: &dyn Event) -> Result<u64, LogError> {
+        let codec = &self.inner.codec;
+        let encoded = codec.encode_dyn(event)?;
+        let record = match codec.keys() {
+            Some(_) => LogRecord::Sealed(codec.seal(encoded)?),
+            None => LogRecord::Plain(encoded),
+        };
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+
+        let mut state = self.inner.state.lock().unwrap();
+        let envelope = LogEnvelope {
+            sequence: state.next_sequence,
+            recorded_at,
+            record,
+        };
+        serde_json::to_writer(&mut state.writer, &envelope).map_err(CodecError::from)?;
+        state.writer.write_all(b"\n")?;
+        state.next_sequence += 1;
+        state.stats.pending += 1;
+
+        let due = match state.policy {
+            FlushPolicy::EveryEvent => true,
+            FlushPolicy::EveryN(count) => state.stats.pending >= count.max(1),
+            FlushPolicy::Interval(interval) => {
+                state
+                    .clock
+                    .now()
+                    .saturating_duration_since(state.last_flush)
+                    >= interval
+            }
+            FlushPolicy::Manual => false,
+        };
+        if due {
+            state.flush()?;
+        }
+        Ok(envelope.sequence)
+    }
+
+    /// Write buffered events and sync the file to disk
+    pub fn flush(&self) -> Result<(), LogError> {
+        self.inner.state.lock().unwrap().flush()
+    }
+
+    /// Get flush counts and latencies
+    pub fn flush_stats(&self) -> FlushStats {
+        self.inner.state.lock().unwrap().stats
+    }
+
+    /// Get how many observed events could not be appended
+    ///
+    /// Counts events of types not registered with the codec, and I/O or
+    /// encryption failures, seen while the log was used as an observer.
+    pub fn failed_appends(&self) -> u64 {
+        self.inner.state.lock().unwrap().failed_appends
+    }
+}
+
+impl LogState {
+    fn flush(&mut self) -> Result<(), LogError> {
+        let started = self.clock.now();
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        let now = self.clock.now();
+        let elapsed = now.saturating_duration_since(started);
+
+        let stats = &mut self.stats;
+        stats.flushes += 1;
+        stats.events_flushed += stats.pending as u64;
+        stats.pending = 0;
+        stats.last_latency = elapsed;
+        stats.max_latency = stats.max_latency.max(elapsed);
+        stats.total_latency += elapsed;
+        self.last_flush = now;
+        Ok(())
+    }
+}
+
+impl Drop for LogShared {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.stats.pending > 0 {
+                let _ = state.flush();
+            }
+        }
+    }
+}
+
+impl DispatchObserver for EventLog {
+    fn on_dispatch(&self, event: &dyn Event, result: &DispatchResult, _elapsed: Duration) {
+        if result.is_blocked() {
+            return;
+        }
+        if self.append(event).is_err() {
+            self.inner.state.lock().unwrap().failed_appends += 1;
+        }
+    }
+}
+
+/// Envelopes read from a log file, starting at a byte offset
+///
+/// Yields each envelope with the offset of the line after it, so a reader
+/// can resume where it stopped.
+pub(crate) struct Entries {
+    reader: BufReader<File>,
+    offset: u64,
+    line: usize,
+    buffer: String,
+}
+
+impl Entries {
+    pub(crate) fn open(path: &Path, offset: u64, line: usize) -> Result<Self, LogError> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            offset,
+            line,
+            buffer: String::new(),
+        })
+    }
+}
+
+impl Iterator for Entries {
+    type Item = Result<(LogEnvelope, u64), LogError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            let read = match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(read) => read,
+                Err(error) => return Some(Err(error.into())),
+            };
+            // A line without its newline is still being written
+            if !self.buffer.ends_with('\n') {
+                return None;
+            }
+            self.offset += read as u64;
+            self.line += 1;
+            if self.buffer.trim().is_empty() {
+                continue;
+            }
+            return Some(
+                serde_json::from_str(&self.buffer)
+                    .map(|envelope| (envelope, self.offset))
+                    .map_err(|source| LogError::Corrupt {
+                        line: self.line,
+                        source,
+                    }),
+            );
+        }
+    }
+}
+
+/// Read-only view of a log file, usable without a dispatcher
+///
+/// Iterating reads the file lazily, one line at a time. A trailing line
+/// that is still being written is ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::codec::EventCodec;
+/// use mod_events::persist::{EventLog, LogReader};
+/// use mod_events::{Event, EventSchema};
+/// use serde::{Deserialize, Serialize};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct Shipped {
+///     order: u64,
+/// }
+///
+/// impl Event for Shipped {}
+///
+/// impl EventSchema for Shipped {
+///     const NAME: &'static str = "order.shipped";
+///     const VERSION: u32 = 1;
+/// }
+///
+/// let mut codec = EventCodec::new();
+/// codec.register::<Shipped>().unwrap();
+/// let codec = Arc::new(codec);
+/// # let path = std::env::temp_dir().join(format!("mod-events-reader-{}.log", std::process::id()));
+/// # let _ = std::fs::remove_file(&path);
+/// let log = EventLog::open(&path, codec.clone()).unwrap();
+/// for order in 1..=3 {
+///     log.append(&Shipped { order }).unwrap();
+/// }
+/// drop(log);
+///
+/// let reader = LogReader::open(&path).unwrap();
+/// let summary = reader.summary().unwrap();
+/// assert_eq!(summary.events, 3);
+/// assert_eq!(summary.by_name["order.shipped"], 3);
+///
+/// let orders: Vec<u64> = reader
+///     .events(&codec)
+///     .unwrap()
+///     .map(|entry| entry.unwrap().event.downcast::<Shipped>().unwrap().order)
+///     .collect();
+/// assert_eq!(orders, [1, 2, 3]);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogReader {
+    path: PathBuf,
+}
+
+impl LogReader {
+    /// Open an existing log file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, LogError> {
+        let path = path.as_ref().to_path_buf();
+        File::open(&path)?;
+        Ok(Self { path })
+    }
+
+    /// Get the path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Iterate the envelopes without decoding their payloads
+    pub fn envelopes(&self) -> Result<Envelopes, LogError> {
+        Ok(Envelopes(Entries::open(&self.path, 0, 0)?))
+    }
+
+    /// Iterate the decoded events
+    ///
+    /// Sealed payloads are opened with the codec's keys.
+    pub fn events<'a>(&self, codec: &'a EventCodec) -> Result<LoggedEvents<'a>, LogError> {
+        Ok(LoggedEvents {
+            entries: Entries::open(&self.path, 0, 0)?,
+            codec,
+        })
+    }
+
+    /// Read the whole log and summarize it, without decoding payloads
+    pub fn summary(&self) -> Result<LogSummary, LogError> {
+        let mut summary = LogSummary::default();
+        for entry in Entries::open(&self.path, 0, 0)? {
+            let (envelope, offset) = entry?;
+            summary.record(&envelope);
+            summary.bytes = offset;
+        }
+        Ok(summary)
+    }
+}
+
+/// Envelopes of a log, created by [`LogReader::envelopes`]
+pub struct Envelopes(Entries);
+
+impl Iterator for Envelopes {
+    type Item = Result<LogEnvelope, LogError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|entry| entry.map(|(envelope, _)| envelope))
+    }
+}
+
+/// Event decoded from a log, yielded by [`LogReader::events`]
+#[derive(Debug)]
+pub struct LoggedEvent {
+    /// Position in the log
+    pub sequence: u64,
+    /// Milliseconds since the Unix epoch when the event was appended
+    pub recorded_at: u64,
+    /// The decoded event
+    pub event: Box<dyn Event>,
+}
+
+/// Decoded events of a log, created by [`LogReader::events`]
+pub struct LoggedEvents<'a> {
+    entries: Entries,
+    codec: &'a EventCodec,
+}
+
+impl Iterator for LoggedEvents<'_> {
+    type Item = Result<LoggedEvent, LogError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (envelope, _) = match self.entries.next()? {
+            Ok(entry) => entry,
+            Err(error) => return Some(Err(error)),
+        };
+        Some(
+            envelope
+                .record
+                .decode(self.codec)
+                .map(|event| LoggedEvent {
+                    sequence: envelope.sequence,
+                    recorded_at: envelope.recorded_at,
+                    event,
+                })
+                .map_err(LogError::from),
+        )
+    }
+}
+
+/// Summary statistics of a log, produced by [`LogReader::summary`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogSummary {
+    /// Number of envelopes
+    pub events: u64,
+    /// Envelopes whose payload is encrypted
+    pub sealed: u64,
+    /// Sequence number of the first envelope
+    pub first_sequence: Option<u64>,
+    /// Sequence number of the last envelope
+    pub last_sequence: Option<u64>,
+    /// Earliest append time, in milliseconds since the Unix epoch
+    pub first_recorded_at: Option<u64>,
+    /// Latest append time, in milliseconds since the Unix epoch
+    pub last_recorded_at: Option<u64>,
+    /// Places where a sequence number does not follow the previous one
+    pub gaps: u64,
+    /// Envelopes per event name
+    pub by_name: BTreeMap<String, u64>,
+    /// Keys the sealed payloads were encrypted with
+    pub key_ids: BTreeSet<String>,
+    /// Size of the complete lines read, in bytes
+    pub bytes: u64,
+}
+
+impl LogSummary {
+    /// Get the time between the first and last append
+    pub fn span(&self) -> Option<Duration> {
+        let first = self.first_recorded_at?;
+        let last = self.last_recorded_at?;
+        Some(Duration::from_millis(last.saturating_sub(first)))
+    }
+
+    fn record(&mut self, envelope: &LogEnvelope) {
+        if self
+            .last_sequence
+            .is_some_and(|last| envelope.sequence != last + 1)
+        {
+            self.gaps += 1;
+        }
+        self.events += 1;
+        self.first_sequence.get_or_insert(envelope.sequence);
+        self.last_sequence = Some(envelope.sequence);
+        self.first_recorded_at = Some(
+            self.first_recorded_at
+                .map_or(envelope.recorded_at, |first| {
+                    first.min(envelope.recorded_at)
+                }),
+        );
+        self.last_recorded_at = Some(
+            self.last_recorded_at
+                .map_or(envelope.recorded_at, |last| last.max(envelope.recorded_at)),
+        );
+        let name = match &envelope.record {
+            LogRecord::Plain(record) => &record.name,
+            LogRecord::Sealed(record) => {
+                self.sealed += 1;
+                self.key_ids.insert(record.key_id.clone());
+                &record.name
+            }
+        };
+        *self.by_name.entry(name.clone()).or_default() += 1;
+    }
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Read model built by folding the events of an [`EventLog`]
+///
+/// Projections usually keep their state behind an `Arc` shared with the
+/// code that queries it.
+pub trait Projection: Send {
+    /// Fold one event into the projection
+    fn apply(&mut self, sequence: u64, event: &dyn Event) -> Result<(), BoxError>;
+
+    /// Discard all state before a rebuild
+    fn reset(&mut self);
+}
+
+/// Errors raised while updating projections
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectionError {
+    /// No projection is registered under this name
+    #[error("unknown projection `{0}`")]
+    Unknown(String),
+    /// Reading or decoding the log failed
+    #[error(transparent)]
+    Log(#[from] LogError),
+    /// A projection rejected an event; it stays at that position
+    #[error("projection `{projection}` failed at sequence {sequence}: {source}")]
+    Apply {
+        /// Name of the projection
+        projection: String,
+        /// Sequence number of the rejected event
+        sequence: u64,
+        /// The projection's error
+        source: BoxError,
+    },
+}
+
+/// Position and lag of one projection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectionStatus {
+    /// Name the projection was registered under
+    pub name: String,
+    /// Sequence number of the next event the projection will apply
+    pub position: u64,
+    /// Events in the log the projection has not applied yet
+    pub lag: u64,
+    /// Number of times the projection was rebuilt
+    pub rebuilds: u64,
+}
+
+struct ProjectionSlot {
+    projection: Box<dyn Projection>,
+    position: u64,
+    offset: u64,
+    line: usize,
+    rebuilds: u64,
+}
+
+/// Keeps projections of an [`EventLog`] up to date
+///
+/// Each projection remembers how far into the log it has read, so
+/// [`catch_up`](Self::catch_up) only applies new events, and a single
+/// projection can be [rebuilt](Self::rebuild) from the start while the
+/// others keep serving and catching up; projections are locked one at a
+/// time. Only events already flushed to the file are read, so the
+/// reported lag includes buffered events.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::codec::EventCodec;
+/// use mod_events::persist::{EventLog, Projection, ProjectionManager};
+/// use mod_events::{Event, EventSchema};
+/// use serde::{Deserialize, Serialize};
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct Deposited {
+///     amount: u64,
+/// }
+///
+/// impl Event for Deposited {}
+///
+/// impl EventSchema for Deposited {
+///     const NAME: &'static str = "account.deposited";
+///     const VERSION: u32 = 1;
+/// }
+///
+/// struct Balance(Arc<AtomicU64>);
+///
+/// impl Projection for Balance {
+///     fn apply(
+///         &mut self,
+///         _sequence: u64,
+///         event: &dyn Event,
+///     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///         if let Some(deposit) = event.downcast_ref::<Deposited>() {
+///             self.0.fetch_add(deposit.amount, Ordering::Relaxed);
+///         }
+///         Ok(())
+///     }
+///
+///     fn reset(&mut self) {
+///         self.0.store(0, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let mut codec = EventCodec::new();
+/// codec.register::<Deposited>().unwrap();
+/// # let path = std::env::temp_dir().join(format!("mod-events-projection-{}.log", std::process::id()));
+/// # let _ = std::fs::remove_file(&path);
+/// let log = EventLog::open(&path, Arc::new(codec)).unwrap();
+/// let projections = ProjectionManager::new(log.clone());
+///
+/// let balance = Arc::new(AtomicU64::new(0));
+/// projections.register("balance", Balance(balance.clone()));
+///
+/// for amount in [10, 20] {
+///     log.append(&Deposited { amount }).unwrap();
+/// }
+/// assert_eq!(projections.lag("balance"), Some(2));
+///
+/// projections.catch_up().unwrap();
+/// assert_eq!(balance.load(Ordering::Relaxed), 30);
+/// assert_eq!(projections.position("balance"), Some(2));
+///
+/// // Rebuilding replays the whole log into a fresh state
+/// projections.rebuild("balance").unwrap();
+/// assert_eq!(balance.load(Ordering::Relaxed), 30);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct ProjectionManager {
+    log: EventLog,
+    projections: Mutex<BTreeMap<String, Arc<Mutex<ProjectionSlot>>>>,
+}
+
+impl std::fmt::Debug for ProjectionManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectionManager")
+            .field("log", &self.log.path())
+            .field("projections", &self.projections.lock().unwrap().keys())
+            .finish()
+    }
+}
+
+impl ProjectionManager {
+    /// Manage projections of `log`
+    pub fn new(log: EventLog) -> Self {
+        Self {
+            log,
+            projections: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Register a projection starting at the beginning of the log
+    ///
+    /// Replaces any projection registered under the same name.
+    pub fn register(&self, name: impl Into<String>, projection: impl Projection + 'static) {
+        let slot = ProjectionSlot {
+            projection: Box::new(projection),
+            position: 0,
+            offset: 0,
+            line: 0,
+            rebuilds: 0,
+        };
+        self.projections
+            .lock()
+            .unwrap()
+            .insert(name.into(), Arc::new(Mutex::new(slot)));
+    }
+
+    /// Remove a projection
+    pub fn remove(&self, name: &str) -> bool {
+        self.projections.lock().unwrap().remove(name).is_some()
+    }
+
+    /// Apply new events to every projection
+    ///
+    /// Returns the number of events applied across all projections.
+    /// Stops at the first failing projection; the ones before it keep
+    /// their progress.
+    pub fn catch_up(&self) -> Result<u64, ProjectionError> {
+        let mut applied = 0;
+        for (name, slot) in self.slots() {
+            applied += self.advance(&name, &slot)?;
+        }
+        Ok(applied)
+    }
+
+    /// Apply new events to one projection, returning how many were applied
+    pub fn catch_up_one(&self, name: &str) -> Result<u64, ProjectionError> {
+        let slot = self.slot(name)?;
+        self.advance(name, &slot)
+    }
+
+    /// Reset one projection and replay the whole log into it
+    ///
+    /// Other projections are not locked and keep catching up meanwhile.
+    /// Returns the number of events replayed.
+    pub fn rebuild(&self, name: &str) -> Result<u64, ProjectionError> {
+        let slot = self.slot(name)?;
+        {
+            let mut slot = slot.lock().unwrap();
+            slot.projection.reset();
+            slot.position = 0;
+            slot.offset = 0;
+            slot.line = 0;
+            slot.rebuilds += 1;
+        }
+        self.advance(name, &slot)
+    }
+
+    /// Get the sequence number of the next event a projection will apply
+    pub fn position(&self, name: &str) -> Option<u64> {
+        let slot = self.slot(name).ok()?;
+        let position = slot.lock().unwrap().position;
+        Some(position)
+    }
+
+    /// Get how many events of the log a projection has not applied
+    pub fn lag(&self, name: &str) -> Option<u64> {
+        self.position(name)
+            .map(|position| self.log.len().saturating_sub(position))
+    }
+
+    /// Get the position and lag of every projection, sorted by name
+    pub fn status(&self) -> Vec<ProjectionStatus> {
+        let len = self.log.len();
+        self.slots()
+            .into_iter()
+            .map(|(name, slot)| {
+                let slot = slot.lock().unwrap();
+                ProjectionStatus {
+                    name,
+                    position: slot.position,
+                    lag: len.saturating_sub(slot.position),
+                    rebuilds: slot.rebuilds,
+                }
+            })
+            .collect()
+    }
+
+    fn slots(&self) -> Vec<(String, Arc<Mutex<ProjectionSlot>>)> {
+        self.projections
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, slot)| (name.clone(), slot.clone()))
+            .collect()
+    }
+
+    fn slot(&self, name: &str) -> Result<Arc<Mutex<ProjectionSlot>>, ProjectionError> {
+        self.projections
+            .lock()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ProjectionError::Unknown(name.to_string()))
+    }
+
+    fn advance(&self, name: &str, slot: &Mutex<ProjectionSlot>) -> Result<u64, ProjectionError> {
+        let mut slot = slot.lock().unwrap();
+        let codec = self.log.codec();
+        let mut applied = 0;
+        for entry in Entries::open(self.log.path(), slot.offset, slot.line)? {
+            let (envelope, next_offset) = entry?;
+            let event = envelope.record.decode(codec).map_err(LogError::from)?;
+            slot.projection
+                .apply(envelope.sequence, event.as_ref())
+                .map_err(|source| ProjectionError::Apply {
+                    projection: name.to_string(),
+                    sequence: envelope.sequence,
+                    source,
+                })?;
+            slot.position = envelope.sequence + 1;
+            slot.offset = next_offset;
+            slot.line += 1;
+            applied += 1;
+        }
+        Ok(applied)
+    }
+}
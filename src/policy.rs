@@ -0,0 +1,150 @@
+//! Dispatch policies
+
+/// What the dispatcher does when a listener returns an error
+///
+/// Set with
+/// [`EventDispatcher::set_error_policy`](crate::EventDispatcher::set_error_policy);
+/// [`EventDispatcher::dispatch_until_err`](crate::EventDispatcher::dispatch_until_err)
+/// stops at the first error for a single dispatch whatever the policy.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{ErrorPolicy, Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct Validate;
+///
+/// impl Event for Validate {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.set_error_policy(ErrorPolicy::StopOnError);
+///
+/// dispatcher.subscribe(|_: &Validate| Err("invalid".into()));
+/// let never_ran = dispatcher.subscribe(|_: &Validate| Ok(()));
+///
+/// let result = dispatcher.dispatch(Validate);
+/// assert_eq!(result.error_count(), 1);
+/// assert_eq!(result.skipped(), &[never_ran]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorPolicy {
+    /// Keep invoking the remaining listeners (default)
+    #[default]
+    Continue,
+    /// Skip the remaining listeners after the first error
+    StopOnError,
+}
+
+/// What the dispatcher does when a listener panics
+///
+/// Set with
+/// [`EventDispatcher::set_panic_policy`](crate::EventDispatcher::set_panic_policy).
+/// A captured panic is reported in the dispatch result as
+/// [`ListenerError::Panic`](crate::ListenerError::Panic) and counts as a
+/// listener error, including for the [`ErrorPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PanicPolicy {
+    /// Let the panic unwind through the dispatching call (default)
+    #[default]
+    Propagate,
+    /// Catch the panic and keep invoking the remaining listeners
+    Capture,
+    /// Catch the panic and skip the remaining listeners
+    Abort,
+}
+
+impl PanicPolicy {
+    /// Check whether listener panics are caught
+    pub(crate) fn catches(self) -> bool {
+        self != PanicPolicy::Propagate
+    }
+}
+
+/// Whether an event type is delivered to all of its listeners or one
+///
+/// Set per event type with
+/// [`EventDispatcher::set_delivery`](crate::EventDispatcher::set_delivery).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Delivery, Event, EventDispatcher, ListenerOptions, SubscribeError};
+///
+/// #[derive(Debug, Clone)]
+/// struct Job {
+///     id: u32,
+/// }
+///
+/// impl Event for Job {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.set_delivery::<Job>(Delivery::Unicast).unwrap();
+/// assert!(dispatcher.dispatch(Job { id: 0 }).is_undelivered());
+///
+/// let first = dispatcher.subscribe(|_: &Job| Ok(()));
+/// let second = dispatcher.subscribe(|_: &Job| Ok(()));
+///
+/// let result = dispatcher.dispatch(Job { id: 1 });
+/// assert_eq!(result.listener_count(), 1);
+/// assert!(result.iter().all(|(id, _)| id == first));
+/// let result = dispatcher.dispatch(Job { id: 2 });
+/// assert!(result.iter().all(|(id, _)| id == second));
+///
+/// // Listener groups make no sense when only one listener gets each event
+/// let grouped = dispatcher.try_subscribe_with_options(
+///     |_: &Job| Ok(()),
+///     ListenerOptions::new().balanced("workers"),
+/// );
+/// assert!(matches!(grouped, Err(SubscribeError::Unicast(_))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Delivery {
+    /// Every listener receives every event (default)
+    #[default]
+    Broadcast,
+    /// Each event is consumed by exactly one listener, taking turns
+    ///
+    /// Sync and async listeners take turns separately, each within their
+    /// own dispatch mode. An event with no listener to consume it is
+    /// reported by [`DispatchResult::is_undelivered`](crate::DispatchResult::is_undelivered).
+    Unicast,
+}
+
+/// How a load-balanced group picks the listener for each event
+///
+/// Set per group with
+/// [`EventDispatcher::set_load_balance`](crate::EventDispatcher::set_load_balance);
+/// see [`ListenerOptions::balanced`](crate::ListenerOptions::balanced).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalance {
+    /// Take turns in subscription order (default)
+    #[default]
+    RoundRobin,
+    /// Pick the member with the fewest calls in progress, taking turns on ties
+    LeastBusy,
+}
+
+/// What a bounded subscription does when its buffer is full
+///
+/// Applies to subscriptions such as
+/// [`EventDispatcher::subscribe_mpsc`](crate::EventDispatcher::subscribe_mpsc).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BackpressurePolicy {
+    /// Discard the event for that consumer (default)
+    #[default]
+    DropNewest,
+    /// Discard the event and report a listener error in the dispatch result
+    Fail,
+    /// Block the dispatching thread until the consumer makes room
+    ///
+    /// Should not be used when dispatching from inside an async runtime,
+    /// where blocking the thread can deadlock the consumer. Channel
+    /// subscriptions cannot block there at all and report a full buffer
+    /// as a listener error, as with [`Fail`](Self::Fail).
+    Block,
+}
@@ -0,0 +1,152 @@
+//! Recycling of high-frequency event allocations
+
+use crate::sync::Mutex;
+use crate::Event;
+use std::ops::{Deref, DerefMut};
+
+type Factory<T> = Box<dyn Fn() -> T + Send + Sync>;
+type Reset<T> = Box<dyn Fn(&mut T) + Send + Sync>;
+
+/// Pool of reusable events
+///
+/// [`acquire`](Self::acquire) hands out a previously used event, or a new
+/// one if none is idle. The event returns to the pool when the
+/// [`Pooled`] guard drops, after the reset hook has cleared it, so
+/// buffers inside the event keep their capacity across frames. Dispatch
+/// pooled events with
+/// [`EventDispatcher::dispatch_ref`](crate::EventDispatcher::dispatch_ref).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, EventPool};
+///
+/// #[derive(Debug, Clone, Default)]
+/// struct ParticlesMoved {
+///     positions: Vec<(f32, f32)>,
+/// }
+///
+/// impl Event for ParticlesMoved {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.on(|moved: &ParticlesMoved| assert_eq!(moved.positions.len(), 1000));
+///
+/// let pool = EventPool::new(ParticlesMoved::default).with_reset(|event| event.positions.clear());
+///
+/// for frame in 0..3 {
+///     let mut event = pool.acquire();
+///     event.positions.extend((0..1000).map(|i| (i as f32, frame as f32)));
+///     dispatcher.dispatch_ref(&*event);
+/// }
+///
+/// // Every frame reused the same allocation
+/// assert_eq!(pool.idle(), 1);
+/// assert!(pool.acquire().positions.capacity() >= 1000);
+/// ```
+pub struct EventPool<T> {
+    idle: Mutex<Vec<T>>,
+    factory: Factory<T>,
+    reset: Option<Reset<T>>,
+    max_idle: usize,
+}
+
+impl<T> std::fmt::Debug for EventPool<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventPool")
+            .field("event", &std::any::type_name::<T>())
+            .field("idle", &self.idle())
+            .field("max_idle", &self.max_idle)
+            .finish()
+    }
+}
+
+impl<T: Event> EventPool<T> {
+    /// Default number of idle events kept for reuse
+    pub const DEFAULT_MAX_IDLE: usize = 64;
+
+    /// Create a pool that builds new events with `factory`
+    pub fn new(factory: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+            factory: Box::new(factory),
+            reset: None,
+            max_idle: Self::DEFAULT_MAX_IDLE,
+        }
+    }
+
+    /// Clear events with `reset` before they are reused
+    pub fn with_reset(mut self, reset: impl Fn(&mut T) + Send + Sync + 'static) -> Self {
+        self.reset = Some(Box::new(reset));
+        self
+    }
+
+    /// Keep at most `max_idle` events; extra returned events are dropped
+    pub fn with_max_idle(mut self, max_idle: usize) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    /// Take an event from the pool, creating one if none is idle
+    pub fn acquire(&self) -> Pooled<'_, T> {
+        let event = self.idle.lock().unwrap().pop();
+        Pooled {
+            event: Some(event.unwrap_or_else(|| (self.factory)())),
+            pool: self,
+        }
+    }
+
+    fn release(&self, mut event: T) {
+        if let Some(reset) = &self.reset {
+            reset(&mut event);
+        }
+        let mut idle = self.idle.lock().unwrap();
+        if idle.len() < self.max_idle {
+            idle.push(event);
+        }
+    }
+}
+
+impl<T> EventPool<T> {
+    /// Get the number of idle events ready for reuse
+    pub fn idle(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+}
+
+/// Event borrowed from an [`EventPool`]
+///
+/// Returns the event to the pool on drop.
+#[derive(Debug)]
+pub struct Pooled<'a, T: Event> {
+    event: Option<T>,
+    pool: &'a EventPool<T>,
+}
+
+impl<T: Event> Pooled<'_, T> {
+    /// Take the event out of the pool for good
+    pub fn detach(mut self) -> T {
+        self.event.take().expect("event is present until drop")
+    }
+}
+
+impl<T: Event> Deref for Pooled<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.event.as_ref().expect("event is present until drop")
+    }
+}
+
+impl<T: Event> DerefMut for Pooled<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.event.as_mut().expect("event is present until drop")
+    }
+}
+
+impl<T: Event> Drop for Pooled<'_, T> {
+    fn drop(&mut self) {
+        if let Some(event) = self.event.take() {
+            self.pool.release(event);
+        }
+    }
+}
@@ -15,11 +15,7 @@
 ///     message: String,
 /// }
 ///
-/// impl Event for MyEvent {
-///     fn as_any(&self) -> &dyn std::any::Any {
-///         self
-///     }
-/// }
+/// impl Event for MyEvent {}
 ///
 /// let dispatcher = EventDispatcher::new();
 ///
@@ -36,6 +32,7 @@
 /// }, Priority::Normal);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Priority {
     /// Lowest priority (0)
     Lowest = 0,
@@ -0,0 +1,149 @@
+//! Property-testing support (requires "proptest" feature)
+//!
+//! Strategies for generating random dispatcher configurations and event
+//! sequences, and a checker for the dispatcher's delivery invariants:
+//!
+//! - every live listener receives each event exactly once
+//! - listeners run in descending priority order
+//! - listeners with equal priority run in registration order
+//! - unsubscribed listeners receive nothing
+//!
+//! # Example
+//!
+//! ```rust
+//! use mod_events::property::{check_dispatch_invariants, scenarios};
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&scenarios(), |scenario| check_dispatch_invariants(&scenario))
+//!     .unwrap();
+//! ```
+
+use crate::{Event, EventDispatcher, Priority, TypedListenerId};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use std::sync::{Arc, Mutex};
+
+/// Strategy producing any [`Priority`]
+pub fn priorities() -> impl Strategy<Value = Priority> {
+    prop::sample::select(Priority::all())
+}
+
+impl Arbitrary for Priority {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Priority>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        priorities().boxed()
+    }
+}
+
+/// A single step of a generated scenario
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Subscribe a new listener with the given priority
+    Subscribe(Priority),
+    /// Unsubscribe the listener at this index (modulo the number subscribed so far)
+    Unsubscribe(usize),
+    /// Dispatch a probe event
+    Emit,
+}
+
+/// A generated sequence of operations against a fresh dispatcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scenario {
+    /// Operations applied in order
+    pub operations: Vec<Operation>,
+}
+
+/// Strategy producing a single [`Operation`]
+pub fn operations() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        3 => priorities().prop_map(Operation::Subscribe),
+        1 => any::<usize>().prop_map(Operation::Unsubscribe),
+        2 => Just(Operation::Emit),
+    ]
+}
+
+/// Strategy producing scenarios of up to 64 operations
+pub fn scenarios() -> impl Strategy<Value = Scenario> {
+    prop::collection::vec(operations(), 0..64).prop_map(|operations| Scenario { operations })
+}
+
+/// Event dispatched by [`check_dispatch_invariants`]
+#[derive(Debug, Clone, Copy)]
+pub struct ProbeEvent {
+    /// Sequence number of the emit operation
+    pub sequence: usize,
+}
+
+impl Event for ProbeEvent {}
+
+/// Run a scenario and check the delivery invariants
+///
+/// Returns a [`TestCaseError`] describing the first violation, so it can
+/// be used directly as the body of a proptest case.
+pub fn check_dispatch_invariants(scenario: &Scenario) -> Result<(), TestCaseError> {
+    let dispatcher = EventDispatcher::new();
+    let deliveries: Arc<Mutex<Vec<(usize, usize)>>> = Arc::new(Mutex::new(Vec::new()));
+
+    // (index, priority, id, live)
+    let mut listeners: Vec<(usize, Priority, TypedListenerId<ProbeEvent>, bool)> = Vec::new();
+    let mut sequence = 0;
+
+    for operation in &scenario.operations {
+        match *operation {
+            Operation::Subscribe(priority) => {
+                let index = listeners.len();
+                let log = deliveries.clone();
+                let id = dispatcher.subscribe_with_priority(
+                    move |event: &ProbeEvent| {
+                        log.lock().unwrap().push((event.sequence, index));
+                        Ok(())
+                    },
+                    priority,
+                );
+                listeners.push((index, priority, id, true));
+            }
+            Operation::Unsubscribe(index) => {
+                if listeners.is_empty() {
+                    continue;
+                }
+                let index = index % listeners.len();
+                let (_, _, id, live) = &mut listeners[index];
+                let removed = dispatcher.unsubscribe(*id);
+                prop_assert_eq!(removed, *live, "unsubscribe result for listener {}", index);
+                *live = false;
+            }
+            Operation::Emit => {
+                deliveries.lock().unwrap().clear();
+                let result = dispatcher.dispatch(ProbeEvent { sequence });
+
+                let mut expected: Vec<(usize, Priority)> = listeners
+                    .iter()
+                    .filter(|(_, _, _, live)| *live)
+                    .map(|(index, priority, _, _)| (*index, *priority))
+                    .collect();
+                expected.sort_by_key(|(index, priority)| (std::cmp::Reverse(*priority), *index));
+                let expected: Vec<usize> = expected.into_iter().map(|(index, _)| index).collect();
+
+                let delivered: Vec<usize> = deliveries
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|(seq, index)| {
+                        debug_assert_eq!(*seq, sequence);
+                        *index
+                    })
+                    .collect();
+
+                prop_assert_eq!(result.listener_count(), expected.len());
+                prop_assert_eq!(delivered, expected, "delivery order for event {}", sequence);
+                sequence += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Listener responses and their aggregation
+
+use crate::Priority;
+use std::any::Any;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+/// Reduces listener responses into a single value
+///
+/// Used with [`EventDispatcher::dispatch_aggregate`](crate::EventDispatcher::dispatch_aggregate).
+/// Responses are folded in listener priority order as they are produced,
+/// so no `Vec` of responses is built. Returning [`ControlFlow::Break`]
+/// from [`fold`](Self::fold) stops dispatch; the remaining responders
+/// are not invoked.
+///
+/// `Vec<R>` collects every response. Use [`fold`] for ad-hoc reductions.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Aggregator, Event, EventDispatcher};
+/// use std::ops::ControlFlow;
+///
+/// #[derive(Debug, Clone)]
+/// struct DeleteRequested {
+///     path: String,
+/// }
+///
+/// impl Event for DeleteRequested {}
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Verdict {
+///     Allow,
+///     Deny,
+/// }
+///
+/// /// Deny as soon as any listener denies
+/// struct Veto(Verdict);
+///
+/// impl Aggregator<Verdict> for Veto {
+///     type Output = Verdict;
+///
+///     fn fold(&mut self, response: Verdict) -> ControlFlow<()> {
+///         if response == Verdict::Deny {
+///             self.0 = Verdict::Deny;
+///             return ControlFlow::Break(());
+///         }
+///         ControlFlow::Continue(())
+///     }
+///
+///     fn finish(self) -> Verdict {
+///         self.0
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.respond(|event: &DeleteRequested| {
+///     if event.path.starts_with("/etc") {
+///         Verdict::Deny
+///     } else {
+///         Verdict::Allow
+///     }
+/// });
+/// dispatcher.respond(|_: &DeleteRequested| Verdict::Allow);
+///
+/// let verdict = dispatcher.dispatch_aggregate(
+///     DeleteRequested { path: "/etc/passwd".into() },
+///     Veto(Verdict::Allow),
+/// );
+/// assert_eq!(verdict, Verdict::Deny);
+/// ```
+pub trait Aggregator<R> {
+    /// The reduced value
+    type Output;
+
+    /// Fold one response into the aggregate
+    fn fold(&mut self, response: R) -> ControlFlow<()>;
+
+    /// Produce the reduced value once dispatch is over
+    fn finish(self) -> Self::Output;
+}
+
+impl<R> Aggregator<R> for Vec<R> {
+    type Output = Vec<R>;
+
+    fn fold(&mut self, response: R) -> ControlFlow<()> {
+        self.push(response);
+        ControlFlow::Continue(())
+    }
+
+    fn finish(self) -> Vec<R> {
+        self
+    }
+}
+
+/// Aggregator built from an accumulator and a closure, created by [`fold`]
+#[derive(Debug, Clone)]
+pub struct Fold<A, F> {
+    acc: Option<A>,
+    f: F,
+}
+
+/// Build an [`Aggregator`] from an initial value and a fold closure
+///
+/// The closure returns [`ControlFlow::Break`] with the final value to
+/// stop dispatch early.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{fold, Event, EventDispatcher};
+/// use std::ops::ControlFlow;
+///
+/// #[derive(Debug, Clone)]
+/// struct Quote;
+///
+/// impl Event for Quote {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.respond(|_: &Quote| 120u32);
+/// dispatcher.respond(|_: &Quote| 95u32);
+///
+/// let cheapest = dispatcher.dispatch_aggregate(
+///     Quote,
+///     fold(u32::MAX, |best: u32, price: u32| ControlFlow::Continue(best.min(price))),
+/// );
+/// assert_eq!(cheapest, 95);
+/// ```
+pub fn fold<A, R, F>(init: A, f: F) -> Fold<A, F>
+where
+    F: FnMut(A, R) -> ControlFlow<A, A>,
+{
+    Fold { acc: Some(init), f }
+}
+
+impl<A, R, F> Aggregator<R> for Fold<A, F>
+where
+    F: FnMut(A, R) -> ControlFlow<A, A>,
+{
+    type Output = A;
+
+    fn fold(&mut self, response: R) -> ControlFlow<()> {
+        let acc = self.acc.take().expect("fold called after break");
+        match (self.f)(acc, response) {
+            ControlFlow::Continue(acc) => {
+                self.acc = Some(acc);
+                ControlFlow::Continue(())
+            }
+            ControlFlow::Break(acc) => {
+                self.acc = Some(acc);
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    fn finish(self) -> A {
+        self.acc.expect("accumulator is always restored")
+    }
+}
+
+/// Internal responder storage, erased over the event and response types
+///
+/// `handler` holds a `Box<dyn Fn(&T) -> R + Send + Sync>`; the dispatcher
+/// keys responders by both type ids, so the downcast cannot fail. It is
+/// shared so a dispatch can call it without holding the responder table.
+pub(crate) struct ResponderWrapper {
+    pub(crate) handler: Arc<dyn Any + Send + Sync>,
+    pub(crate) priority: Priority,
+    pub(crate) id: usize,
+    pub(crate) name: &'static str,
+}
+
+impl std::fmt::Debug for ResponderWrapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponderWrapper")
+            .field("priority", &self.priority)
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .finish()
+    }
+}
@@ -1,37 +1,360 @@
 //! Event dispatch result types
 
+use crate::{BlockReason, ListenerId, ListenerPanicked};
+use std::time::Duration;
+
+/// Why a listener failed to handle an event
+///
+/// Every variant carries the failing listener's id and the name of the
+/// event it was handling, so failures can be matched on instead of
+/// inspecting boxed error messages.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, ListenerError};
+///
+/// #[derive(Debug, Clone)]
+/// struct Charge;
+///
+/// impl Event for Charge {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// let billing = dispatcher.subscribe(|_: &Charge| Err("card declined".into()));
+///
+/// let result = dispatcher.dispatch(Charge);
+/// match result.listener_errors()[0] {
+///     ListenerError::Handler { listener, source, .. } => {
+///         assert_eq!(*listener, billing);
+///         assert_eq!(source.to_string(), "card declined");
+///     }
+///     other => panic!("unexpected failure: {other}"),
+/// }
+/// ```
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ListenerError {
+    /// The listener returned an error
+    #[error("listener {listener} failed handling {event}: {source}")]
+    Handler {
+        /// The failing listener
+        listener: ListenerId,
+        /// Name of the event being handled
+        event: &'static str,
+        /// The error returned by the listener
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The listener panicked
+    #[error("listener {listener} panicked handling {event}: {message}")]
+    Panic {
+        /// The failing listener
+        listener: ListenerId,
+        /// Name of the event being handled
+        event: &'static str,
+        /// The panic message, if it was a string
+        message: String,
+    },
+    /// The listener did not finish before the dispatch timed out
+    #[error("listener {listener} did not finish handling {event} within {timeout:?}")]
+    Timeout {
+        /// The failing listener
+        listener: ListenerId,
+        /// Name of the event being handled
+        event: &'static str,
+        /// The timeout that was exceeded
+        timeout: Duration,
+    },
+    /// The listener was cancelled before it finished
+    #[error("listener {listener} was cancelled handling {event}")]
+    Cancelled {
+        /// The failing listener
+        listener: ListenerId,
+        /// Name of the event being handled
+        event: &'static str,
+    },
+    /// The listener was not invoked because its circuit breaker is open
+    #[error("listener {listener} not invoked for {event}: circuit open")]
+    CircuitOpen {
+        /// The failing listener
+        listener: ListenerId,
+        /// Name of the event being handled
+        event: &'static str,
+    },
+    /// The async listener cannot run in a synchronous dispatch
+    ///
+    /// Reported when the dispatcher's [`AsyncBridge`](crate::AsyncBridge)
+    /// is `Error`.
+    #[error("async listener {listener} cannot run in a synchronous dispatch of {event}")]
+    AsyncUnsupported {
+        /// The failing listener
+        listener: ListenerId,
+        /// Name of the event being handled
+        event: &'static str,
+    },
+}
+
+impl ListenerError {
+    /// Record an error returned by a listener, recognising caught panics
+    pub(crate) fn from_listener(
+        listener: ListenerId,
+        event: &'static str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    ) -> Self {
+        match source.downcast::<ListenerPanicked>() {
+            Ok(panicked) => ListenerError::Panic {
+                listener,
+                event,
+                message: panicked.0,
+            },
+            Err(source) => ListenerError::Handler {
+                listener,
+                event,
+                source,
+            },
+        }
+    }
+
+    /// Get the id of the failing listener
+    pub fn listener(&self) -> ListenerId {
+        match self {
+            ListenerError::Handler { listener, .. }
+            | ListenerError::Panic { listener, .. }
+            | ListenerError::Timeout { listener, .. }
+            | ListenerError::Cancelled { listener, .. }
+            | ListenerError::CircuitOpen { listener, .. }
+            | ListenerError::AsyncUnsupported { listener, .. } => *listener,
+        }
+    }
+
+    /// Get the name of the event being handled
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ListenerError::Handler { event, .. }
+            | ListenerError::Panic { event, .. }
+            | ListenerError::Timeout { event, .. }
+            | ListenerError::Cancelled { event, .. }
+            | ListenerError::CircuitOpen { event, .. }
+            | ListenerError::AsyncUnsupported { event, .. } => event,
+        }
+    }
+
+    /// Get the error returned by the listener, if it returned one
+    pub fn handler_error(&self) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        match self {
+            ListenerError::Handler { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Convert into a boxed error
+    ///
+    /// Handler errors are returned exactly as the listener produced them,
+    /// so existing code that downcasts boxed errors keeps working.
+    pub fn into_boxed(self) -> Box<dyn std::error::Error + Send + Sync> {
+        match self {
+            ListenerError::Handler { source, .. } => source,
+            other => Box::new(other),
+        }
+    }
+}
+
+/// What happened to a single listener during dispatch
+///
+/// Yielded alongside each listener's id when iterating a
+/// [`DispatchResult`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Outcome<'a> {
+    /// The listener handled the event successfully
+    Success,
+    /// The listener returned an error
+    Error(&'a (dyn std::error::Error + Send + Sync + 'static)),
+    /// The listener panicked with this message
+    Panic(&'a str),
+    /// The listener did not finish before the dispatch timed out
+    TimedOut(Duration),
+    /// The listener was cancelled before it finished
+    Cancelled,
+    /// The listener was not invoked because its circuit breaker is open
+    CircuitOpen,
+    /// The async listener could not run in a synchronous dispatch
+    AsyncUnsupported,
+    /// The listener was never invoked
+    Skipped,
+}
+
+impl Outcome<'_> {
+    /// Check whether the listener handled the event successfully
+    pub fn is_success(&self) -> bool {
+        matches!(self, Outcome::Success)
+    }
+
+    fn from_result(result: &Result<(), ListenerError>) -> Outcome<'_> {
+        match result {
+            Ok(()) => Outcome::Success,
+            Err(ListenerError::Handler { source, .. }) => Outcome::Error(source.as_ref()),
+            Err(ListenerError::Panic { message, .. }) => Outcome::Panic(message),
+            Err(ListenerError::Timeout { timeout, .. }) => Outcome::TimedOut(*timeout),
+            Err(ListenerError::Cancelled { .. }) => Outcome::Cancelled,
+            Err(ListenerError::CircuitOpen { .. }) => Outcome::CircuitOpen,
+            Err(ListenerError::AsyncUnsupported { .. }) => Outcome::AsyncUnsupported,
+        }
+    }
+}
+
+/// Iterator over the per-listener outcomes of a dispatch
+///
+/// Created by [`DispatchResult::iter`]. Invoked listeners come first, in
+/// the order they ran, followed by skipped listeners.
+#[derive(Debug, Clone)]
+pub struct Outcomes<'a> {
+    results: std::slice::Iter<'a, (ListenerId, &'static str, Result<(), ListenerError>)>,
+    skipped: std::slice::Iter<'a, ListenerId>,
+}
+
+impl<'a> Iterator for Outcomes<'a> {
+    type Item = (ListenerId, Outcome<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((id, _, result)) = self.results.next() {
+            return Some((*id, Outcome::from_result(result)));
+        }
+        self.skipped.next().map(|id| (*id, Outcome::Skipped))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.results.len() + self.skipped.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Outcomes<'_> {}
+
 /// Result of event dispatch
 ///
 /// Contains information about the success or failure of event dispatch,
 /// including any errors that occurred during listener execution.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, Outcome};
+///
+/// #[derive(Debug, Clone)]
+/// struct OrderPlaced;
+///
+/// impl Event for OrderPlaced {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// let reserve = dispatcher.subscribe(|_: &OrderPlaced| Ok(()));
+/// let charge = dispatcher.subscribe(|_: &OrderPlaced| Err("card declined".into()));
+///
+/// let result = dispatcher.dispatch(OrderPlaced);
+/// for (id, outcome) in &result {
+///     if let Outcome::Error(error) = outcome {
+///         assert_eq!(id, charge);
+///         println!("compensating for {}: {}", id, error);
+///     }
+/// }
+/// assert!(matches!(result.iter().next(), Some((id, Outcome::Success)) if id == reserve));
+/// ```
 #[derive(Debug)]
 pub struct DispatchResult {
-    results: Vec<Result<(), Box<dyn std::error::Error + Send + Sync>>>,
-    blocked: bool,
+    results: Vec<(ListenerId, &'static str, Result<(), ListenerError>)>,
+    blocked: Option<BlockReason>,
     listener_count: usize,
+    skipped: Vec<ListenerId>,
+    stopped_by: Option<ListenerId>,
+    cancelled: bool,
+    undelivered: bool,
+    timed_out: Vec<ListenerId>,
+    async_skipped: usize,
 }
 
 impl DispatchResult {
-    pub(crate) fn new(results: Vec<Result<(), Box<dyn std::error::Error + Send + Sync>>>) -> Self {
+    pub(crate) fn new(results: Vec<(ListenerId, &'static str, Result<(), ListenerError>)>) -> Self {
         let listener_count = results.len();
         Self {
             results,
-            blocked: false,
+            blocked: None,
             listener_count,
+            skipped: Vec::new(),
+            stopped_by: None,
+            cancelled: false,
+            undelivered: false,
+            timed_out: Vec::new(),
+            async_skipped: 0,
         }
     }
 
-    pub(crate) fn blocked() -> Self {
+    pub(crate) fn with_skipped(mut self, skipped: Vec<ListenerId>) -> Self {
+        self.skipped = skipped;
+        self
+    }
+
+    /// Combine the outcomes of dispatching one event to several dispatchers
+    pub(crate) fn combine(mut self, other: DispatchResult) -> Self {
+        self.results.extend(other.results);
+        self.blocked = self.blocked.or(other.blocked);
+        self.listener_count += other.listener_count;
+        self.skipped.extend(other.skipped);
+        self.stopped_by = self.stopped_by.or(other.stopped_by);
+        self.cancelled |= other.cancelled;
+        self.undelivered &= other.undelivered;
+        self.timed_out.extend(other.timed_out);
+        self.async_skipped += other.async_skipped;
+        self
+    }
+
+    pub(crate) fn with_undelivered(mut self, undelivered: bool) -> Self {
+        self.undelivered = undelivered;
+        self
+    }
+
+    pub(crate) fn with_cancelled(mut self, cancelled: bool) -> Self {
+        self.cancelled = cancelled;
+        self
+    }
+
+    pub(crate) fn with_stopped_by(mut self, stopped_by: Option<ListenerId>) -> Self {
+        self.stopped_by = stopped_by;
+        self
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn with_timed_out(mut self, timed_out: Vec<ListenerId>) -> Self {
+        self.timed_out = timed_out;
+        self
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn with_async_skipped(mut self, async_skipped: usize) -> Self {
+        self.async_skipped = async_skipped;
+        self
+    }
+
+    pub(crate) fn blocked(reason: BlockReason) -> Self {
         Self {
             results: Vec::new(),
-            blocked: true,
+            blocked: Some(reason),
             listener_count: 0,
+            skipped: Vec::new(),
+            stopped_by: None,
+            cancelled: false,
+            undelivered: false,
+            timed_out: Vec::new(),
+            async_skipped: 0,
         }
     }
 
     /// Check if the event was blocked by middleware
     pub fn is_blocked(&self) -> bool {
-        self.blocked
+        self.blocked.is_some()
+    }
+
+    /// Get why middleware blocked the event, if it was blocked
+    pub fn block_reason(&self) -> Option<&BlockReason> {
+        self.blocked.as_ref()
     }
 
     /// Get the total number of listeners that were called
@@ -39,32 +362,180 @@ impl DispatchResult {
         self.listener_count
     }
 
+    /// Get the listeners that were never invoked
+    ///
+    /// Listeners are skipped when a time budget is exceeded, when their
+    /// [`RateLimit`](crate::RateLimit) is reached, or, under
+    /// [`ErrorPolicy::StopOnError`](crate::ErrorPolicy::StopOnError), after
+    /// an earlier listener fails, or after a listener consumes the event
+    /// with [`Handled::Stop`](crate::Handled::Stop).
+    pub fn skipped(&self) -> &[ListenerId] {
+        &self.skipped
+    }
+
+    /// Check if a [`Delivery::Unicast`](crate::Delivery::Unicast) event found no listener to consume it
+    pub fn is_undelivered(&self) -> bool {
+        self.undelivered
+    }
+
+    /// Check if the event was cancelled through its [`Cancellation`](crate::Cancellation)
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Get the listener that consumed the event with [`Handled::Stop`](crate::Handled::Stop)
+    pub fn stopped_by(&self) -> Option<ListenerId> {
+        self.stopped_by
+    }
+
+    /// Get the listeners that had not finished when the dispatch timed out
+    pub fn timed_out(&self) -> &[ListenerId] {
+        &self.timed_out
+    }
+
+    /// Get how many async listeners a synchronous dispatch left out
+    ///
+    /// Non-zero when async listeners exist for the event but the
+    /// dispatcher's [`AsyncBridge`](crate::AsyncBridge) could not run them.
+    pub fn async_skipped(&self) -> usize {
+        self.async_skipped
+    }
+
     /// Get the number of successful handlers
     pub fn success_count(&self) -> usize {
-        self.results.iter().filter(|r| r.is_ok()).count()
+        self.results.iter().filter(|(_, _, r)| r.is_ok()).count()
     }
 
     /// Get the number of failed handlers
     pub fn error_count(&self) -> usize {
-        self.results.iter().filter(|r| r.is_err()).count()
+        self.results.iter().filter(|(_, _, r)| r.is_err()).count()
+    }
+
+    /// Get the id and name of every listener that handled the event successfully
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Alert;
+    ///
+    /// impl Event for Alert {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// let email = dispatcher.subscribe(|_: &Alert| Ok(()));
+    /// let sms = dispatcher.subscribe(|_: &Alert| Err("gateway down".into()));
+    ///
+    /// let result = dispatcher.dispatch(Alert);
+    /// let delivered: Vec<_> = result.succeeded().into_iter().map(|(id, _)| id).collect();
+    /// assert_eq!(delivered, vec![email]);
+    /// assert_eq!(result.failed()[0].0, sms);
+    /// ```
+    pub fn succeeded(&self) -> Vec<(ListenerId, &'static str)> {
+        self.results
+            .iter()
+            .filter(|(_, _, r)| r.is_ok())
+            .map(|(id, name, _)| (*id, *name))
+            .collect()
+    }
+
+    /// Get the id and name of every listener that failed to handle the event
+    pub fn failed(&self) -> Vec<(ListenerId, &'static str)> {
+        self.results
+            .iter()
+            .filter(|(_, _, r)| r.is_err())
+            .map(|(id, name, _)| (*id, *name))
+            .collect()
     }
 
     /// Get all errors that occurred during dispatch
-    pub fn errors(&self) -> Vec<&(dyn std::error::Error + Send + Sync)> {
+    ///
+    /// Errors returned by listeners are yielded as-is; other failures are
+    /// yielded as their [`ListenerError`]. Use
+    /// [`listener_errors`](Self::listener_errors) for structured access.
+    pub fn errors(&self) -> Vec<&(dyn std::error::Error + Send + Sync + 'static)> {
         self.results
             .iter()
-            .filter_map(|r| r.as_ref().err())
-            .map(|e| e.as_ref())
+            .filter_map(|(_, _, r)| r.as_ref().err())
+            .map(|e| match e.handler_error() {
+                Some(source) => source,
+                None => e as &(dyn std::error::Error + Send + Sync + 'static),
+            })
+            .collect()
+    }
+
+    /// Get all failures that occurred during dispatch
+    pub fn listener_errors(&self) -> Vec<&ListenerError> {
+        self.results
+            .iter()
+            .filter_map(|(_, _, r)| r.as_ref().err())
             .collect()
     }
 
     /// Check if all handlers succeeded
     pub fn all_succeeded(&self) -> bool {
-        !self.blocked && self.results.iter().all(|r| r.is_ok())
+        self.blocked.is_none() && self.results.iter().all(|(_, _, r)| r.is_ok())
     }
 
     /// Check if any handlers failed
     pub fn has_errors(&self) -> bool {
-        self.results.iter().any(|r| r.is_err())
+        self.results.iter().any(|(_, _, r)| r.is_err())
+    }
+
+    /// Consume the result, returning the owned failures
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mod_events::{Event, EventDispatcher};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Upload;
+    ///
+    /// impl Event for Upload {}
+    ///
+    /// let dispatcher = EventDispatcher::new();
+    /// dispatcher.subscribe(|_: &Upload| Err("bucket unavailable".into()));
+    ///
+    /// let errors = dispatcher.dispatch(Upload).into_errors();
+    /// std::thread::spawn(move || {
+    ///     for error in errors {
+    ///         eprintln!("{}", error);
+    ///     }
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// ```
+    pub fn into_errors(self) -> Vec<ListenerError> {
+        self.results
+            .into_iter()
+            .filter_map(|(_, _, r)| r.err())
+            .collect()
+    }
+
+    /// Consume the result, returning each invoked listener's id and result
+    pub fn into_results(self) -> Vec<(ListenerId, Result<(), ListenerError>)> {
+        self.results
+            .into_iter()
+            .map(|(id, _, result)| (id, result))
+            .collect()
+    }
+
+    /// Iterate over each listener's id and outcome
+    pub fn iter(&self) -> Outcomes<'_> {
+        Outcomes {
+            results: self.results.iter(),
+            skipped: self.skipped.iter(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a DispatchResult {
+    type Item = (ListenerId, Outcome<'a>);
+    type IntoIter = Outcomes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
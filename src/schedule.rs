@@ -0,0 +1,197 @@
+//! Delayed and periodic dispatch driven by the dispatcher's clock
+
+use crate::sync::Mutex;
+use crate::{Event, EventDispatcher};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Handle for cancelling a scheduled dispatch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScheduleId(u64);
+
+impl fmt::Display for ScheduleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "schedule#{}", self.0)
+    }
+}
+
+enum Job {
+    Once(Box<dyn Event>),
+    Every {
+        period: Duration,
+        make: Box<dyn Fn() -> Box<dyn Event> + Send + Sync>,
+    },
+}
+
+#[derive(Default)]
+struct ScheduleState {
+    next_id: u64,
+    /// Jobs keyed by due time, ties broken by scheduling order
+    jobs: BTreeMap<(Instant, u64), Job>,
+}
+
+/// Dispatches events after a delay or periodically
+///
+/// Due times are taken from the dispatcher's [`Clock`](crate::Clock), and
+/// nothing fires on its own: [`run_due`](Self::run_due) dispatches every
+/// event that is due, in due order. Call it from a timer or frame loop in
+/// production; in tests, pair the dispatcher with a
+/// [`TestClock`](crate::TestClock) and advance it manually to fire events
+/// deterministically without sleeping.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, Scheduler, TestClock};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone)]
+/// struct Heartbeat;
+///
+/// impl Event for Heartbeat {}
+///
+/// #[derive(Debug, Clone)]
+/// struct SessionExpired;
+///
+/// impl Event for SessionExpired {}
+///
+/// let clock = TestClock::new();
+/// let dispatcher = Arc::new(EventDispatcher::with_clock(clock.clone()));
+/// let beats = Arc::new(AtomicUsize::new(0));
+/// let counter = beats.clone();
+/// dispatcher.on(move |_: &Heartbeat| {
+///     counter.fetch_add(1, Ordering::SeqCst);
+/// });
+///
+/// let scheduler = Scheduler::new(dispatcher.clone());
+/// scheduler.dispatch_every(Duration::from_secs(10), || Heartbeat);
+/// let expiry = scheduler.dispatch_after(Duration::from_secs(60), SessionExpired);
+///
+/// clock.advance(Duration::from_secs(35));
+/// assert_eq!(scheduler.run_due(), 3);
+/// assert_eq!(beats.load(Ordering::SeqCst), 3);
+///
+/// assert!(scheduler.cancel(expiry));
+/// clock.advance(Duration::from_secs(30));
+/// assert_eq!(scheduler.run_due(), 3);
+/// ```
+pub struct Scheduler {
+    dispatcher: Arc<EventDispatcher>,
+    state: Mutex<ScheduleState>,
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("pending", &self.pending())
+            .field("next_due", &self.next_due())
+            .finish()
+    }
+}
+
+impl Scheduler {
+    /// Create a scheduler dispatching through `dispatcher`
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self {
+            dispatcher,
+            state: Mutex::new(ScheduleState::default()),
+        }
+    }
+
+    /// Get the dispatcher events are dispatched through
+    pub fn dispatcher(&self) -> &Arc<EventDispatcher> {
+        &self.dispatcher
+    }
+
+    /// Dispatch `event` once `delay` has elapsed
+    pub fn dispatch_after<T: Event>(&self, delay: Duration, event: T) -> ScheduleId {
+        let due = self.dispatcher.clock().now() + delay;
+        self.insert(due, Job::Once(Box::new(event)))
+    }
+
+    /// Dispatch an event built by `make` every `period`
+    ///
+    /// The first event is due one period from now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn dispatch_every<T, F>(&self, period: Duration, make: F) -> ScheduleId
+    where
+        T: Event,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        assert!(!period.is_zero(), "schedule period must be non-zero");
+        let due = self.dispatcher.clock().now() + period;
+        let make: Box<dyn Fn() -> Box<dyn Event> + Send + Sync> =
+            Box::new(move || Box::new(make()));
+        self.insert(due, Job::Every { period, make })
+    }
+
+    /// Cancel a scheduled dispatch
+    ///
+    /// Returns `false` if it already fired or was cancelled.
+    pub fn cancel(&self, id: ScheduleId) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let key = state.jobs.keys().find(|(_, job)| *job == id.0).copied();
+        key.is_some_and(|key| state.jobs.remove(&key).is_some())
+    }
+
+    /// Get the number of scheduled dispatches
+    pub fn pending(&self) -> usize {
+        self.state.lock().unwrap().jobs.len()
+    }
+
+    /// Get when the next dispatch is due
+    pub fn next_due(&self) -> Option<Instant> {
+        let state = self.state.lock().unwrap();
+        state.jobs.keys().next().map(|(due, _)| *due)
+    }
+
+    /// Dispatch every event that is due, returning how many were dispatched
+    ///
+    /// Periodic schedules that fell behind fire once for each missed
+    /// period. Events scheduled by listeners fire in the same call if
+    /// they are already due.
+    pub fn run_due(&self) -> usize {
+        let now = self.dispatcher.clock().now();
+        let mut dispatched = 0;
+        loop {
+            let event = {
+                let mut state = self.state.lock().unwrap();
+                let Some(entry) = state.jobs.first_entry() else {
+                    break;
+                };
+                if entry.key().0 > now {
+                    break;
+                }
+                let ((due, id), job) = entry.remove_entry();
+                match job {
+                    Job::Once(event) => event,
+                    Job::Every { period, make } => {
+                        let event = make();
+                        state
+                            .jobs
+                            .insert((due + period, id), Job::Every { period, make });
+                        event
+                    }
+                }
+            };
+            self.dispatcher.dispatch_boxed(event);
+            dispatched += 1;
+        }
+        dispatched
+    }
+
+    fn insert(&self, due: Instant, job: Job) -> ScheduleId {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.jobs.insert((due, id), job);
+        ScheduleId(id)
+    }
+}
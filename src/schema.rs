@@ -0,0 +1,157 @@
+//! Event schema registry
+
+use crate::Event;
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+/// Self-description of an event type
+///
+/// Gives an event a stable name and version that do not depend on its
+/// Rust path, so serialized and networked events can be identified across
+/// binaries.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventSchema, SchemaRegistry};
+///
+/// #[derive(Debug, Clone)]
+/// struct OrderPlaced {
+///     order_id: u64,
+/// }
+///
+/// impl Event for OrderPlaced {}
+///
+/// impl EventSchema for OrderPlaced {
+///     const NAME: &'static str = "orders.placed";
+///     const VERSION: u32 = 2;
+///     const JSON_SCHEMA: Option<&'static str> =
+///         Some(r#"{"type":"object","properties":{"order_id":{"type":"integer"}}}"#);
+/// }
+///
+/// let mut registry = SchemaRegistry::new();
+/// registry.register::<OrderPlaced>().unwrap();
+///
+/// let info = registry.get("orders.placed").unwrap();
+/// assert_eq!(info.version, 2);
+/// assert!(info.json_schema.is_some());
+/// ```
+pub trait EventSchema: Event {
+    /// Stable, path-independent name of the event
+    const NAME: &'static str;
+
+    /// Current schema version, starting at 1
+    const VERSION: u32 = 1;
+
+    /// Optional JSON Schema describing the serialized form
+    const JSON_SCHEMA: Option<&'static str> = None;
+}
+
+/// Registered description of an event type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaInfo {
+    /// Stable name from [`EventSchema::NAME`]
+    pub name: &'static str,
+    /// Schema version from [`EventSchema::VERSION`]
+    pub version: u32,
+    /// JSON Schema from [`EventSchema::JSON_SCHEMA`]
+    pub json_schema: Option<&'static str>,
+    /// Rust type name of the event
+    pub type_name: &'static str,
+    /// Type ID of the event
+    pub type_id: TypeId,
+}
+
+impl SchemaInfo {
+    /// Describe an event type
+    pub fn of<T: EventSchema>() -> Self {
+        Self {
+            name: T::NAME,
+            version: T::VERSION,
+            json_schema: T::JSON_SCHEMA,
+            type_name: std::any::type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+        }
+    }
+}
+
+/// Errors raised while registering schemas
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SchemaError {
+    /// Two different types claimed the same stable name
+    #[error("event name `{name}` is already registered by {existing}")]
+    NameConflict {
+        /// The contested name
+        name: &'static str,
+        /// Type name of the type already registered
+        existing: &'static str,
+    },
+}
+
+/// Runtime registry of the event schemas a binary understands
+///
+/// Schemas are listed in name order, so the registry can be published as
+/// a manifest of supported events.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    by_name: BTreeMap<&'static str, SchemaInfo>,
+    by_type: BTreeMap<TypeId, &'static str>,
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an event type
+    ///
+    /// Registering the same type again is a no-op; registering a different
+    /// type under an existing name fails.
+    pub fn register<T: EventSchema>(&mut self) -> Result<&SchemaInfo, SchemaError> {
+        let info = SchemaInfo::of::<T>();
+        if let Some(existing) = self.by_name.get(info.name) {
+            if existing.type_id != info.type_id {
+                return Err(SchemaError::NameConflict {
+                    name: info.name,
+                    existing: existing.type_name,
+                });
+            }
+        }
+
+        self.by_type.insert(info.type_id, info.name);
+        Ok(self.by_name.entry(info.name).or_insert(info))
+    }
+
+    /// Look up a schema by stable name
+    pub fn get(&self, name: &str) -> Option<&SchemaInfo> {
+        self.by_name.get(name)
+    }
+
+    /// Look up the schema of an event type
+    pub fn get_type<T: Event>(&self) -> Option<&SchemaInfo> {
+        self.get_type_id(TypeId::of::<T>())
+    }
+
+    /// Look up a schema by type ID
+    pub fn get_type_id(&self, type_id: TypeId) -> Option<&SchemaInfo> {
+        self.by_type
+            .get(&type_id)
+            .and_then(|name| self.by_name.get(name))
+    }
+
+    /// Iterate over every registered schema in name order
+    pub fn iter(&self) -> impl Iterator<Item = &SchemaInfo> {
+        self.by_name.values()
+    }
+
+    /// Get the number of registered schemas
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    /// Check whether the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+}
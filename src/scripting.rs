@@ -0,0 +1,148 @@
+//! Scripting host bindings (requires "rhai" feature)
+//!
+//! Lets embedded [Rhai](https://rhai.rs) scripts subscribe to and emit
+//! [`DynamicEvent`]s. Each loaded script gets its own [`ScriptScope`];
+//! dropping the scope removes every listener the script registered, so
+//! mods can be unloaded or hot-reloaded cleanly.
+//!
+//! Scripts see two functions:
+//!
+//! - `on(name, callback)` subscribes `callback(name, payload)` to events
+//!   named `name`, where `payload` is the payload decoded as UTF-8
+//! - `emit(name, payload)` emits an event with a string or blob payload
+//!
+//! Subscriptions must be made while the script body runs; callbacks may
+//! emit further events but cannot subscribe.
+
+use crate::{DynamicEvent, EventDispatcher, ListenerId};
+use rhai::{Blob, Engine, EvalAltResult, FnPtr, ParseError};
+use std::sync::{Arc, Mutex};
+
+/// Errors raised while loading a script
+#[derive(Debug, thiserror::Error)]
+pub enum ScriptError {
+    /// The script failed to compile
+    #[error("script failed to compile: {0}")]
+    Compile(#[from] ParseError),
+    /// The script body failed while running
+    #[error("script failed to run: {0}")]
+    Runtime(#[from] Box<EvalAltResult>),
+}
+
+/// Loads scripts against a shared dispatcher
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::scripting::ScriptHost;
+/// use mod_events::{DynamicEvent, EventDispatcher};
+/// use std::sync::Arc;
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// let host = ScriptHost::new(dispatcher.clone());
+///
+/// let scope = host
+///     .load(r#"
+///         on("player.joined", |name, payload| {
+///             emit("chat.message", "welcome " + payload);
+///         });
+///     "#)
+///     .unwrap();
+///
+/// dispatcher.subscribe_named("chat.message", |event: &DynamicEvent| {
+///     assert_eq!(event.payload(), b"welcome alice");
+///     Ok(())
+/// });
+///
+/// dispatcher.emit(DynamicEvent::new("player.joined", b"alice".to_vec()));
+///
+/// // Unloading the script removes its listeners
+/// drop(scope);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScriptHost {
+    dispatcher: Arc<EventDispatcher>,
+}
+
+impl ScriptHost {
+    /// Create a host for the given dispatcher
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+
+    /// Compile and run a script, returning its scope
+    pub fn load(&self, source: &str) -> Result<ScriptScope, ScriptError> {
+        let pending: Arc<Mutex<Vec<(String, FnPtr)>>> = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = Engine::new();
+
+        let registrations = pending.clone();
+        engine.register_fn("on", move |name: &str, callback: FnPtr| {
+            registrations
+                .lock()
+                .unwrap()
+                .push((name.to_string(), callback));
+        });
+
+        let dispatcher = self.dispatcher.clone();
+        engine.register_fn("emit", move |name: &str, payload: &str| {
+            dispatcher.emit(DynamicEvent::new(name, payload.as_bytes().to_vec()));
+        });
+
+        let dispatcher = self.dispatcher.clone();
+        engine.register_fn("emit", move |name: &str, payload: Blob| {
+            dispatcher.emit(DynamicEvent::new(name, payload));
+        });
+
+        let ast = engine.compile(source)?;
+        engine.run_ast(&ast)?;
+
+        let engine = Arc::new(engine);
+        let ast = Arc::new(ast);
+        let registrations = std::mem::take(&mut *pending.lock().unwrap());
+
+        let listeners = registrations
+            .into_iter()
+            .map(|(name, callback)| {
+                let engine = engine.clone();
+                let ast = ast.clone();
+                self.dispatcher
+                    .subscribe_named(name, move |event: &DynamicEvent| {
+                        let payload = String::from_utf8_lossy(event.payload()).into_owned();
+                        callback
+                            .call::<()>(&engine, &ast, (event.name().to_string(), payload))
+                            .map_err(|e| e.to_string().into())
+                    })
+            })
+            .map(ListenerId::from)
+            .collect();
+
+        Ok(ScriptScope {
+            dispatcher: self.dispatcher.clone(),
+            listeners,
+        })
+    }
+}
+
+/// Listeners registered by one loaded script
+///
+/// Dropping the scope unsubscribes all of them.
+#[derive(Debug)]
+pub struct ScriptScope {
+    dispatcher: Arc<EventDispatcher>,
+    listeners: Vec<ListenerId>,
+}
+
+impl ScriptScope {
+    /// Get the ids of the listeners this script registered
+    pub fn listeners(&self) -> &[ListenerId] {
+        &self.listeners
+    }
+}
+
+impl Drop for ScriptScope {
+    fn drop(&mut self) {
+        for id in self.listeners.drain(..) {
+            self.dispatcher.unsubscribe(id);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Tower integration (requires "tower" feature)
+//!
+//! [`LifecycleLayer`] emits [`RequestStarted`] and [`RequestCompleted`]
+//! events for every HTTP request a service handles. [`GateLayer`] rejects
+//! requests whose [`RequestStarted`] event is blocked by dispatcher
+//! middleware, so authorization or rate limiting written as middleware can
+//! guard a service.
+
+use crate::{BlockReason, Event, EventDispatcher};
+use http::{Method, Request, Response, StatusCode, Uri};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower_layer::Layer;
+use tower_service::Service;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Emitted when a service starts handling a request
+#[derive(Debug, Clone)]
+pub struct RequestStarted {
+    /// Request method
+    pub method: Method,
+    /// Request URI
+    pub uri: Uri,
+}
+
+impl Event for RequestStarted {}
+
+/// Emitted when a service finishes handling a request
+#[derive(Debug, Clone)]
+pub struct RequestCompleted {
+    /// Request method
+    pub method: Method,
+    /// Request URI
+    pub uri: Uri,
+    /// Response status, or `None` if the service returned an error
+    pub status: Option<StatusCode>,
+    /// Time from receiving the request to the response
+    pub latency: Duration,
+}
+
+impl Event for RequestCompleted {}
+
+/// Layer emitting request lifecycle events into a dispatcher
+///
+/// # Example
+///
+/// ```rust
+/// use http::{Request, Response, StatusCode};
+/// use mod_events::service::{LifecycleLayer, RequestCompleted};
+/// use mod_events::EventDispatcher;
+/// use std::convert::Infallible;
+/// use std::sync::Arc;
+/// use tower::{service_fn, Layer, ServiceExt};
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// dispatcher.on(|done: &RequestCompleted| {
+///     println!("{} {} -> {:?} in {:?}", done.method, done.uri, done.status, done.latency);
+/// });
+///
+/// let service = LifecycleLayer::new(dispatcher.clone()).layer(service_fn(|_: Request<()>| async {
+///     Ok::<_, Infallible>(Response::new(()))
+/// }));
+///
+/// let response = service.oneshot(Request::get("/health").body(()).unwrap()).await.unwrap();
+/// assert_eq!(response.status(), StatusCode::OK);
+/// assert_eq!(dispatcher.metrics().len(), 2);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct LifecycleLayer {
+    dispatcher: Arc<EventDispatcher>,
+}
+
+impl LifecycleLayer {
+    /// Create a layer emitting into `dispatcher`
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
+
+impl<S> Layer<S> for LifecycleLayer {
+    type Service = LifecycleService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LifecycleService {
+            inner,
+            dispatcher: self.dispatcher.clone(),
+        }
+    }
+}
+
+/// Service produced by [`LifecycleLayer`]
+#[derive(Debug, Clone)]
+pub struct LifecycleService<S> {
+    inner: S,
+    dispatcher: Arc<EventDispatcher>,
+}
+
+impl<S, B, R> Service<Request<B>> for LifecycleService<S>
+where
+    S: Service<Request<B>, Response = Response<R>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let method = request.method().clone();
+        let uri = request.uri().clone();
+        let dispatcher = self.dispatcher.clone();
+        let started = dispatcher.clock().now();
+        dispatcher.emit(RequestStarted {
+            method: method.clone(),
+            uri: uri.clone(),
+        });
+
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let result = response.await;
+            dispatcher.emit(RequestCompleted {
+                method,
+                uri,
+                status: result.as_ref().ok().map(|response| response.status()),
+                latency: dispatcher.clock().now().saturating_duration_since(started),
+            });
+            result
+        })
+    }
+}
+
+/// Error returned by [`GateService`]
+#[derive(Debug, thiserror::Error)]
+pub enum GateError<E> {
+    /// Dispatcher middleware blocked the request
+    #[error("request {0}")]
+    Blocked(BlockReason),
+    /// The inner service failed
+    #[error(transparent)]
+    Service(E),
+}
+
+/// Layer rejecting requests blocked by dispatcher middleware
+///
+/// Each request is dispatched as a [`RequestStarted`] event; if middleware
+/// blocks it, the service fails with [`GateError::Blocked`] without calling
+/// the inner service.
+///
+/// # Example
+///
+/// ```rust
+/// use http::{Method, Request, Response};
+/// use mod_events::service::{GateError, GateLayer, RequestStarted};
+/// use mod_events::{Event, EventDispatcher};
+/// use std::convert::Infallible;
+/// use std::sync::Arc;
+/// use tower::{service_fn, Layer, ServiceExt};
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// dispatcher.add_middleware(|event: &dyn Event| {
+///     event
+///         .downcast_ref::<RequestStarted>()
+///         .map_or(true, |request| request.method != Method::DELETE)
+/// });
+///
+/// let service = GateLayer::new(dispatcher).layer(service_fn(|_: Request<()>| async {
+///     Ok::<_, Infallible>(Response::new(()))
+/// }));
+///
+/// let denied = service
+///     .clone()
+///     .oneshot(Request::delete("/users/1").body(()).unwrap())
+///     .await;
+/// assert!(matches!(denied, Err(GateError::Blocked(_))));
+///
+/// let allowed = service.oneshot(Request::get("/users/1").body(()).unwrap()).await;
+/// assert!(allowed.is_ok());
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct GateLayer {
+    dispatcher: Arc<EventDispatcher>,
+}
+
+impl GateLayer {
+    /// Create a layer checking requests against `dispatcher`'s middleware
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
+
+impl<S> Layer<S> for GateLayer {
+    type Service = GateService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GateService {
+            inner,
+            dispatcher: self.dispatcher.clone(),
+        }
+    }
+}
+
+/// Service produced by [`GateLayer`]
+#[derive(Debug, Clone)]
+pub struct GateService<S> {
+    inner: S,
+    dispatcher: Arc<EventDispatcher>,
+}
+
+impl<S, B> Service<Request<B>> for GateService<S>
+where
+    S: Service<Request<B>>,
+    S::Future: Send + 'static,
+    S::Error: 'static,
+    S::Response: 'static,
+{
+    type Response = S::Response;
+    type Error = GateError<S::Error>;
+    type Future = BoxFuture<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(GateError::Service)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let result = self.dispatcher.dispatch(RequestStarted {
+            method: request.method().clone(),
+            uri: request.uri().clone(),
+        });
+        if let Some(reason) = result.block_reason() {
+            let reason = reason.clone();
+            return Box::pin(async move { Err(GateError::Blocked(reason)) });
+        }
+
+        let response = self.inner.call(request);
+        Box::pin(async move { response.await.map_err(GateError::Service) })
+    }
+}
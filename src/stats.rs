@@ -0,0 +1,165 @@
+//! Aggregated dispatcher statistics
+
+use crate::sync::{AtomicUsize, Ordering};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Width of one rate bucket
+const BUCKET: Duration = Duration::from_secs(10);
+
+/// Buckets kept, enough for the longest rate window (15 minutes)
+const BUCKETS: usize = 90;
+
+/// Number of event types listed by [`EventDispatcher::stats`](crate::EventDispatcher::stats)
+pub(crate) const TOP_EVENT_TYPES: usize = 5;
+
+/// Dispatch volume of one event type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventVolume {
+    /// Display name of the event type
+    pub name: String,
+    /// Total number of times it has been dispatched
+    pub dispatched: usize,
+}
+
+/// Snapshot of dispatcher activity
+///
+/// Returned by [`EventDispatcher::stats`](crate::EventDispatcher::stats).
+/// Totals cover the dispatcher's lifetime; rates cover the trailing
+/// window, or the uptime if that is shorter. `Display` renders it on one
+/// line for periodic logging.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatcherStats {
+    /// Time since the dispatcher was created, on its clock
+    pub uptime: Duration,
+    /// Events dispatched, including blocked ones
+    pub dispatched: usize,
+    /// Events blocked by middleware
+    pub blocked: usize,
+    /// Listener invocations
+    pub invocations: usize,
+    /// Listener invocations that returned an error
+    pub errors: usize,
+    /// Events dispatched per second over the last minute
+    pub rate_1m: f64,
+    /// Events dispatched per second over the last 5 minutes
+    pub rate_5m: f64,
+    /// Events dispatched per second over the last 15 minutes
+    pub rate_15m: f64,
+    /// Most dispatched event types, busiest first
+    pub top_events: Vec<EventVolume>,
+    /// Listeners currently subscribed across all event types
+    pub listener_count: usize,
+    /// Event types with metrics
+    pub event_types: usize,
+}
+
+impl fmt::Display for DispatcherStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "uptime={:.0?} dispatched={} blocked={} errors={} rate={:.2}/{:.2}/{:.2} listeners={} types={}",
+            self.uptime,
+            self.dispatched,
+            self.blocked,
+            self.errors,
+            self.rate_1m,
+            self.rate_5m,
+            self.rate_15m,
+            self.listener_count,
+            self.event_types,
+        )?;
+        for (index, event) in self.top_events.iter().enumerate() {
+            let separator = if index == 0 { " top=" } else { "," };
+            write!(f, "{}{}:{}", separator, event.name, event.dispatched)?;
+        }
+        Ok(())
+    }
+}
+
+struct RateBucket {
+    /// Bucket number since the tracker started; `usize::MAX` when unused
+    slot: AtomicUsize,
+    count: AtomicUsize,
+}
+
+/// Dispatch counts in ten-second buckets over the last 15 minutes
+///
+/// Buckets are recycled lock-free, so a dispatch racing a bucket reset
+/// may go uncounted; rates are approximate.
+pub(crate) struct DispatchRate {
+    started: Instant,
+    buckets: Box<[RateBucket]>,
+}
+
+impl fmt::Debug for DispatchRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DispatchRate")
+            .field("started", &self.started)
+            .finish()
+    }
+}
+
+impl DispatchRate {
+    pub(crate) fn new(started: Instant) -> Self {
+        Self {
+            started,
+            buckets: (0..BUCKETS)
+                .map(|_| RateBucket {
+                    slot: AtomicUsize::new(usize::MAX),
+                    count: AtomicUsize::new(0),
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn started(&self) -> Instant {
+        self.started
+    }
+
+    fn slot(&self, at: Instant) -> usize {
+        (at.saturating_duration_since(self.started).as_secs() / BUCKET.as_secs()) as usize
+    }
+
+    /// Count a dispatch that started at `at`
+    pub(crate) fn record(&self, at: Instant) {
+        let slot = self.slot(at);
+        let bucket = &self.buckets[slot % BUCKETS];
+        let current = bucket.slot.load(Ordering::Relaxed);
+        if current != slot
+            && bucket
+                .slot
+                .compare_exchange(current, slot, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            bucket.count.store(0, Ordering::Relaxed);
+        }
+        bucket.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Get dispatches per second over the trailing `window`
+    pub(crate) fn per_second(&self, now: Instant, window: Duration) -> f64 {
+        let uptime = now.saturating_duration_since(self.started);
+        let newest = self.slot(now);
+        let span = (window.as_secs() / BUCKET.as_secs()) as usize;
+        let oldest = (newest + 1).saturating_sub(span);
+        let count: usize = self
+            .buckets
+            .iter()
+            .filter(|bucket| {
+                let slot = bucket.slot.load(Ordering::Relaxed);
+                (oldest..=newest).contains(&slot)
+            })
+            .map(|bucket| bucket.count.load(Ordering::Relaxed))
+            .sum();
+        // Whole buckets before the current one, plus the part of it elapsed
+        let covered = (BUCKET * (newest - oldest) as u32
+            + Duration::from_secs(uptime.as_secs() % BUCKET.as_secs())
+            + Duration::from_nanos(uptime.subsec_nanos().into()))
+        .min(uptime);
+        if covered.is_zero() {
+            return 0.0;
+        }
+        count as f64 / covered.as_secs_f64()
+    }
+}
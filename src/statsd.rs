@@ -0,0 +1,134 @@
+//! StatsD metrics export (requires "statsd" feature)
+//!
+//! [`StatsdObserver`] pushes a counter and a timer per dispatch over UDP,
+//! tagged DogStatsD-style with the event name and outcome, so short-lived
+//! jobs can report to Datadog or any StatsD-compatible agent.
+
+use crate::{display_type_name, DispatchObserver, DispatchResult, Event};
+use std::fmt::Write as _;
+use std::io;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+/// Observer sending dispatch counters and timings to a StatsD endpoint
+///
+/// For every dispatch it sends, in one datagram:
+///
+/// - `<prefix>dispatch.count` counter, tagged `event` and `outcome`
+///   (`success`, `error` or `blocked`)
+/// - `<prefix>dispatch.duration` timer in milliseconds, tagged the same
+/// - `<prefix>listener.errors` counter of failed listeners, when any failed
+///
+/// Send failures are ignored so a missing agent never affects dispatch.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::statsd::StatsdObserver;
+/// use mod_events::{Event, EventDispatcher};
+/// use std::net::UdpSocket;
+///
+/// #[derive(Debug, Clone)]
+/// struct InvoiceSent;
+///
+/// impl Event for InvoiceSent {}
+///
+/// let agent = UdpSocket::bind("127.0.0.1:0").unwrap();
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.add_observer(
+///     StatsdObserver::new(agent.local_addr().unwrap())
+///         .unwrap()
+///         .prefix("billing.")
+///         .tag("env", "test"),
+/// );
+/// dispatcher.on(|_: &InvoiceSent| {});
+/// dispatcher.emit(InvoiceSent);
+///
+/// let mut buffer = [0; 512];
+/// let len = agent.recv(&mut buffer).unwrap();
+/// let packet = std::str::from_utf8(&buffer[..len]).unwrap();
+/// assert!(packet.starts_with(
+///     "billing.dispatch.count:1|c|#env:test,event:InvoiceSent,outcome:success\n"
+/// ));
+/// ```
+#[derive(Debug)]
+pub struct StatsdObserver {
+    socket: UdpSocket,
+    prefix: String,
+    tags: String,
+}
+
+impl StatsdObserver {
+    /// Create an observer sending to the agent at `address`
+    pub fn new(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let address = address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no address given"))?;
+        let local = if address.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            prefix: String::new(),
+            tags: String::new(),
+        })
+    }
+
+    /// Prepend `prefix` to every metric name
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Add a tag sent with every metric
+    pub fn tag(mut self, key: &str, value: &str) -> Self {
+        let _ = write!(self.tags, "{}:{},", key, value);
+        self
+    }
+}
+
+impl DispatchObserver for StatsdObserver {
+    fn on_dispatch(&self, event: &dyn Event, result: &DispatchResult, elapsed: Duration) {
+        let outcome = if result.is_blocked() {
+            "blocked"
+        } else if result.has_errors() {
+            "error"
+        } else {
+            "success"
+        };
+        let tags = format!(
+            "{}event:{},outcome:{}",
+            self.tags,
+            display_type_name(event.event_name()),
+            outcome
+        );
+
+        let mut packet = String::new();
+        let _ = writeln!(packet, "{}dispatch.count:1|c|#{}", self.prefix, tags);
+        let _ = write!(
+            packet,
+            "{}dispatch.duration:{:.3}|ms|#{}",
+            self.prefix,
+            elapsed.as_secs_f64() * 1e3,
+            tags
+        );
+        if result.error_count() > 0 {
+            let _ = write!(
+                packet,
+                "\n{}listener.errors:{}|c|#{}",
+                self.prefix,
+                result.error_count(),
+                tags
+            );
+        }
+
+        let _ = self.socket.send(packet.as_bytes());
+    }
+}
@@ -0,0 +1,111 @@
+//! Subscriptions removed when their guard is dropped
+
+use crate::{EventDispatcher, ListenerId};
+use std::fmt;
+use std::sync::{Arc, Weak};
+
+/// Unsubscribes a listener when dropped
+///
+/// Returned by [`EventDispatcher::subscribe_scoped`]; wrap the id of any
+/// other subscription with [`new`](Self::new). Store the guard in the
+/// component that owns the listener and the listener goes away with it.
+/// The guard holds the dispatcher weakly, so it does not keep the
+/// dispatcher alive. It may be dropped anywhere, including inside a
+/// listener during a dispatch.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, SubscriptionGuard};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone)]
+/// struct Resized;
+///
+/// impl Event for Resized {}
+///
+/// struct Panel {
+///     _on_resize: SubscriptionGuard,
+/// }
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// let panel = Panel {
+///     _on_resize: dispatcher.subscribe_scoped(|_: &Resized| Ok(())),
+/// };
+/// assert_eq!(dispatcher.listener_count::<Resized>(), 1);
+///
+/// drop(panel);
+/// assert_eq!(dispatcher.listener_count::<Resized>(), 0);
+///
+/// // Keep a listener past its guard
+/// let guard = SubscriptionGuard::new(&dispatcher, dispatcher.on(|_: &Resized| {}));
+/// guard.detach();
+/// assert_eq!(dispatcher.listener_count::<Resized>(), 1);
+/// ```
+///
+/// A listener dropping its own guard:
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, SubscriptionGuard};
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Debug, Clone)]
+/// struct Closed;
+///
+/// impl Event for Closed {}
+///
+/// let dispatcher = Arc::new(EventDispatcher::new());
+/// let slot: Arc<Mutex<Option<SubscriptionGuard>>> = Arc::default();
+/// let own = slot.clone();
+/// let guard = dispatcher.subscribe_scoped(move |_: &Closed| {
+///     own.lock().unwrap().take();
+///     Ok(())
+/// });
+/// *slot.lock().unwrap() = Some(guard);
+///
+/// assert_eq!(dispatcher.dispatch(Closed).listener_count(), 1);
+/// assert_eq!(dispatcher.listener_count::<Closed>(), 0);
+/// ```
+#[must_use = "dropping the guard unsubscribes the listener immediately"]
+pub struct SubscriptionGuard {
+    dispatcher: Weak<EventDispatcher>,
+    id: ListenerId,
+}
+
+impl SubscriptionGuard {
+    /// Guard the subscription `id` of `dispatcher`
+    pub fn new(dispatcher: &Arc<EventDispatcher>, id: impl Into<ListenerId>) -> Self {
+        Self {
+            dispatcher: Arc::downgrade(dispatcher),
+            id: id.into(),
+        }
+    }
+
+    /// Get the id of the guarded listener
+    pub fn id(&self) -> ListenerId {
+        self.id
+    }
+
+    /// Keep the listener subscribed and give up the guard
+    pub fn detach(mut self) -> ListenerId {
+        self.dispatcher = Weak::new();
+        self.id
+    }
+}
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        if let Some(dispatcher) = self.dispatcher.upgrade() {
+            dispatcher.unsubscribe(self.id);
+        }
+    }
+}
+
+impl fmt::Debug for SubscriptionGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubscriptionGuard")
+            .field("id", &self.id)
+            .field("attached", &(self.dispatcher.strong_count() > 0))
+            .finish()
+    }
+}
@@ -0,0 +1,39 @@
+//! Synchronization primitives used by the dispatcher
+//!
+//! Building with `--cfg loom` swaps the locks and atomics for
+//! [loom](https://docs.rs/loom)'s instrumented versions so the
+//! dispatcher's locking can be model-checked. `Arc` always comes from
+//! `std`, since listeners are stored as unsized trait objects.
+//!
+//! A loom test drives the dispatcher inside `loom::model`:
+//!
+//! ```ignore
+//! #[test]
+//! fn concurrent_subscribe_and_dispatch() {
+//!     loom::model(|| {
+//!         let dispatcher = std::sync::Arc::new(EventDispatcher::new());
+//!         let d = dispatcher.clone();
+//!         let t = loom::thread::spawn(move || {
+//!             d.on(|_: &Tick| {});
+//!         });
+//!         dispatcher.emit(Tick);
+//!         t.join().unwrap();
+//!     });
+//! }
+//! ```
+//!
+//! and is run with the async runtime disabled:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --no-default-features
+//! ```
+
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(loom)]
+pub(crate) use loom::sync::{Condvar, Mutex, RwLock};
+
+#[cfg(not(loom))]
+pub(crate) use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(not(loom))]
+pub(crate) use std::sync::{Condvar, Mutex, RwLock};
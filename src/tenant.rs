@@ -0,0 +1,106 @@
+//! Tenant-scoped dispatch on a shared dispatcher
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::sync::Arc;
+
+/// Tenant a dispatch is made on behalf of
+///
+/// Attach one to a [`DispatchContext`](crate::DispatchContext) and
+/// dispatch with
+/// [`EventDispatcher::dispatch_with_context`](crate::EventDispatcher::dispatch_with_context).
+/// Listeners subscribed with [`ListenerOptions::tenant`](crate::ListenerOptions::tenant)
+/// only see dispatches of their tenant; listeners without a tenant see
+/// every dispatch. Per-tenant totals are reported by
+/// [`EventDispatcher::tenant_metrics`](crate::EventDispatcher::tenant_metrics).
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{DispatchContext, Event, EventDispatcher, ListenerOptions, TenantId};
+///
+/// #[derive(Debug, Clone)]
+/// struct InvoiceCreated;
+///
+/// impl Event for InvoiceCreated {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.on(|_: &InvoiceCreated| println!("shared bookkeeping"));
+/// dispatcher.on_with_options(
+///     |_: &InvoiceCreated| println!("acme's custom webhook"),
+///     ListenerOptions::new().tenant("acme"),
+/// );
+///
+/// let acme = DispatchContext::new().with(TenantId::new("acme"));
+/// let globex = DispatchContext::new().with(TenantId::new("globex"));
+/// assert_eq!(dispatcher.dispatch_with_context(InvoiceCreated, &acme).listener_count(), 2);
+/// assert_eq!(dispatcher.dispatch_with_context(InvoiceCreated, &globex).listener_count(), 1);
+///
+/// let metrics = dispatcher.tenant_metrics("acme").unwrap();
+/// assert_eq!(metrics.dispatch_count, 1);
+/// assert_eq!(metrics.listener_calls, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TenantId(Arc<str>);
+
+impl TenantId {
+    /// Create a tenant id
+    pub fn new(id: impl Into<Arc<str>>) -> Self {
+        Self(id.into())
+    }
+
+    /// Get the id as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Borrow<str> for TenantId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for TenantId {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for TenantId {
+    fn from(id: String) -> Self {
+        Self::new(id)
+    }
+}
+
+/// Dispatch totals of one tenant
+///
+/// Returned by [`EventDispatcher::tenant_metrics`](crate::EventDispatcher::tenant_metrics).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantMetrics {
+    /// Number of dispatches made for the tenant
+    pub dispatch_count: usize,
+    /// Number of those dispatches blocked by middleware
+    pub blocked_count: usize,
+    /// Number of listener invocations
+    pub listener_calls: usize,
+    /// Number of listener errors
+    pub error_count: usize,
+}
+
+impl TenantMetrics {
+    pub(crate) fn record(&mut self, result: &crate::DispatchResult) {
+        self.dispatch_count += 1;
+        if result.is_blocked() {
+            self.blocked_count += 1;
+        }
+        self.listener_calls += result.listener_count();
+        self.error_count += result.error_count();
+    }
+}
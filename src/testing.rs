@@ -0,0 +1,544 @@
+//! Test utilities (requires "test-utils" feature)
+//!
+//! Helpers for unit-testing code that dispatches events. Enable the
+//! feature only for tests:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! mod-events = { version = "0.1", features = ["test-utils"] }
+//! ```
+
+use crate::sync::Mutex;
+use crate::{
+    Dispatch, DispatchResult, Event, EventDispatcher, IntoListenerResult, ListenerId,
+    TypedListenerId,
+};
+use std::fmt;
+use std::path::Path;
+
+/// Ordered record of dispatched events shared by the test dispatchers
+#[derive(Debug, Default)]
+struct Recorder {
+    events: Mutex<Vec<Box<dyn Event>>>,
+}
+
+impl Recorder {
+    fn record<T: Event>(&self, event: T) {
+        self.events.lock().unwrap().push(Box::new(event));
+    }
+
+    fn captured<T: Event + Clone>(&self) -> Vec<T> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|e| e.downcast_ref::<T>())
+            .cloned()
+            .collect()
+    }
+
+    fn count<T: Event>(&self) -> usize {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.is::<T>())
+            .count()
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| e.event_name())
+            .collect()
+    }
+
+    fn clear(&self) {
+        self.events.lock().unwrap().clear();
+    }
+
+    fn snapshot(&self) -> EventSnapshot {
+        let lines = self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(index, event)| format!("{:04} {:?}", index, event))
+            .collect();
+        EventSnapshot { lines }
+    }
+
+    #[track_caller]
+    fn assert_dispatched<T: Event>(&self, predicate: impl Fn(&T) -> bool) {
+        let events = self.events.lock().unwrap();
+        let found = events
+            .iter()
+            .filter_map(|e| e.downcast_ref::<T>())
+            .any(predicate);
+        if !found {
+            panic!(
+                "expected a matching `{}` to be dispatched, captured: {:#?}",
+                std::any::type_name::<T>(),
+                *events
+            );
+        }
+    }
+
+    #[track_caller]
+    fn assert_not_dispatched<T: Event>(&self) {
+        let events = self.events.lock().unwrap();
+        let offending: Vec<&T> = events
+            .iter()
+            .filter_map(|e| e.downcast_ref::<T>())
+            .collect();
+        if !offending.is_empty() {
+            panic!(
+                "expected no `{}` to be dispatched, captured: {:#?}",
+                std::any::type_name::<T>(),
+                offending
+            );
+        }
+    }
+
+    #[track_caller]
+    fn assert_dispatched_times<T: Event>(&self, times: usize) {
+        let count = self.count::<T>();
+        if count != times {
+            panic!(
+                "expected `{}` to be dispatched {} time(s), was dispatched {} time(s)",
+                std::any::type_name::<T>(),
+                times,
+                count
+            );
+        }
+    }
+}
+
+/// Generates the query and assertion methods shared by the test dispatchers
+macro_rules! recorder_methods {
+    () => {
+        /// Get clones of all captured events of type `T`, in dispatch order
+        pub fn captured<T: Event + Clone>(&self) -> Vec<T> {
+            self.recorder.captured()
+        }
+
+        /// Count the captured events of type `T`
+        pub fn captured_count<T: Event>(&self) -> usize {
+            self.recorder.count::<T>()
+        }
+
+        /// Get the names of all captured events, in dispatch order
+        pub fn captured_names(&self) -> Vec<&'static str> {
+            self.recorder.names()
+        }
+
+        /// Forget all captured events
+        pub fn clear_captured(&self) {
+            self.recorder.clear();
+        }
+
+        /// Render the captured events as a stable textual snapshot
+        pub fn snapshot(&self) -> EventSnapshot {
+            self.recorder.snapshot()
+        }
+
+        /// Assert that an event of type `T` matching `predicate` was dispatched
+        ///
+        /// # Panics
+        ///
+        /// Panics, listing the captured events, if no event matches.
+        #[track_caller]
+        pub fn assert_dispatched<T: Event>(&self, predicate: impl Fn(&T) -> bool) {
+            self.recorder.assert_dispatched(predicate);
+        }
+
+        /// Assert that no event of type `T` was dispatched
+        ///
+        /// # Panics
+        ///
+        /// Panics, listing the offending events, if any were captured.
+        #[track_caller]
+        pub fn assert_not_dispatched<T: Event>(&self) {
+            self.recorder.assert_not_dispatched::<T>();
+        }
+
+        /// Assert that exactly `times` events of type `T` were dispatched
+        ///
+        /// # Panics
+        ///
+        /// Panics if the count differs.
+        #[track_caller]
+        pub fn assert_dispatched_times<T: Event>(&self, times: usize) {
+            self.recorder.assert_dispatched_times::<T>(times);
+        }
+    };
+}
+
+/// Dispatcher wrapper that records every event dispatched through it
+///
+/// Listeners still run as usual. Only the dispatching methods of the
+/// wrapper capture events, so it exposes those and the common
+/// subscription methods rather than the whole [`EventDispatcher`];
+/// reach the inner dispatcher with [`dispatcher`](Self::dispatcher) for
+/// other setup. Events are captured after dispatch, so no `Clone` bound
+/// is needed.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::testing::CapturingDispatcher;
+/// use mod_events::Event;
+///
+/// #[derive(Debug, Clone)]
+/// struct OrderPlaced {
+///     order_id: u64,
+/// }
+///
+/// impl Event for OrderPlaced {}
+///
+/// #[derive(Debug, Clone)]
+/// struct OrderCancelled;
+///
+/// impl Event for OrderCancelled {}
+///
+/// let dispatcher = CapturingDispatcher::new();
+/// dispatcher.on(|_: &OrderPlaced| {});
+/// dispatcher.emit(OrderPlaced { order_id: 42 });
+///
+/// dispatcher.assert_dispatched::<OrderPlaced>(|e| e.order_id == 42);
+/// dispatcher.assert_not_dispatched::<OrderCancelled>();
+/// ```
+#[derive(Debug)]
+pub struct CapturingDispatcher {
+    dispatcher: EventDispatcher,
+    recorder: Recorder,
+}
+
+impl Default for CapturingDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CapturingDispatcher {
+    /// Create a capturing dispatcher around a new `EventDispatcher`
+    pub fn new() -> Self {
+        Self::wrap(EventDispatcher::new())
+    }
+
+    /// Capture events dispatched through an existing dispatcher
+    pub fn wrap(dispatcher: EventDispatcher) -> Self {
+        Self {
+            dispatcher,
+            recorder: Recorder::default(),
+        }
+    }
+
+    /// Dispatch an event and record it
+    pub fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        let result = self.dispatcher.dispatch_event(&event);
+        self.recorder.record(event);
+        result
+    }
+
+    /// Dispatch an event asynchronously and record it (requires "async" feature)
+    #[cfg(feature = "async")]
+    pub async fn dispatch_async<T: Event>(&self, event: T) -> DispatchResult {
+        let result = self.dispatcher.dispatch_event_async(&event).await;
+        self.recorder.record(event);
+        result
+    }
+
+    /// Fire and forget, recording the event
+    pub fn emit<T: Event>(&self, event: T) {
+        let _ = self.dispatch(event);
+    }
+
+    /// Subscribe a listener on the inner dispatcher
+    pub fn subscribe<T, F>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        self.dispatcher.subscribe(listener)
+    }
+
+    /// Subscribe a listener on the inner dispatcher, accepting any listener return type
+    pub fn on<T, F, R>(&self, listener: F) -> TypedListenerId<T>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + Sync + 'static,
+        R: IntoListenerResult,
+    {
+        self.dispatcher.on(listener)
+    }
+
+    /// Unsubscribe a listener from the inner dispatcher
+    pub fn unsubscribe(&self, listener_id: impl Into<ListenerId>) -> bool {
+        self.dispatcher.unsubscribe(listener_id)
+    }
+
+    /// Get the inner dispatcher, for setup such as middleware
+    ///
+    /// Events dispatched on it directly are not captured.
+    pub fn dispatcher(&self) -> &EventDispatcher {
+        &self.dispatcher
+    }
+
+    recorder_methods!();
+}
+
+impl Dispatch for CapturingDispatcher {
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        CapturingDispatcher::dispatch(self, event)
+    }
+}
+
+/// [`Dispatch`] implementation that records events without running anything
+///
+/// Use it to test services that emit events in isolation: no listeners or
+/// middleware are involved, and every dispatch reports zero listeners.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::testing::MockDispatcher;
+/// use mod_events::{Dispatch, Event};
+///
+/// #[derive(Debug, Clone)]
+/// struct UserRegistered {
+///     user_id: u64,
+/// }
+///
+/// impl Event for UserRegistered {}
+///
+/// fn register(events: &impl Dispatch, user_id: u64) {
+///     events.emit(UserRegistered { user_id });
+/// }
+///
+/// let mock = MockDispatcher::new();
+/// register(&mock, 7);
+///
+/// mock.assert_dispatched::<UserRegistered>(|e| e.user_id == 7);
+/// assert_eq!(mock.captured::<UserRegistered>()[0].user_id, 7);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockDispatcher {
+    recorder: Recorder,
+}
+
+impl MockDispatcher {
+    /// Create an empty mock dispatcher
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    recorder_methods!();
+}
+
+impl Dispatch for MockDispatcher {
+    fn dispatch<T: Event>(&self, event: T) -> DispatchResult {
+        self.recorder.record(event);
+        DispatchResult::new(Vec::new())
+    }
+}
+
+/// Environment variable that makes golden assertions rewrite their files
+pub const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+/// Stable textual form of a recorded event sequence
+///
+/// Each captured event becomes one line holding its sequence number and
+/// `Debug` representation. Type paths are left out so that moving an event
+/// between modules does not invalidate golden files. Values that change between runs,
+/// such as timestamps and generated ids, can be masked with
+/// [`redact_field`](Self::redact_field) or [`redact`](Self::redact)
+/// before comparing against a checked-in golden file.
+///
+/// Set the `UPDATE_GOLDEN=1` environment variable to (re)write golden
+/// files instead of comparing against them.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::testing::MockDispatcher;
+/// use mod_events::{Dispatch, Event};
+///
+/// #[derive(Debug, Clone)]
+/// struct OrderPlaced {
+///     order_id: u64,
+///     created_at: u64,
+/// }
+///
+/// impl Event for OrderPlaced {}
+///
+/// let mock = MockDispatcher::new();
+/// mock.emit(OrderPlaced { order_id: 42, created_at: 1_700_000_000 });
+///
+/// let snapshot = mock.snapshot().redact_field("created_at", "<timestamp>");
+/// assert_eq!(
+///     snapshot.to_string(),
+///     "0000 OrderPlaced { order_id: 42, created_at: <timestamp> }\n"
+/// );
+///
+/// let golden = std::env::temp_dir().join("mod_events_order_golden.txt");
+/// std::fs::write(&golden, snapshot.to_string()).unwrap();
+/// snapshot.assert_matches_golden(&golden);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSnapshot {
+    lines: Vec<String>,
+}
+
+impl EventSnapshot {
+    /// Get the snapshot lines, one per captured event
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Rewrite every line with a custom redaction function
+    pub fn redact(mut self, redactor: impl Fn(&str) -> String) -> Self {
+        for line in &mut self.lines {
+            *line = redactor(line);
+        }
+        self
+    }
+
+    /// Replace the value of every `field: value` pair named `field`
+    ///
+    /// Works on the `Debug` representation, so nested structs, lists and
+    /// quoted strings are replaced as a whole.
+    pub fn redact_field(self, field: &str, replacement: &str) -> Self {
+        self.redact(|line| redact_debug_field(line, field, replacement))
+    }
+
+    /// Compare against a golden file, or rewrite it when `UPDATE_GOLDEN` is set
+    ///
+    /// # Panics
+    ///
+    /// Panics with a line diff if the snapshot differs from the file, or
+    /// if the file is missing and `UPDATE_GOLDEN` is not set.
+    #[track_caller]
+    pub fn assert_matches_golden(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.to_string();
+
+        if std::env::var_os(UPDATE_GOLDEN_ENV).is_some_and(|v| v != "0") {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+            std::fs::write(path, &actual).unwrap();
+            return;
+        }
+
+        let expected = match std::fs::read_to_string(path) {
+            Ok(expected) => expected,
+            Err(e) => panic!(
+                "golden file {} could not be read ({}); run with {}=1 to create it",
+                path.display(),
+                e,
+                UPDATE_GOLDEN_ENV
+            ),
+        };
+
+        if expected != actual {
+            panic!(
+                "event snapshot differs from golden file {} (run with {}=1 to update):\n{}",
+                path.display(),
+                UPDATE_GOLDEN_ENV,
+                line_diff(&expected, &actual)
+            );
+        }
+    }
+}
+
+impl fmt::Display for EventSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+fn redact_debug_field(line: &str, field: &str, replacement: &str) -> String {
+    let pattern = format!("{}: ", field);
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(pos) = rest.find(&pattern) {
+        let boundary = rest[..pos]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_'));
+        let value_start = pos + pattern.len();
+        out.push_str(&rest[..value_start]);
+        rest = &rest[value_start..];
+        if !boundary {
+            continue;
+        }
+
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut end = rest.len();
+        for (i, c) in rest.char_indices() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                '{' | '[' | '(' => depth += 1,
+                '}' | ']' | ')' if depth == 0 => {
+                    end = i;
+                    break;
+                }
+                '}' | ']' | ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let value = &rest[..end];
+        let trailing_space = value.len() - value.trim_end().len();
+        out.push_str(replacement);
+        out.push_str(&value[value.len() - trailing_space..]);
+        rest = &rest[end..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => {}
+            (e, a) => {
+                if let Some(e) = e {
+                    diff.push_str(&format!("-{}\n", e));
+                }
+                if let Some(a) = a {
+                    diff.push_str(&format!("+{}\n", a));
+                }
+            }
+        }
+    }
+
+    diff
+}
@@ -0,0 +1,210 @@
+//! Chrome trace-event export
+
+use crate::sync::Mutex;
+use crate::{
+    display_type_name, DispatchObserver, DispatchResult, Event, ListenerError, ListenerId,
+};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+
+/// Recorder of dispatch and listener timings in Chrome trace-event format
+///
+/// Register a clone with
+/// [`EventDispatcher::add_observer`](crate::EventDispatcher::add_observer)
+/// and call [`to_json`](Self::to_json) whenever a trace is wanted. Load the
+/// output in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev):
+/// each dispatch appears as a span on the dispatching thread with its
+/// listeners nested inside. Recording grows without bound until
+/// [`clear`](Self::clear) is called.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{ChromeTrace, Event, EventDispatcher};
+///
+/// #[derive(Debug, Clone)]
+/// struct FrameRendered;
+///
+/// impl Event for FrameRendered {}
+///
+/// let dispatcher = EventDispatcher::new();
+/// let trace = ChromeTrace::new();
+/// dispatcher.add_observer(trace.clone());
+/// dispatcher.on(|_: &FrameRendered| std::thread::sleep(std::time::Duration::from_millis(1)));
+///
+/// dispatcher.emit(FrameRendered);
+/// assert_eq!(trace.len(), 2);
+///
+/// let json = trace.to_json();
+/// assert!(json.contains("\"name\":\"FrameRendered\""));
+/// # let path = std::env::temp_dir().join("mod-events-trace.json");
+/// std::fs::write(&path, json).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChromeTrace {
+    inner: Arc<TraceState>,
+}
+
+#[derive(Debug)]
+struct TraceState {
+    origin: Instant,
+    records: Mutex<Vec<TraceRecord>>,
+    threads: Mutex<HashMap<ThreadId, usize>>,
+}
+
+#[derive(Debug)]
+struct TraceRecord {
+    name: String,
+    category: &'static str,
+    start: Duration,
+    duration: Duration,
+    thread: usize,
+    args: Vec<(&'static str, String)>,
+}
+
+impl Default for ChromeTrace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChromeTrace {
+    /// Create an empty trace starting now
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(TraceState {
+                origin: Instant::now(),
+                records: Mutex::new(Vec::new()),
+                threads: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Get the number of recorded spans
+    pub fn len(&self) -> usize {
+        self.inner.records.lock().unwrap().len()
+    }
+
+    /// Check whether no spans have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discard every recorded span
+    pub fn clear(&self) {
+        self.inner.records.lock().unwrap().clear();
+    }
+
+    /// Render the recorded spans as a trace-event JSON document
+    pub fn to_json(&self) -> String {
+        let records = self.inner.records.lock().unwrap();
+        let mut json = String::from("{\"traceEvents\":[");
+        for (index, record) in records.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"name\":");
+            push_json_string(&mut json, &record.name);
+            let _ = write!(
+                json,
+                ",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{},\"args\":{{",
+                record.category,
+                record.start.as_secs_f64() * 1e6,
+                record.duration.as_secs_f64() * 1e6,
+                record.thread,
+            );
+            for (arg, (key, value)) in record.args.iter().enumerate() {
+                if arg > 0 {
+                    json.push(',');
+                }
+                push_json_string(&mut json, key);
+                json.push(':');
+                push_json_string(&mut json, value);
+            }
+            json.push_str("}}");
+        }
+        json.push_str("],\"displayTimeUnit\":\"ms\"}");
+        json
+    }
+
+    fn record(
+        &self,
+        name: String,
+        category: &'static str,
+        elapsed: Duration,
+        args: Vec<(&'static str, String)>,
+    ) {
+        let end = Instant::now();
+        let start = end
+            .checked_sub(elapsed)
+            .unwrap_or(end)
+            .saturating_duration_since(self.inner.origin);
+        let thread = {
+            let mut threads = self.inner.threads.lock().unwrap();
+            let next = threads.len() + 1;
+            *threads.entry(std::thread::current().id()).or_insert(next)
+        };
+        self.inner.records.lock().unwrap().push(TraceRecord {
+            name,
+            category,
+            start,
+            duration: elapsed,
+            thread,
+            args,
+        });
+    }
+}
+
+impl DispatchObserver for ChromeTrace {
+    fn on_dispatch(&self, event: &dyn Event, result: &DispatchResult, elapsed: Duration) {
+        let mut args = vec![
+            ("listeners", result.listener_count().to_string()),
+            ("errors", result.error_count().to_string()),
+        ];
+        if let Some(reason) = result.block_reason() {
+            args.push(("blocked", reason.to_string()));
+        }
+        self.record(
+            display_type_name(event.event_name()),
+            "dispatch",
+            elapsed,
+            args,
+        );
+    }
+
+    fn on_listener(
+        &self,
+        _event: &dyn Event,
+        listener: ListenerId,
+        name: &'static str,
+        result: &Result<(), ListenerError>,
+        elapsed: Duration,
+    ) {
+        let mut args = vec![("listener", listener.to_string())];
+        if let Err(error) = result {
+            args.push(("error", error.to_string()));
+        }
+        self.record(display_type_name(name), "listener", elapsed, args);
+    }
+}
+
+pub(crate) fn push_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
@@ -0,0 +1,116 @@
+//! Validation middleware
+
+use crate::{DispatchContext, DynamicEvent, Event, Middleware};
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// Invariants an event must satisfy before it is dispatched
+pub trait Validate: Event {
+    /// Check the event, returning a description of the violation on failure
+    fn validate(&self) -> Result<(), String>;
+}
+
+type Validator = Box<dyn Fn(&dyn Event) -> Result<(), String> + Send + Sync>;
+type NamedValidator = Box<dyn Fn(&DynamicEvent) -> Result<(), String> + Send + Sync>;
+
+/// Middleware that blocks events violating their invariants
+///
+/// Typed events are checked through their [`Validate`] impl once
+/// registered with [`validate`](Self::validate). [`DynamicEvent`]s are
+/// checked by name with [`validate_named`](Self::validate_named). Events
+/// without a registered validator pass through unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use mod_events::{Event, EventDispatcher, Validate, ValidationMiddleware};
+///
+/// #[derive(Debug, Clone)]
+/// struct Transfer {
+///     amount: i64,
+/// }
+///
+/// impl Event for Transfer {}
+///
+/// impl Validate for Transfer {
+///     fn validate(&self) -> Result<(), String> {
+///         if self.amount > 0 {
+///             Ok(())
+///         } else {
+///             Err(format!("amount must be positive, got {}", self.amount))
+///         }
+///     }
+/// }
+///
+/// let dispatcher = EventDispatcher::new();
+/// dispatcher.add_middleware_layer(ValidationMiddleware::new().validate::<Transfer>());
+/// dispatcher.on(|transfer: &Transfer| assert!(transfer.amount > 0));
+///
+/// let result = dispatcher.dispatch(Transfer { amount: -5 });
+/// assert_eq!(
+///     result.block_reason().unwrap().reason,
+///     "amount must be positive, got -5"
+/// );
+/// assert!(dispatcher.dispatch(Transfer { amount: 5 }).all_succeeded());
+/// ```
+#[derive(Default)]
+pub struct ValidationMiddleware {
+    typed: HashMap<TypeId, Validator>,
+    named: HashMap<String, NamedValidator>,
+}
+
+impl std::fmt::Debug for ValidationMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationMiddleware")
+            .field("typed_count", &self.typed.len())
+            .field("named", &self.named.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ValidationMiddleware {
+    /// Create middleware with no validators
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check events of type `T` with their [`Validate`] impl
+    pub fn validate<T: Validate>(mut self) -> Self {
+        self.typed.insert(
+            TypeId::of::<T>(),
+            Box::new(|event| match event.downcast_ref::<T>() {
+                Some(event) => event.validate(),
+                None => Ok(()),
+            }),
+        );
+        self
+    }
+
+    /// Check [`DynamicEvent`]s named `name` with `validator`
+    ///
+    /// The validator typically decodes the payload and checks it against
+    /// the schema the event's producers agreed on.
+    pub fn validate_named<F>(mut self, name: impl Into<String>, validator: F) -> Self
+    where
+        F: Fn(&DynamicEvent) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.named.insert(name.into(), Box::new(validator));
+        self
+    }
+}
+
+impl Middleware for ValidationMiddleware {
+    fn process(&self, event: &dyn Event, _context: &DispatchContext) -> Result<(), String> {
+        if let Some(validator) = self.typed.get(&event.as_any().type_id()) {
+            return validator(event);
+        }
+
+        match event.downcast_ref::<DynamicEvent>() {
+            Some(dynamic) => match self.named.get(dynamic.name()) {
+                Some(validator) => validator(dynamic),
+                None => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Axum integration (requires "axum" feature)
+//!
+//! [`Dispatcher`] extracts the shared dispatcher in handlers, and
+//! [`EventStream`] streams selected event types to web clients as
+//! server-sent events.
+
+use crate::{EventDispatcher, EventSchema, ListenerId};
+use ::axum::extract::{FromRef, FromRequestParts};
+use ::axum::http::request::Parts;
+use ::axum::response::sse::{Event as SseEvent, KeepAlive, KeepAliveStream, Sse};
+use futures_core::Stream;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// Extractor giving handlers the application's dispatcher
+///
+/// Works with any router state from which an `Arc<EventDispatcher>` can
+/// be taken via [`FromRef`], including `Arc<EventDispatcher>` itself.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{routing::post, Router};
+/// use mod_events::web::Dispatcher;
+/// use mod_events::{Event, EventDispatcher};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone)]
+/// struct Pinged;
+///
+/// impl Event for Pinged {}
+///
+/// async fn ping(Dispatcher(dispatcher): Dispatcher) -> &'static str {
+///     dispatcher.emit(Pinged);
+///     "pong"
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/ping", post(ping))
+///     .with_state(Arc::new(EventDispatcher::new()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Dispatcher(pub Arc<EventDispatcher>);
+
+impl std::ops::Deref for Dispatcher {
+    type Target = EventDispatcher;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S> FromRequestParts<S> for Dispatcher
+where
+    Arc<EventDispatcher>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(_parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(Arc::from_ref(state)))
+    }
+}
+
+/// Server-sent event stream of selected event types
+///
+/// Each selected event is sent with its [`EventSchema::NAME`] as the SSE
+/// event name and its JSON encoding as the data. The stream buffers up to
+/// its capacity for a slow client and drops events beyond that. Dropping
+/// the stream, which axum does when the client disconnects, unsubscribes
+/// its listeners.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{extract::State, routing::get, Router};
+/// use mod_events::web::EventStream;
+/// use mod_events::{Event, EventDispatcher, EventSchema};
+/// use serde::Serialize;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone, Serialize)]
+/// struct PriceChanged {
+///     symbol: String,
+///     cents: u64,
+/// }
+///
+/// impl Event for PriceChanged {}
+///
+/// impl EventSchema for PriceChanged {
+///     const NAME: &'static str = "price.changed";
+/// }
+///
+/// async fn prices(State(dispatcher): State<Arc<EventDispatcher>>) -> impl axum::response::IntoResponse {
+///     EventStream::new(dispatcher).event::<PriceChanged>().into_sse()
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/prices", get(prices))
+///     .with_state(Arc::new(EventDispatcher::new()));
+/// ```
+#[derive(Debug)]
+pub struct EventStream {
+    dispatcher: Arc<EventDispatcher>,
+    sender: mpsc::Sender<SseEvent>,
+    receiver: mpsc::Receiver<SseEvent>,
+    listeners: Vec<ListenerId>,
+}
+
+impl EventStream {
+    /// Default number of events buffered for a slow client
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Create a stream with the default buffer capacity
+    pub fn new(dispatcher: Arc<EventDispatcher>) -> Self {
+        Self::with_capacity(dispatcher, Self::DEFAULT_CAPACITY)
+    }
+
+    /// Create a stream buffering up to `capacity` events
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(dispatcher: Arc<EventDispatcher>, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::channel(capacity);
+        Self {
+            dispatcher,
+            sender,
+            receiver,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Include events of type `T` in the stream
+    pub fn event<T>(mut self) -> Self
+    where
+        T: EventSchema + serde::Serialize + 'static,
+    {
+        let sender = self.sender.clone();
+        let id = self.dispatcher.subscribe(move |event: &T| {
+            let data = serde_json::to_string(event)?;
+            let _ = sender.try_send(SseEvent::default().event(T::NAME).data(data));
+            Ok(())
+        });
+        self.listeners.push(id.into());
+        self
+    }
+
+    /// Wrap the stream in an SSE response with default keep-alive pings
+    pub fn into_sse(self) -> Sse<KeepAliveStream<Self>> {
+        Sse::new(self).keep_alive(KeepAlive::default())
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<SseEvent, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx).map(|event| event.map(Ok))
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        for id in self.listeners.drain(..) {
+            self.dispatcher.unsubscribe(id);
+        }
+    }
+}